@@ -0,0 +1,2 @@
+-- Add migration script here
+ALTER TABLE rooms ADD COLUMN passcode_hash TEXT;
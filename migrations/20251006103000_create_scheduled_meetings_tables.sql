@@ -0,0 +1,42 @@
+-- Add migration script here
+CREATE TABLE scheduled_meetings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    title TEXT NOT NULL,
+    description TEXT,
+    created_by INTEGER NOT NULL,
+    starts_at TEXT NOT NULL,
+    ends_at TEXT NOT NULL,
+    timezone TEXT NOT NULL,
+    rrule TEXT,
+    reminder_minutes INTEGER NOT NULL DEFAULT 10,
+    is_cancelled BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    FOREIGN KEY (created_by) REFERENCES users(id)
+);
+
+CREATE INDEX idx_scheduled_meetings_created_by ON scheduled_meetings(created_by);
+
+CREATE TABLE meeting_attendees (
+    meeting_id INTEGER NOT NULL REFERENCES scheduled_meetings(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    PRIMARY KEY (meeting_id, user_id)
+);
+
+CREATE INDEX idx_meeting_attendees_user_id ON meeting_attendees(user_id);
+
+CREATE TABLE meeting_occurrences (
+    meeting_id INTEGER NOT NULL REFERENCES scheduled_meetings(id) ON DELETE CASCADE,
+    occurrence_start TEXT NOT NULL,
+    room_id TEXT NOT NULL REFERENCES rooms(id),
+    reminder_sent_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    PRIMARY KEY (meeting_id, occurrence_start)
+);
+
+CREATE INDEX idx_meeting_occurrences_room_id ON meeting_occurrences(room_id);
+
+CREATE TABLE calendar_feed_tokens (
+    user_id INTEGER PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    token TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP)
+);
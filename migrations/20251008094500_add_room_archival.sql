@@ -0,0 +1,6 @@
+-- Add migration script here
+ALTER TABLE rooms ADD COLUMN archived_at TEXT;
+
+ALTER TABLE users ADD COLUMN is_admin BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_rooms_archived_at ON rooms(archived_at);
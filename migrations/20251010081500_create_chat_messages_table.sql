@@ -0,0 +1,12 @@
+-- Add migration script here
+CREATE TABLE chat_messages (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    room_id TEXT NOT NULL REFERENCES rooms(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id),
+    message TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    edited_at TEXT,
+    deleted_at TEXT
+);
+
+CREATE INDEX idx_chat_messages_room_id ON chat_messages(room_id, id);
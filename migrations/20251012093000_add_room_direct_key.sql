@@ -0,0 +1,4 @@
+-- Add migration script here
+ALTER TABLE rooms ADD COLUMN direct_key TEXT;
+
+CREATE UNIQUE INDEX idx_rooms_direct_key ON rooms(direct_key) WHERE direct_key IS NOT NULL;
@@ -0,0 +1,10 @@
+-- Add migration script here
+ALTER TABLE rooms ADD COLUMN chat_policy TEXT NOT NULL DEFAULT 'participant';
+
+ALTER TABLE rooms ADD COLUMN unmute_policy TEXT NOT NULL DEFAULT 'participant';
+
+ALTER TABLE rooms ADD COLUMN screen_share_policy TEXT NOT NULL DEFAULT 'participant';
+
+ALTER TABLE rooms ADD COLUMN start_call_policy TEXT NOT NULL DEFAULT 'participant';
+
+ALTER TABLE rooms ADD COLUMN invite_policy TEXT NOT NULL DEFAULT 'participant';
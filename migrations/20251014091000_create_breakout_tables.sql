@@ -0,0 +1,30 @@
+-- Add migration script here
+CREATE TABLE breakout_sessions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    room_id TEXT NOT NULL REFERENCES rooms(id) ON DELETE CASCADE,
+    created_by INTEGER NOT NULL REFERENCES users(id),
+    status TEXT NOT NULL DEFAULT 'pending',
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    opened_at TEXT,
+    closes_at TEXT,
+    closed_at TEXT
+);
+
+-- Only one breakout session per meeting room may be pending or open at a time
+CREATE UNIQUE INDEX idx_breakout_sessions_current
+    ON breakout_sessions(room_id) WHERE status != 'closed';
+
+CREATE TABLE breakout_rooms (
+    session_id INTEGER NOT NULL REFERENCES breakout_sessions(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    room_id TEXT REFERENCES rooms(id) ON DELETE SET NULL,
+    PRIMARY KEY (session_id, position)
+);
+
+CREATE TABLE breakout_assignments (
+    session_id INTEGER NOT NULL REFERENCES breakout_sessions(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id),
+    position INTEGER NOT NULL,
+    PRIMARY KEY (session_id, user_id)
+);
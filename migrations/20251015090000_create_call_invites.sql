@@ -0,0 +1,18 @@
+-- Add migration script here
+CREATE TABLE call_invites (
+    call_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    invited_by INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'ringing' CHECK (status IN ('ringing', 'accepted', 'declined', 'missed')),
+    invited_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    expires_at TEXT NOT NULL,
+    responded_at TEXT,
+    PRIMARY KEY (call_id, user_id),
+    FOREIGN KEY (call_id) REFERENCES calls(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id),
+    FOREIGN KEY (invited_by) REFERENCES users(id)
+);
+
+CREATE INDEX idx_call_invites_user_status ON call_invites(user_id, status);
+
+CREATE INDEX idx_call_invites_ringing_expiry ON call_invites(expires_at) WHERE status = 'ringing';
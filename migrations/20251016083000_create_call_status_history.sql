@@ -0,0 +1,13 @@
+-- Add migration script here
+CREATE TABLE call_status_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    call_id INTEGER NOT NULL,
+    from_status TEXT CHECK (from_status IN ('initiated', 'ringing', 'active', 'ended', 'missed', 'rejected', 'failed')),
+    to_status TEXT NOT NULL CHECK (to_status IN ('initiated', 'ringing', 'active', 'ended', 'missed', 'rejected', 'failed')),
+    changed_by INTEGER,
+    changed_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    FOREIGN KEY (call_id) REFERENCES calls(id) ON DELETE CASCADE,
+    FOREIGN KEY (changed_by) REFERENCES users(id)
+);
+
+CREATE INDEX idx_call_status_history_call_id ON call_status_history(call_id, id);
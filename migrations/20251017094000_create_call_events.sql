@@ -0,0 +1,21 @@
+-- Add migration script here
+CREATE TABLE call_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    call_id INTEGER NOT NULL,
+    event_type TEXT NOT NULL CHECK (event_type IN (
+        'joined', 'left', 'reconnected', 'muted', 'unmuted', 'video_on', 'video_off',
+        'screen_share_started', 'screen_share_stopped', 'chat_sent', 'moderator_action',
+        'status_changed'
+    )),
+    actor_id INTEGER,
+    subject_id INTEGER,
+    details TEXT,
+    created_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    FOREIGN KEY (call_id) REFERENCES calls(id) ON DELETE CASCADE,
+    FOREIGN KEY (actor_id) REFERENCES users(id),
+    FOREIGN KEY (subject_id) REFERENCES users(id)
+);
+
+CREATE INDEX idx_call_events_call_id ON call_events(call_id, id);
+
+CREATE INDEX idx_call_events_call_type ON call_events(call_id, event_type);
@@ -0,0 +1,23 @@
+-- Add migration script here
+CREATE TABLE call_participant_sessions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    call_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    joined_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    left_at TEXT,
+    duration INTEGER,
+    FOREIGN KEY (call_id) REFERENCES calls(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+CREATE INDEX idx_call_participant_sessions_call_user ON call_participant_sessions(call_id, user_id, joined_at);
+
+CREATE UNIQUE INDEX idx_call_participant_sessions_open
+    ON call_participant_sessions(call_id, user_id)
+    WHERE left_at IS NULL;
+
+INSERT INTO call_participant_sessions (call_id, user_id, joined_at, left_at, duration)
+SELECT call_id, user_id, joined_at, left_at, duration
+FROM call_participants p
+WHERE EXISTS (SELECT 1 FROM calls c WHERE c.id = p.call_id)
+    AND EXISTS (SELECT 1 FROM users u WHERE u.id = p.user_id);
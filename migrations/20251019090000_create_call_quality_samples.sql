@@ -0,0 +1,17 @@
+-- Add migration script here
+CREATE TABLE call_quality_samples (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    call_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    rtt_ms REAL,
+    jitter_ms REAL,
+    packet_loss_pct REAL,
+    bitrate_kbps REAL,
+    frame_rate REAL,
+    candidate_type TEXT CHECK (candidate_type IN ('host', 'srflx', 'prflx', 'relay')),
+    recorded_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    FOREIGN KEY (call_id) REFERENCES calls(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+CREATE INDEX idx_call_quality_samples_call_user ON call_quality_samples(call_id, user_id, recorded_at);
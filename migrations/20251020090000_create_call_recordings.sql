@@ -0,0 +1,27 @@
+-- Add migration script here
+CREATE TABLE call_recordings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    call_id INTEGER NOT NULL UNIQUE,
+    recorded_by INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'uploading' CHECK (status IN ('uploading', 'ready')),
+    mime_type TEXT NOT NULL,
+    file_name TEXT,
+    size_bytes INTEGER NOT NULL DEFAULT 0,
+    duration_secs INTEGER,
+    created_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    completed_at TEXT,
+    FOREIGN KEY (call_id) REFERENCES calls(id) ON DELETE CASCADE,
+    FOREIGN KEY (recorded_by) REFERENCES users(id)
+);
+
+CREATE INDEX idx_call_recordings_status_created ON call_recordings(status, created_at);
+
+CREATE TABLE call_recording_chunks (
+    recording_id INTEGER NOT NULL,
+    chunk_index INTEGER NOT NULL,
+    file_name TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    uploaded_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    PRIMARY KEY (recording_id, chunk_index),
+    FOREIGN KEY (recording_id) REFERENCES call_recordings(id) ON DELETE CASCADE
+);
@@ -0,0 +1,12 @@
+-- Add migration script here
+CREATE TABLE call_recording_consents (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    call_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    accepted BOOLEAN NOT NULL,
+    decided_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    FOREIGN KEY (call_id) REFERENCES calls(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+CREATE INDEX idx_call_recording_consents_call ON call_recording_consents(call_id, decided_at);
@@ -0,0 +1,20 @@
+-- Add migration script here
+CREATE TABLE call_feedback (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    call_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    rating INTEGER NOT NULL CHECK (rating BETWEEN 1 AND 5),
+    comment TEXT,
+    created_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    updated_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    UNIQUE (call_id, user_id),
+    FOREIGN KEY (call_id) REFERENCES calls(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+CREATE TABLE call_feedback_issues (
+    feedback_id INTEGER NOT NULL,
+    issue TEXT NOT NULL CHECK (issue IN ('echo', 'frozen_video', 'dropped')),
+    PRIMARY KEY (feedback_id, issue),
+    FOREIGN KEY (feedback_id) REFERENCES call_feedback(id) ON DELETE CASCADE
+);
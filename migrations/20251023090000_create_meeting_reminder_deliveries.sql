@@ -0,0 +1,8 @@
+-- Add migration script here
+CREATE TABLE meeting_reminder_deliveries (
+    meeting_id INTEGER NOT NULL REFERENCES scheduled_meetings(id) ON DELETE CASCADE,
+    occurrence_start TEXT NOT NULL,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    delivered_at TEXT NOT NULL DEFAULT (CURRENT_TIMESTAMP),
+    PRIMARY KEY (meeting_id, occurrence_start, user_id)
+);
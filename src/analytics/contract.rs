@@ -0,0 +1,32 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::analytics::filter::{AnalyticsFilter, GroupBy};
+
+#[derive(Deserialize)]
+pub struct AnalyticsParams {
+    /// First day of the range, inclusive. Defaults to 30 days before `to`.
+    pub from: Option<NaiveDate>,
+    /// Last day of the range, inclusive. Defaults to today.
+    pub to: Option<NaiveDate>,
+    pub group_by: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnalyticsReport<T> {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub group_by: GroupBy,
+    pub rows: Vec<T>,
+}
+
+impl<T> AnalyticsReport<T> {
+    pub fn new(filter: &AnalyticsFilter, rows: Vec<T>) -> Self {
+        Self {
+            from: filter.from,
+            to: filter.to,
+            group_by: filter.group_by,
+            rows,
+        }
+    }
+}
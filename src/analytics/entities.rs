@@ -0,0 +1,79 @@
+use serde::Serialize;
+use sqlx::FromRow;
+
+/// Rows are keyed by the report's `group_by`: a user id, room id, day
+/// (`YYYY-MM-DD`), weekday (`0` is Sunday), room type or duration band
+/// (`0` is unfinished calls, `1` the shortest band). `key` and `label` are empty
+/// when the report is not grouped; `label` carries a user or room name.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct CallMinutesRow {
+    pub key: Option<String>,
+    pub label: Option<String>,
+    pub calls: i64,
+    pub total_seconds: i64,
+    pub total_minutes: f64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct CallDurationRow {
+    pub key: Option<String>,
+    pub label: Option<String>,
+    pub calls: i64,
+    pub avg_duration_secs: f64,
+    pub max_duration_secs: i64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct BusyHourRow {
+    pub key: Option<String>,
+    pub label: Option<String>,
+    /// Hour of the day, 0-23, in the server's stored (UTC) time.
+    pub hour: i64,
+    pub calls: i64,
+    pub total_minutes: f64,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct MissedCallRow {
+    pub key: Option<String>,
+    pub label: Option<String>,
+    pub calls: i64,
+    pub missed: i64,
+    pub rejected: i64,
+    pub missed_rate: f64,
+}
+
+/// Ratings participants left, with how long the rated calls lasted.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct FeedbackRow {
+    pub key: Option<String>,
+    pub label: Option<String>,
+    pub responses: i64,
+    pub average_rating: f64,
+    /// Ratings of 1 or 2.
+    pub low_ratings: i64,
+    pub echo: i64,
+    pub frozen_video: i64,
+    pub dropped: i64,
+    pub avg_call_duration_secs: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PeakConcurrencyRow {
+    pub key: Option<String>,
+    pub label: Option<String>,
+    pub peak_calls: i64,
+    pub peak_calls_at: Option<String>,
+    pub peak_participants: i64,
+    pub peak_participants_at: Option<String>,
+}
+
+/// When a call or a participant session was live; `ended_at` is empty while
+/// it still is.
+#[derive(Debug, Clone, FromRow)]
+pub struct ActivityInterval {
+    pub key: Option<String>,
+    pub label: Option<String>,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+}
@@ -0,0 +1,204 @@
+use std::str::FromStr;
+
+use chrono::{Days, NaiveDate};
+use serde::Serialize;
+
+use crate::{analytics::contract::AnalyticsParams, shared::response::AppError};
+
+pub const DEFAULT_RANGE_DAYS: u64 = 30;
+pub const MAX_RANGE_DAYS: i64 = 366;
+
+/// How analytics rows are bucketed. Calls are attributed to the day, hour
+/// and room they started in.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    None,
+    User,
+    Room,
+    Day,
+    Weekday,
+    RoomType,
+    /// Bands of call length, from under 5 minutes to over an hour.
+    Duration,
+}
+
+/// SQL bucketing `c.duration` into the bands used by `GroupBy::Duration`;
+/// keys sort from shortest to longest.
+const DURATION_BAND_KEY: &str = "CASE \
+    WHEN c.duration IS NULL THEN '0' \
+    WHEN c.duration < 300 THEN '1' \
+    WHEN c.duration < 900 THEN '2' \
+    WHEN c.duration < 1800 THEN '3' \
+    WHEN c.duration < 3600 THEN '4' \
+    ELSE '5' END";
+
+const DURATION_BAND_LABEL: &str = "CASE \
+    WHEN c.duration IS NULL THEN 'unfinished' \
+    WHEN c.duration < 300 THEN 'under 5 min' \
+    WHEN c.duration < 900 THEN '5-15 min' \
+    WHEN c.duration < 1800 THEN '15-30 min' \
+    WHEN c.duration < 3600 THEN '30-60 min' \
+    ELSE 'over 60 min' END";
+
+impl GroupBy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::User => "user",
+            Self::Room => "room",
+            Self::Day => "day",
+            Self::Weekday => "weekday",
+            Self::RoomType => "room_type",
+            Self::Duration => "duration",
+        }
+    }
+
+    /// The SQL expressions for a row's key and label, given the alias of the
+    /// user column to group by (`u`) and the timestamp the row started at.
+    /// Calls are `c` and rooms are joined as `r`.
+    pub fn key_columns(&self, user_column: &str, started_at: &str) -> (String, String) {
+        match self {
+            Self::None => ("NULL".into(), "NULL".into()),
+            Self::User => (
+                format!("CAST({} AS TEXT)", user_column),
+                "u.first_name || ' ' || u.last_name".into(),
+            ),
+            Self::Room => ("c.room_id".into(), "r.name".into()),
+            Self::Day => (format!("date({})", started_at), "NULL".into()),
+            Self::Weekday => (
+                format!("strftime('%w', {})", started_at),
+                format!(
+                    "CASE strftime('%w', {}) WHEN '0' THEN 'Sunday' WHEN '1' THEN 'Monday' \
+                     WHEN '2' THEN 'Tuesday' WHEN '3' THEN 'Wednesday' WHEN '4' THEN 'Thursday' \
+                     WHEN '5' THEN 'Friday' ELSE 'Saturday' END",
+                    started_at
+                ),
+            ),
+            Self::RoomType => ("r.room_type".into(), "NULL".into()),
+            Self::Duration => (DURATION_BAND_KEY.into(), DURATION_BAND_LABEL.into()),
+        }
+    }
+}
+
+impl FromStr for GroupBy {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "user" => Ok(Self::User),
+            "room" => Ok(Self::Room),
+            "day" => Ok(Self::Day),
+            "weekday" => Ok(Self::Weekday),
+            "room_type" => Ok(Self::RoomType),
+            "duration" => Ok(Self::Duration),
+            _ => Err(AppError::Validation(format!("Invalid group_by '{}'", s))),
+        }
+    }
+}
+
+/// A validated analytics query covering whole days, `from` through `to`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalyticsFilter {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub group_by: GroupBy,
+}
+
+impl AnalyticsFilter {
+    /// `allowed` lists the groupings the report supports, the first being
+    /// the default.
+    pub fn from_params(
+        params: AnalyticsParams,
+        allowed: &[GroupBy],
+        today: NaiveDate,
+    ) -> Result<Self, AppError> {
+        let to = params.to.unwrap_or(today);
+        let from = params.from.unwrap_or_else(|| {
+            to.checked_sub_days(Days::new(DEFAULT_RANGE_DAYS - 1))
+                .unwrap_or(to)
+        });
+
+        if from > to {
+            return Err(AppError::Validation("from must not be after to".into()));
+        }
+        if (to - from).num_days() >= MAX_RANGE_DAYS {
+            return Err(AppError::Validation(format!(
+                "Date range cannot be longer than {} days",
+                MAX_RANGE_DAYS
+            )));
+        }
+
+        let group_by = match params.group_by.as_deref() {
+            None => allowed[0],
+            Some(value) => {
+                let group_by = value.parse::<GroupBy>()?;
+                if !allowed.contains(&group_by) {
+                    let names: Vec<&str> = allowed.iter().map(GroupBy::as_str).collect();
+                    return Err(AppError::Validation(format!(
+                        "This report can only be grouped by: {}",
+                        names.join(", ")
+                    )));
+                }
+                group_by
+            }
+        };
+
+        Ok(Self { from, to, group_by })
+    }
+
+    /// Inclusive lower bound, comparable with stored timestamps.
+    pub fn start_bound(&self) -> String {
+        format!("{} 00:00:00", self.from)
+    }
+
+    /// Exclusive upper bound: midnight after `to`.
+    pub fn end_bound(&self) -> String {
+        let next = self.to.succ_opt().unwrap_or(self.to);
+        format!("{} 00:00:00", next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(from: Option<&str>, to: Option<&str>, group_by: Option<&str>) -> AnalyticsParams {
+        AnalyticsParams {
+            from: from.map(|d| d.parse().unwrap()),
+            to: to.map(|d| d.parse().unwrap()),
+            group_by: group_by.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_filter_defaults_and_validation() {
+        let today: NaiveDate = "2025-10-31".parse().unwrap();
+        let allowed = [GroupBy::Day, GroupBy::Room];
+
+        let filter =
+            AnalyticsFilter::from_params(params(None, None, None), &allowed, today).unwrap();
+        assert_eq!(filter.from.to_string(), "2025-10-02");
+        assert_eq!(filter.group_by, GroupBy::Day);
+        assert_eq!(filter.start_bound(), "2025-10-02 00:00:00");
+        assert_eq!(filter.end_bound(), "2025-11-01 00:00:00");
+
+        let room = params(Some("2025-10-01"), Some("2025-10-01"), Some("room"));
+        assert_eq!(
+            AnalyticsFilter::from_params(room, &allowed, today)
+                .unwrap()
+                .group_by,
+            GroupBy::Room
+        );
+
+        for bad in [
+            params(Some("2025-10-05"), Some("2025-10-01"), None),
+            params(Some("2024-01-01"), Some("2025-10-01"), None),
+            params(None, None, Some("user")),
+            params(None, None, Some("month")),
+        ] {
+            assert!(AnalyticsFilter::from_params(bad, &allowed, today).is_err());
+        }
+    }
+}
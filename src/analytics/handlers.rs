@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use actix_identity::Identity;
+use actix_web::{HttpResponse, Result as ActixResult, get, web};
+
+use crate::{
+    analytics::{AnalyticsService, contract::AnalyticsParams},
+    shared::response::{AppError, respond_ok},
+};
+
+#[get("/minutes")]
+pub async fn call_minutes(
+    query: web::Query<AnalyticsParams>,
+    identity: Identity,
+    analytics_service: web::Data<Arc<dyn AnalyticsService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let report = analytics_service
+        .call_minutes(user_id, query.into_inner())
+        .await?;
+    respond_ok(report)
+}
+
+#[get("/durations")]
+pub async fn call_durations(
+    query: web::Query<AnalyticsParams>,
+    identity: Identity,
+    analytics_service: web::Data<Arc<dyn AnalyticsService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let report = analytics_service
+        .call_durations(user_id, query.into_inner())
+        .await?;
+    respond_ok(report)
+}
+
+#[get("/peaks")]
+pub async fn peak_concurrency(
+    query: web::Query<AnalyticsParams>,
+    identity: Identity,
+    analytics_service: web::Data<Arc<dyn AnalyticsService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let report = analytics_service
+        .peak_concurrency(user_id, query.into_inner())
+        .await?;
+    respond_ok(report)
+}
+
+#[get("/busiest-hours")]
+pub async fn busiest_hours(
+    query: web::Query<AnalyticsParams>,
+    identity: Identity,
+    analytics_service: web::Data<Arc<dyn AnalyticsService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let report = analytics_service
+        .busiest_hours(user_id, query.into_inner())
+        .await?;
+    respond_ok(report)
+}
+
+#[get("/missed-calls")]
+pub async fn missed_calls(
+    query: web::Query<AnalyticsParams>,
+    identity: Identity,
+    analytics_service: web::Data<Arc<dyn AnalyticsService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let report = analytics_service
+        .missed_calls(user_id, query.into_inner())
+        .await?;
+    respond_ok(report)
+}
+
+#[get("/feedback")]
+pub async fn feedback(
+    query: web::Query<AnalyticsParams>,
+    identity: Identity,
+    analytics_service: web::Data<Arc<dyn AnalyticsService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let report = analytics_service
+        .feedback(user_id, query.into_inner())
+        .await?;
+    respond_ok(report)
+}
@@ -0,0 +1,11 @@
+pub mod contract;
+pub mod entities;
+pub mod filter;
+pub mod handlers;
+pub mod peaks;
+pub mod repository;
+pub mod routes;
+pub mod service;
+
+pub use repository::{AnalyticsRepository, SqliteAnalyticsRepository};
+pub use service::{AnalyticsService, AnalyticsServiceImpl};
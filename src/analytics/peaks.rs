@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use crate::analytics::entities::{ActivityInterval, PeakConcurrencyRow};
+
+/// The most intervals live at once in each group, with the moment that level
+/// was first reached. Something ending at the same instant another starts is
+/// not counted as overlapping, and intervals still open never end.
+fn peak_by_key(intervals: &[ActivityInterval]) -> BTreeMap<Option<String>, (i64, Option<String>)> {
+    let mut changes: BTreeMap<Option<String>, Vec<(&str, i64)>> = BTreeMap::new();
+    for interval in intervals {
+        let entry = changes.entry(interval.key.clone()).or_default();
+        entry.push((interval.started_at.as_str(), 1));
+        if let Some(ended_at) = interval.ended_at.as_deref() {
+            entry.push((ended_at, -1));
+        }
+    }
+
+    changes
+        .into_iter()
+        .map(|(key, mut changes)| {
+            changes.sort();
+            let (mut live, mut peak, mut peak_at) = (0, 0, None);
+            for (at, delta) in changes {
+                live += delta;
+                if live > peak {
+                    peak = live;
+                    peak_at = Some(at.to_string());
+                }
+            }
+            (key, (peak, peak_at))
+        })
+        .collect()
+}
+
+/// Combines the peaks of calls and of participant sessions into one row per
+/// group.
+pub fn peak_concurrency(
+    calls: &[ActivityInterval],
+    sessions: &[ActivityInterval],
+) -> Vec<PeakConcurrencyRow> {
+    let mut labels: BTreeMap<Option<String>, Option<String>> = BTreeMap::new();
+    for interval in calls.iter().chain(sessions) {
+        labels
+            .entry(interval.key.clone())
+            .or_insert_with(|| interval.label.clone());
+    }
+
+    let call_peaks = peak_by_key(calls);
+    let session_peaks = peak_by_key(sessions);
+
+    labels
+        .into_iter()
+        .map(|(key, label)| {
+            let (peak_calls, peak_calls_at) = call_peaks.get(&key).cloned().unwrap_or_default();
+            let (peak_participants, peak_participants_at) =
+                session_peaks.get(&key).cloned().unwrap_or_default();
+            PeakConcurrencyRow {
+                key,
+                label,
+                peak_calls,
+                peak_calls_at,
+                peak_participants,
+                peak_participants_at,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(key: &str, started_at: &str, ended_at: Option<&str>) -> ActivityInterval {
+        ActivityInterval {
+            key: Some(key.to_string()),
+            label: None,
+            started_at: format!("2025-10-18 {}", started_at),
+            ended_at: ended_at.map(|t| format!("2025-10-18 {}", t)),
+        }
+    }
+
+    #[test]
+    fn test_peak_concurrency_per_group() {
+        let calls = vec![
+            interval("a", "09:00:00", Some("10:00:00")),
+            interval("a", "09:30:00", Some("09:45:00")),
+            // Starts as the first call ends, so it does not overlap it
+            interval("a", "10:00:00", None),
+            interval("b", "11:00:00", Some("11:05:00")),
+        ];
+        let sessions = vec![
+            interval("a", "09:00:00", Some("09:50:00")),
+            interval("a", "09:01:00", Some("09:40:00")),
+            interval("a", "09:31:00", None),
+        ];
+
+        let rows = peak_concurrency(&calls, &sessions);
+        assert_eq!(rows.len(), 2);
+
+        assert_eq!(rows[0].key.as_deref(), Some("a"));
+        assert_eq!(rows[0].peak_calls, 2);
+        assert_eq!(
+            rows[0].peak_calls_at.as_deref(),
+            Some("2025-10-18 09:30:00")
+        );
+        assert_eq!(rows[0].peak_participants, 3);
+        assert_eq!(
+            rows[0].peak_participants_at.as_deref(),
+            Some("2025-10-18 09:31:00")
+        );
+
+        assert_eq!(rows[1].peak_calls, 1);
+        assert_eq!(rows[1].peak_participants, 0);
+        assert_eq!(rows[1].peak_participants_at, None);
+    }
+}
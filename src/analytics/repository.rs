@@ -0,0 +1,295 @@
+use async_trait::async_trait;
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
+
+use crate::{
+    analytics::{
+        entities::{
+            ActivityInterval, BusyHourRow, CallDurationRow, CallMinutesRow, FeedbackRow,
+            MissedCallRow,
+        },
+        filter::{AnalyticsFilter, GroupBy},
+    },
+    shared::response::AppError,
+};
+
+const CALLS_FROM: &str = "calls c \
+    LEFT JOIN rooms r ON r.id = c.room_id \
+    LEFT JOIN users u ON u.id = c.caller_id";
+
+const SESSIONS_FROM: &str = "call_participant_sessions s \
+    JOIN calls c ON c.id = s.call_id \
+    LEFT JOIN rooms r ON r.id = c.room_id \
+    LEFT JOIN users u ON u.id = s.user_id";
+
+const FEEDBACK_FROM: &str = "call_feedback f \
+    JOIN calls c ON c.id = f.call_id \
+    LEFT JOIN rooms r ON r.id = c.room_id \
+    LEFT JOIN users u ON u.id = f.user_id";
+
+const FINISHED_CALL: &str = "c.status NOT IN ('initiated', 'ringing', 'active')";
+
+#[async_trait]
+pub trait AnalyticsRepository {
+    /// Per user this is the time they spent on calls; otherwise it is the
+    /// length of the calls themselves.
+    async fn call_minutes(&self, filter: &AnalyticsFilter)
+    -> Result<Vec<CallMinutesRow>, AppError>;
+
+    /// Ended calls only; grouping by user groups by caller.
+    async fn call_durations(
+        &self,
+        filter: &AnalyticsFilter,
+    ) -> Result<Vec<CallDurationRow>, AppError>;
+
+    async fn busiest_hours(&self, filter: &AnalyticsFilter) -> Result<Vec<BusyHourRow>, AppError>;
+
+    /// Finished calls only; grouping by user groups by caller.
+    async fn missed_calls(&self, filter: &AnalyticsFilter) -> Result<Vec<MissedCallRow>, AppError>;
+
+    /// Grouping by user groups by who left the rating; calls are attributed
+    /// to the day they started.
+    async fn feedback(&self, filter: &AnalyticsFilter) -> Result<Vec<FeedbackRow>, AppError>;
+
+    async fn call_intervals(
+        &self,
+        filter: &AnalyticsFilter,
+    ) -> Result<Vec<ActivityInterval>, AppError>;
+
+    async fn session_intervals(
+        &self,
+        filter: &AnalyticsFilter,
+    ) -> Result<Vec<ActivityInterval>, AppError>;
+}
+
+pub struct SqliteAnalyticsRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteAnalyticsRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Builds `SELECT key, label, <columns> FROM <from> WHERE <started_at> in
+/// range [AND <condition>]`, leaving grouping and ordering to the caller.
+fn select_in_range<'a>(
+    filter: &AnalyticsFilter,
+    columns: &str,
+    from: &str,
+    user_column: &str,
+    started_at: &str,
+    condition: Option<&str>,
+) -> QueryBuilder<'a, Sqlite> {
+    let (key, label) = filter.group_by.key_columns(user_column, started_at);
+
+    let mut query = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT {} AS key, {} AS label, {} FROM {} WHERE {} >= ",
+        key, label, columns, from, started_at
+    ));
+    query
+        .push_bind(filter.start_bound())
+        .push(format!(" AND {} < ", started_at))
+        .push_bind(filter.end_bound());
+
+    if let Some(condition) = condition {
+        query.push(format!(" AND {}", condition));
+    }
+    query
+}
+
+/// Days, weekdays and duration bands read best in order; everything else
+/// lists the biggest groups first.
+fn order_by(group_by: GroupBy, metric: &str) -> String {
+    match group_by {
+        GroupBy::Day | GroupBy::Weekday | GroupBy::Duration => " ORDER BY key".to_string(),
+        _ => format!(" ORDER BY {} DESC, key", metric),
+    }
+}
+
+#[async_trait]
+impl AnalyticsRepository for SqliteAnalyticsRepository {
+    async fn call_minutes(
+        &self,
+        filter: &AnalyticsFilter,
+    ) -> Result<Vec<CallMinutesRow>, AppError> {
+        let mut query = if filter.group_by == GroupBy::User {
+            select_in_range(
+                filter,
+                "COUNT(DISTINCT s.call_id) AS calls, \
+                 COALESCE(SUM(s.duration), 0) AS total_seconds, \
+                 ROUND(COALESCE(SUM(s.duration), 0) / 60.0, 1) AS total_minutes",
+                SESSIONS_FROM,
+                "s.user_id",
+                "s.joined_at",
+                None,
+            )
+        } else {
+            select_in_range(
+                filter,
+                "COUNT(*) AS calls, \
+                 COALESCE(SUM(c.duration), 0) AS total_seconds, \
+                 ROUND(COALESCE(SUM(c.duration), 0) / 60.0, 1) AS total_minutes",
+                CALLS_FROM,
+                "c.caller_id",
+                "c.started_at",
+                None,
+            )
+        };
+        query
+            .push(" GROUP BY key")
+            .push(order_by(filter.group_by, "total_seconds"));
+
+        let rows = query
+            .build_query_as::<CallMinutesRow>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn call_durations(
+        &self,
+        filter: &AnalyticsFilter,
+    ) -> Result<Vec<CallDurationRow>, AppError> {
+        let mut query = select_in_range(
+            filter,
+            "COUNT(*) AS calls, \
+             COALESCE(AVG(c.duration), 0.0) AS avg_duration_secs, \
+             COALESCE(MAX(c.duration), 0) AS max_duration_secs",
+            CALLS_FROM,
+            "c.caller_id",
+            "c.started_at",
+            Some("c.status = 'ended' AND c.duration IS NOT NULL"),
+        );
+        query
+            .push(" GROUP BY key")
+            .push(order_by(filter.group_by, "calls"));
+
+        let rows = query
+            .build_query_as::<CallDurationRow>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn busiest_hours(&self, filter: &AnalyticsFilter) -> Result<Vec<BusyHourRow>, AppError> {
+        let mut query = select_in_range(
+            filter,
+            "CAST(strftime('%H', c.started_at) AS INTEGER) AS hour, \
+             COUNT(*) AS calls, \
+             ROUND(COALESCE(SUM(c.duration), 0) / 60.0, 1) AS total_minutes",
+            CALLS_FROM,
+            "c.caller_id",
+            "c.started_at",
+            None,
+        );
+        query.push(" GROUP BY key, hour ORDER BY key, calls DESC, total_minutes DESC, hour");
+
+        let rows = query
+            .build_query_as::<BusyHourRow>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn missed_calls(&self, filter: &AnalyticsFilter) -> Result<Vec<MissedCallRow>, AppError> {
+        let mut query = select_in_range(
+            filter,
+            "COUNT(*) AS calls, \
+             SUM(c.status = 'missed') AS missed, \
+             SUM(c.status = 'rejected') AS rejected, \
+             COALESCE(ROUND(SUM(c.status = 'missed') * 1.0 / COUNT(*), 4), 0.0) AS missed_rate",
+            CALLS_FROM,
+            "c.caller_id",
+            "c.started_at",
+            Some(FINISHED_CALL),
+        );
+        query
+            .push(" GROUP BY key")
+            .push(order_by(filter.group_by, "missed_rate"));
+
+        let rows = query
+            .build_query_as::<MissedCallRow>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn feedback(&self, filter: &AnalyticsFilter) -> Result<Vec<FeedbackRow>, AppError> {
+        let mut query = select_in_range(
+            filter,
+            "COUNT(*) AS responses, \
+             ROUND(AVG(f.rating), 2) AS average_rating, \
+             SUM(f.rating <= 2) AS low_ratings, \
+             SUM(EXISTS (SELECT 1 FROM call_feedback_issues i \
+                 WHERE i.feedback_id = f.id AND i.issue = 'echo')) AS echo, \
+             SUM(EXISTS (SELECT 1 FROM call_feedback_issues i \
+                 WHERE i.feedback_id = f.id AND i.issue = 'frozen_video')) AS frozen_video, \
+             SUM(EXISTS (SELECT 1 FROM call_feedback_issues i \
+                 WHERE i.feedback_id = f.id AND i.issue = 'dropped')) AS dropped, \
+             COALESCE(AVG(c.duration), 0.0) AS avg_call_duration_secs",
+            FEEDBACK_FROM,
+            "f.user_id",
+            "c.started_at",
+            None,
+        );
+        query
+            .push(" GROUP BY key")
+            .push(order_by(filter.group_by, "responses"));
+
+        let rows = query
+            .build_query_as::<FeedbackRow>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn call_intervals(
+        &self,
+        filter: &AnalyticsFilter,
+    ) -> Result<Vec<ActivityInterval>, AppError> {
+        let mut query = select_in_range(
+            filter,
+            "c.started_at AS started_at, c.ended_at AS ended_at",
+            CALLS_FROM,
+            "c.caller_id",
+            "c.started_at",
+            None,
+        );
+        query.push(" ORDER BY c.started_at");
+
+        let intervals = query
+            .build_query_as::<ActivityInterval>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(intervals)
+    }
+
+    async fn session_intervals(
+        &self,
+        filter: &AnalyticsFilter,
+    ) -> Result<Vec<ActivityInterval>, AppError> {
+        let mut query = select_in_range(
+            filter,
+            "s.joined_at AS started_at, s.left_at AS ended_at",
+            SESSIONS_FROM,
+            "s.user_id",
+            "s.joined_at",
+            None,
+        );
+        query.push(" ORDER BY s.joined_at");
+
+        let intervals = query
+            .build_query_as::<ActivityInterval>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(intervals)
+    }
+}
@@ -0,0 +1,16 @@
+use actix_web::{middleware, web};
+
+use crate::{analytics::handlers, infrastructure::middlewares::auth_middleware};
+
+pub fn analytics_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/analytics")
+            .wrap(middleware::from_fn(auth_middleware::auth))
+            .service(handlers::call_minutes)
+            .service(handlers::call_durations)
+            .service(handlers::peak_concurrency)
+            .service(handlers::busiest_hours)
+            .service(handlers::missed_calls)
+            .service(handlers::feedback),
+    );
+}
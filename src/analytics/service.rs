@@ -0,0 +1,229 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    analytics::{
+        contract::{AnalyticsParams, AnalyticsReport},
+        entities::{
+            BusyHourRow, CallDurationRow, CallMinutesRow, FeedbackRow, MissedCallRow,
+            PeakConcurrencyRow,
+        },
+        filter::{AnalyticsFilter, GroupBy},
+        peaks,
+        repository::AnalyticsRepository,
+    },
+    shared::response::AppError,
+    users::UserService,
+};
+
+/// Usage reports across all calls, available to administrators only. Every
+/// report covers whole days and lists the groupings it accepts, the first
+/// being the default.
+#[async_trait]
+pub trait AnalyticsService: Send + Sync {
+    /// Groups by day, user, room, weekday or none.
+    async fn call_minutes(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+    ) -> Result<AnalyticsReport<CallMinutesRow>, AppError>;
+
+    /// Groups by none, day, room, user or weekday.
+    async fn call_durations(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+    ) -> Result<AnalyticsReport<CallDurationRow>, AppError>;
+
+    /// Groups by none, day or room.
+    async fn peak_concurrency(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+    ) -> Result<AnalyticsReport<PeakConcurrencyRow>, AppError>;
+
+    /// Groups by none, weekday or room; each group lists its hours busiest
+    /// first.
+    async fn busiest_hours(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+    ) -> Result<AnalyticsReport<BusyHourRow>, AppError>;
+
+    /// Groups by none, day, room, user or weekday.
+    async fn missed_calls(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+    ) -> Result<AnalyticsReport<MissedCallRow>, AppError>;
+
+    /// Groups by none, room type, duration band, room, day or user.
+    async fn feedback(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+    ) -> Result<AnalyticsReport<FeedbackRow>, AppError>;
+}
+
+pub struct AnalyticsServiceImpl {
+    repo: Arc<dyn AnalyticsRepository + Send + Sync>,
+    user_service: Arc<dyn UserService + Send + Sync>,
+}
+
+impl AnalyticsServiceImpl {
+    pub fn new(
+        repo: Arc<dyn AnalyticsRepository + Send + Sync>,
+        user_service: Arc<dyn UserService + Send + Sync>,
+    ) -> Self {
+        Self { repo, user_service }
+    }
+
+    async fn filter_for_admin(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+        allowed: &[GroupBy],
+    ) -> Result<AnalyticsFilter, AppError> {
+        if !self.user_service.is_admin(user_id).await? {
+            return Err(AppError::Unauthorized(
+                "Only administrators can view call analytics".into(),
+            ));
+        }
+
+        AnalyticsFilter::from_params(params, allowed, Utc::now().date_naive())
+    }
+}
+
+#[async_trait]
+impl AnalyticsService for AnalyticsServiceImpl {
+    async fn call_minutes(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+    ) -> Result<AnalyticsReport<CallMinutesRow>, AppError> {
+        let filter = self
+            .filter_for_admin(
+                user_id,
+                params,
+                &[
+                    GroupBy::Day,
+                    GroupBy::User,
+                    GroupBy::Room,
+                    GroupBy::Weekday,
+                    GroupBy::None,
+                ],
+            )
+            .await?;
+
+        let rows = self.repo.call_minutes(&filter).await?;
+        Ok(AnalyticsReport::new(&filter, rows))
+    }
+
+    async fn call_durations(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+    ) -> Result<AnalyticsReport<CallDurationRow>, AppError> {
+        let filter = self
+            .filter_for_admin(
+                user_id,
+                params,
+                &[
+                    GroupBy::None,
+                    GroupBy::Day,
+                    GroupBy::Room,
+                    GroupBy::User,
+                    GroupBy::Weekday,
+                ],
+            )
+            .await?;
+
+        let rows = self.repo.call_durations(&filter).await?;
+        Ok(AnalyticsReport::new(&filter, rows))
+    }
+
+    async fn peak_concurrency(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+    ) -> Result<AnalyticsReport<PeakConcurrencyRow>, AppError> {
+        let filter = self
+            .filter_for_admin(
+                user_id,
+                params,
+                &[GroupBy::None, GroupBy::Day, GroupBy::Room],
+            )
+            .await?;
+
+        let calls = self.repo.call_intervals(&filter).await?;
+        let sessions = self.repo.session_intervals(&filter).await?;
+        let rows = peaks::peak_concurrency(&calls, &sessions);
+        Ok(AnalyticsReport::new(&filter, rows))
+    }
+
+    async fn busiest_hours(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+    ) -> Result<AnalyticsReport<BusyHourRow>, AppError> {
+        let filter = self
+            .filter_for_admin(
+                user_id,
+                params,
+                &[GroupBy::None, GroupBy::Weekday, GroupBy::Room],
+            )
+            .await?;
+
+        let rows = self.repo.busiest_hours(&filter).await?;
+        Ok(AnalyticsReport::new(&filter, rows))
+    }
+
+    async fn missed_calls(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+    ) -> Result<AnalyticsReport<MissedCallRow>, AppError> {
+        let filter = self
+            .filter_for_admin(
+                user_id,
+                params,
+                &[
+                    GroupBy::None,
+                    GroupBy::Day,
+                    GroupBy::Room,
+                    GroupBy::User,
+                    GroupBy::Weekday,
+                ],
+            )
+            .await?;
+
+        let rows = self.repo.missed_calls(&filter).await?;
+        Ok(AnalyticsReport::new(&filter, rows))
+    }
+
+    async fn feedback(
+        &self,
+        user_id: i32,
+        params: AnalyticsParams,
+    ) -> Result<AnalyticsReport<FeedbackRow>, AppError> {
+        let filter = self
+            .filter_for_admin(
+                user_id,
+                params,
+                &[
+                    GroupBy::None,
+                    GroupBy::RoomType,
+                    GroupBy::Duration,
+                    GroupBy::Room,
+                    GroupBy::Day,
+                    GroupBy::User,
+                ],
+            )
+            .await?;
+
+        let rows = self.repo.feedback(&filter).await?;
+        Ok(AnalyticsReport::new(&filter, rows))
+    }
+}
@@ -0,0 +1,30 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct NewBreakoutSession {
+    pub room_id: String,
+    pub count: i32,
+    /// Optional room names; missing ones default to "Breakout room N".
+    pub names: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+pub struct ManualAssignment {
+    pub user_id: i32,
+    pub position: i32,
+}
+
+#[derive(Deserialize)]
+pub struct AssignParams {
+    pub assignments: Vec<ManualAssignment>,
+}
+
+#[derive(Deserialize)]
+pub struct OpenBreakoutParams {
+    pub duration_minutes: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct BreakoutBroadcast {
+    pub message: String,
+}
@@ -0,0 +1,84 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BreakoutStatus {
+    Pending,
+    Open,
+    Closed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BreakoutSession {
+    pub id: i32,
+    /// The meeting room the breakout rooms belong to.
+    pub room_id: String,
+    pub created_by: i32,
+    pub status: BreakoutStatus,
+    pub created_at: NaiveDateTime,
+    pub opened_at: Option<NaiveDateTime>,
+    pub closes_at: Option<NaiveDateTime>,
+    pub closed_at: Option<NaiveDateTime>,
+}
+
+/// A numbered slot in a session. The underlying instant room is only
+/// created when the session opens.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BreakoutRoom {
+    pub session_id: i32,
+    pub position: i32,
+    pub name: String,
+    pub room_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BreakoutAssignment {
+    pub session_id: i32,
+    pub user_id: i32,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BreakoutRoomView {
+    pub position: i32,
+    pub name: String,
+    pub room_id: Option<String>,
+    pub user_ids: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BreakoutOverview {
+    pub session: BreakoutSession,
+    pub rooms: Vec<BreakoutRoomView>,
+}
+
+impl BreakoutOverview {
+    pub fn new(
+        session: BreakoutSession,
+        rooms: Vec<BreakoutRoom>,
+        assignments: &[BreakoutAssignment],
+    ) -> Self {
+        let rooms = rooms
+            .into_iter()
+            .map(|room| BreakoutRoomView {
+                position: room.position,
+                user_ids: assignments
+                    .iter()
+                    .filter(|a| a.position == room.position)
+                    .map(|a| a.user_id)
+                    .collect(),
+                name: room.name,
+                room_id: room.room_id,
+            })
+            .collect();
+
+        Self { session, rooms }
+    }
+
+    pub fn room_ids(&self) -> impl Iterator<Item = &str> {
+        self.rooms.iter().filter_map(|room| room.room_id.as_deref())
+    }
+}
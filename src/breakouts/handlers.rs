@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use actix_identity::Identity;
+use actix_web::{HttpResponse, Result as ActixResult, get, post, web};
+
+use crate::{
+    breakouts::{
+        BreakoutService,
+        contract::{AssignParams, BreakoutBroadcast, NewBreakoutSession, OpenBreakoutParams},
+    },
+    calls::SignalingServer,
+    shared::response::{AppError, respond_ok},
+};
+
+#[post("")]
+pub async fn create_session(
+    payload: web::Json<NewBreakoutSession>,
+    identity: Identity,
+    breakout_service: web::Data<Arc<dyn BreakoutService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+    let payload = payload.into_inner();
+
+    let overview = breakout_service
+        .create_session(&payload.room_id, user_id, payload.count, payload.names)
+        .await?;
+    respond_ok(overview)
+}
+
+#[get("/rooms/{room_id}")]
+pub async fn current_session(
+    room_id: web::Path<String>,
+    identity: Identity,
+    breakout_service: web::Data<Arc<dyn BreakoutService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let overview = breakout_service
+        .get_current_session(&room_id.into_inner(), user_id)
+        .await?;
+    respond_ok(overview)
+}
+
+#[post("/{session_id}/assign")]
+pub async fn assign(
+    session_id: web::Path<i32>,
+    payload: web::Json<AssignParams>,
+    identity: Identity,
+    breakout_service: web::Data<Arc<dyn BreakoutService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let overview = breakout_service
+        .assign(
+            session_id.into_inner(),
+            user_id,
+            payload.into_inner().assignments,
+        )
+        .await?;
+    respond_ok(overview)
+}
+
+#[post("/{session_id}/assign/random")]
+pub async fn assign_randomly(
+    session_id: web::Path<i32>,
+    identity: Identity,
+    breakout_service: web::Data<Arc<dyn BreakoutService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let overview = breakout_service
+        .assign_randomly(session_id.into_inner(), user_id)
+        .await?;
+    respond_ok(overview)
+}
+
+#[post("/{session_id}/open")]
+pub async fn open(
+    session_id: web::Path<i32>,
+    payload: web::Json<OpenBreakoutParams>,
+    identity: Identity,
+    breakout_service: web::Data<Arc<dyn BreakoutService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let overview = breakout_service
+        .open(session_id.into_inner(), user_id, payload.duration_minutes)
+        .await?;
+
+    signaling_server.open_breakouts(&overview).await;
+    signaling_server
+        .record_moderator_action(&overview.session.room_id, user_id, None, "breakouts_opened")
+        .await;
+    respond_ok(overview)
+}
+
+#[post("/{session_id}/broadcast")]
+pub async fn broadcast(
+    session_id: web::Path<i32>,
+    payload: web::Json<BreakoutBroadcast>,
+    identity: Identity,
+    breakout_service: web::Data<Arc<dyn BreakoutService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let overview = breakout_service
+        .get_moderated_session(session_id.into_inner(), user_id)
+        .await?;
+
+    signaling_server
+        .broadcast_to_breakouts(&overview, user_id, &payload.message)
+        .await?;
+    signaling_server
+        .record_moderator_action(
+            &overview.session.room_id,
+            user_id,
+            None,
+            "breakout_broadcast",
+        )
+        .await;
+    respond_ok("Message broadcast successfully")
+}
+
+#[post("/{session_id}/close")]
+pub async fn close(
+    session_id: web::Path<i32>,
+    identity: Identity,
+    breakout_service: web::Data<Arc<dyn BreakoutService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let overview = breakout_service
+        .close(session_id.into_inner(), user_id)
+        .await?;
+
+    signaling_server.close_breakouts(&overview).await;
+    signaling_server
+        .record_moderator_action(&overview.session.room_id, user_id, None, "breakouts_closed")
+        .await;
+    respond_ok(overview)
+}
@@ -0,0 +1,11 @@
+pub mod contract;
+pub mod entities;
+pub mod handlers;
+pub mod repository;
+pub mod routes;
+pub mod service;
+pub mod timer;
+
+pub use entities::{BreakoutOverview, BreakoutSession};
+pub use repository::{BreakoutRepository, SqliteBreakoutRepository};
+pub use service::{BreakoutService, BreakoutServiceImpl};
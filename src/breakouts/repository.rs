@@ -0,0 +1,244 @@
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+
+use crate::{
+    breakouts::entities::{BreakoutAssignment, BreakoutRoom, BreakoutSession},
+    shared::response::AppError,
+};
+
+#[async_trait]
+pub trait BreakoutRepository {
+    async fn create_session(
+        &self,
+        room_id: &str,
+        created_by: i32,
+        names: &[String],
+    ) -> Result<BreakoutSession, AppError>;
+
+    async fn get_session(&self, session_id: i32) -> Result<Option<BreakoutSession>, AppError>;
+
+    /// The pending or open session of a meeting room, if any.
+    async fn get_current_session(&self, room_id: &str)
+    -> Result<Option<BreakoutSession>, AppError>;
+
+    async fn list_rooms(&self, session_id: i32) -> Result<Vec<BreakoutRoom>, AppError>;
+
+    async fn list_assignments(&self, session_id: i32) -> Result<Vec<BreakoutAssignment>, AppError>;
+
+    /// Upserts assignments, first clearing the existing ones when `replace` is set.
+    async fn save_assignments(
+        &self,
+        session_id: i32,
+        assignments: &[(i32, i32)],
+        replace: bool,
+    ) -> Result<(), AppError>;
+
+    /// Marks the session open and records the instant room created for each slot.
+    async fn mark_open(
+        &self,
+        session_id: i32,
+        closes_at: Option<String>,
+        rooms: &[(i32, String)],
+    ) -> Result<(), AppError>;
+
+    async fn mark_closed(&self, session_id: i32) -> Result<(), AppError>;
+
+    async fn list_expired_sessions(&self) -> Result<Vec<BreakoutSession>, AppError>;
+}
+
+pub struct SqliteBreakoutRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteBreakoutRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl BreakoutRepository for SqliteBreakoutRepository {
+    async fn create_session(
+        &self,
+        room_id: &str,
+        created_by: i32,
+        names: &[String],
+    ) -> Result<BreakoutSession, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let session = sqlx::query_as::<_, BreakoutSession>(
+            r#"
+            INSERT INTO breakout_sessions (room_id, created_by)
+            VALUES ($1, $2)
+            RETURNING *
+            "#,
+        )
+        .bind(room_id)
+        .bind(created_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        for (index, name) in names.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO breakout_rooms (session_id, position, name)
+                VALUES ($1, $2, $3)
+                "#,
+            )
+            .bind(session.id)
+            .bind(index as i32 + 1)
+            .bind(name)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(session)
+    }
+
+    async fn get_session(&self, session_id: i32) -> Result<Option<BreakoutSession>, AppError> {
+        let session =
+            sqlx::query_as::<_, BreakoutSession>("SELECT * FROM breakout_sessions WHERE id = $1")
+                .bind(session_id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(session)
+    }
+
+    async fn get_current_session(
+        &self,
+        room_id: &str,
+    ) -> Result<Option<BreakoutSession>, AppError> {
+        let session = sqlx::query_as::<_, BreakoutSession>(
+            "SELECT * FROM breakout_sessions WHERE room_id = $1 AND status != 'closed'",
+        )
+        .bind(room_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(session)
+    }
+
+    async fn list_rooms(&self, session_id: i32) -> Result<Vec<BreakoutRoom>, AppError> {
+        let rooms = sqlx::query_as::<_, BreakoutRoom>(
+            "SELECT * FROM breakout_rooms WHERE session_id = $1 ORDER BY position",
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rooms)
+    }
+
+    async fn list_assignments(&self, session_id: i32) -> Result<Vec<BreakoutAssignment>, AppError> {
+        let assignments = sqlx::query_as::<_, BreakoutAssignment>(
+            "SELECT * FROM breakout_assignments WHERE session_id = $1 ORDER BY position, user_id",
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(assignments)
+    }
+
+    async fn save_assignments(
+        &self,
+        session_id: i32,
+        assignments: &[(i32, i32)],
+        replace: bool,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        if replace {
+            sqlx::query("DELETE FROM breakout_assignments WHERE session_id = $1")
+                .bind(session_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        for (user_id, position) in assignments {
+            sqlx::query(
+                r#"
+                INSERT INTO breakout_assignments (session_id, user_id, position)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (session_id, user_id) DO UPDATE SET position = excluded.position
+                "#,
+            )
+            .bind(session_id)
+            .bind(user_id)
+            .bind(position)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn mark_open(
+        &self,
+        session_id: i32,
+        closes_at: Option<String>,
+        rooms: &[(i32, String)],
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        for (position, room_id) in rooms {
+            sqlx::query(
+                "UPDATE breakout_rooms SET room_id = $1 WHERE session_id = $2 AND position = $3",
+            )
+            .bind(room_id)
+            .bind(session_id)
+            .bind(position)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE breakout_sessions
+            SET status = 'open', opened_at = CURRENT_TIMESTAMP, closes_at = $1
+            WHERE id = $2
+            "#,
+        )
+        .bind(closes_at)
+        .bind(session_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn mark_closed(&self, session_id: i32) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE breakout_sessions
+            SET status = 'closed', closed_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND status != 'closed'
+            "#,
+        )
+        .bind(session_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_expired_sessions(&self) -> Result<Vec<BreakoutSession>, AppError> {
+        let sessions = sqlx::query_as::<_, BreakoutSession>(
+            r#"
+            SELECT * FROM breakout_sessions
+            WHERE status = 'open' AND closes_at IS NOT NULL AND closes_at <= CURRENT_TIMESTAMP
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sessions)
+    }
+}
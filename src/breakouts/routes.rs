@@ -0,0 +1,17 @@
+use actix_web::{middleware, web};
+
+use crate::{breakouts::handlers, infrastructure::middlewares::auth_middleware};
+
+pub fn breakout_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/breakouts")
+            .wrap(middleware::from_fn(auth_middleware::auth))
+            .service(handlers::create_session)
+            .service(handlers::current_session)
+            .service(handlers::assign)
+            .service(handlers::assign_randomly)
+            .service(handlers::open)
+            .service(handlers::broadcast)
+            .service(handlers::close),
+    );
+}
@@ -0,0 +1,409 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use rand::seq::SliceRandom;
+
+use crate::{
+    breakouts::{
+        contract::ManualAssignment,
+        entities::{BreakoutOverview, BreakoutSession, BreakoutStatus},
+        repository::BreakoutRepository,
+    },
+    rooms::{RoomMemberRole, RoomService, RoomType, search::SQLITE_TIMESTAMP_FORMAT},
+    shared::response::AppError,
+};
+
+const MAX_BREAKOUT_ROOMS: i32 = 50;
+const MAX_DURATION_MINUTES: i64 = 240;
+
+#[async_trait]
+pub trait BreakoutService: Send + Sync {
+    async fn create_session(
+        &self,
+        room_id: &str,
+        moderator_id: i32,
+        count: i32,
+        names: Option<Vec<String>>,
+    ) -> Result<BreakoutOverview, AppError>;
+
+    /// The pending or open session of a meeting room, visible to its members.
+    async fn get_current_session(
+        &self,
+        room_id: &str,
+        user_id: i32,
+    ) -> Result<Option<BreakoutOverview>, AppError>;
+
+    /// Loads a session after checking the user moderates its meeting room.
+    async fn get_moderated_session(
+        &self,
+        session_id: i32,
+        moderator_id: i32,
+    ) -> Result<BreakoutOverview, AppError>;
+
+    async fn assign(
+        &self,
+        session_id: i32,
+        moderator_id: i32,
+        assignments: Vec<ManualAssignment>,
+    ) -> Result<BreakoutOverview, AppError>;
+
+    /// Shuffles the meeting's participants evenly across the breakout rooms,
+    /// replacing any earlier assignments. Owners and moderators stay put.
+    async fn assign_randomly(
+        &self,
+        session_id: i32,
+        moderator_id: i32,
+    ) -> Result<BreakoutOverview, AppError>;
+
+    /// Creates an instant room for every slot and adds the assigned members.
+    async fn open(
+        &self,
+        session_id: i32,
+        moderator_id: i32,
+        duration_minutes: Option<i64>,
+    ) -> Result<BreakoutOverview, AppError>;
+
+    async fn close(&self, session_id: i32, moderator_id: i32)
+    -> Result<BreakoutOverview, AppError>;
+
+    /// Closes every open session whose timer has run out. Sessions that fail
+    /// to close are logged and left for the next call.
+    async fn close_expired(&self) -> Result<Vec<BreakoutOverview>, AppError>;
+}
+
+pub struct BreakoutServiceImpl {
+    repo: Arc<dyn BreakoutRepository + Send + Sync>,
+    room_service: Arc<dyn RoomService + Send + Sync>,
+}
+
+impl BreakoutServiceImpl {
+    pub fn new(
+        repo: Arc<dyn BreakoutRepository + Send + Sync>,
+        room_service: Arc<dyn RoomService + Send + Sync>,
+    ) -> Self {
+        Self { repo, room_service }
+    }
+
+    async fn ensure_moderator(&self, room_id: &str, user_id: i32) -> Result<(), AppError> {
+        if !self
+            .room_service
+            .is_user_moderator(room_id, user_id)
+            .await?
+        {
+            return Err(AppError::Unauthorized(
+                "Only room owners and moderators can manage breakout rooms".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn overview(&self, session: BreakoutSession) -> Result<BreakoutOverview, AppError> {
+        let rooms = self.repo.list_rooms(session.id).await?;
+        let assignments = self.repo.list_assignments(session.id).await?;
+        Ok(BreakoutOverview::new(session, rooms, &assignments))
+    }
+
+    async fn load(&self, session_id: i32) -> Result<BreakoutSession, AppError> {
+        self.repo
+            .get_session(session_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Breakout session {} not found", session_id)))
+    }
+
+    fn ensure_status(session: &BreakoutSession, status: BreakoutStatus) -> Result<(), AppError> {
+        if session.status != status {
+            let message = match status {
+                BreakoutStatus::Pending => "Breakout rooms have already been opened",
+                BreakoutStatus::Open => "Breakout rooms are not open",
+                BreakoutStatus::Closed => "Breakout rooms are still running",
+            };
+            return Err(AppError::Validation(message.into()));
+        }
+        Ok(())
+    }
+
+    /// Closes the session and lets everyone go from the breakout rooms, which
+    /// the instant room lifecycle then archives.
+    async fn finish(&self, session: BreakoutSession) -> Result<BreakoutOverview, AppError> {
+        self.repo.mark_closed(session.id).await?;
+
+        let overview = self.overview(self.load(session.id).await?).await?;
+
+        for room_id in overview.room_ids() {
+            for user in self.room_service.list_room_users(room_id).await? {
+                self.room_service.leave_room(room_id, user.id).await?;
+            }
+        }
+
+        Ok(overview)
+    }
+}
+
+#[async_trait]
+impl BreakoutService for BreakoutServiceImpl {
+    async fn create_session(
+        &self,
+        room_id: &str,
+        moderator_id: i32,
+        count: i32,
+        names: Option<Vec<String>>,
+    ) -> Result<BreakoutOverview, AppError> {
+        if !(1..=MAX_BREAKOUT_ROOMS).contains(&count) {
+            return Err(AppError::Validation(format!(
+                "Number of breakout rooms must be between 1 and {}",
+                MAX_BREAKOUT_ROOMS
+            )));
+        }
+
+        let room = self
+            .room_service
+            .get_room(room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
+
+        if room.room_type != RoomType::Meeting {
+            return Err(AppError::Validation(
+                "Breakout rooms are only available in meeting rooms".into(),
+            ));
+        }
+        if room.is_archived() {
+            return Err(AppError::Validation(format!(
+                "Room {} is archived",
+                room_id
+            )));
+        }
+
+        self.ensure_moderator(room_id, moderator_id).await?;
+
+        if self.repo.get_current_session(room_id).await?.is_some() {
+            return Err(AppError::Validation(
+                "This room already has breakout rooms; close them first".into(),
+            ));
+        }
+
+        let mut names = names.unwrap_or_default();
+        names.resize(count as usize, String::new());
+        let names: Vec<String> = names
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| match name.trim() {
+                "" => format!("Breakout room {}", index + 1),
+                name => name.to_string(),
+            })
+            .collect();
+
+        let session = self
+            .repo
+            .create_session(room_id, moderator_id, &names)
+            .await?;
+
+        self.overview(session).await
+    }
+
+    async fn get_current_session(
+        &self,
+        room_id: &str,
+        user_id: i32,
+    ) -> Result<Option<BreakoutOverview>, AppError> {
+        if !self.room_service.is_user_in_room(room_id, user_id).await? {
+            return Err(AppError::Unauthorized(format!(
+                "User {} is not a member of room {}",
+                user_id, room_id
+            )));
+        }
+
+        match self.repo.get_current_session(room_id).await? {
+            Some(session) => Ok(Some(self.overview(session).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_moderated_session(
+        &self,
+        session_id: i32,
+        moderator_id: i32,
+    ) -> Result<BreakoutOverview, AppError> {
+        let session = self.load(session_id).await?;
+        self.ensure_moderator(&session.room_id, moderator_id)
+            .await?;
+        self.overview(session).await
+    }
+
+    async fn assign(
+        &self,
+        session_id: i32,
+        moderator_id: i32,
+        assignments: Vec<ManualAssignment>,
+    ) -> Result<BreakoutOverview, AppError> {
+        let session = self.load(session_id).await?;
+        self.ensure_moderator(&session.room_id, moderator_id)
+            .await?;
+        Self::ensure_status(&session, BreakoutStatus::Pending)?;
+
+        let slots = self.repo.list_rooms(session_id).await?.len() as i32;
+
+        let mut pairs = Vec::with_capacity(assignments.len());
+        for assignment in assignments {
+            if !(1..=slots).contains(&assignment.position) {
+                return Err(AppError::Validation(format!(
+                    "Breakout room {} does not exist",
+                    assignment.position
+                )));
+            }
+            if !self
+                .room_service
+                .is_user_in_room(&session.room_id, assignment.user_id)
+                .await?
+            {
+                return Err(AppError::Validation(format!(
+                    "User {} is not a member of room {}",
+                    assignment.user_id, session.room_id
+                )));
+            }
+            pairs.push((assignment.user_id, assignment.position));
+        }
+
+        self.repo
+            .save_assignments(session_id, &pairs, false)
+            .await?;
+        self.overview(session).await
+    }
+
+    async fn assign_randomly(
+        &self,
+        session_id: i32,
+        moderator_id: i32,
+    ) -> Result<BreakoutOverview, AppError> {
+        let session = self.load(session_id).await?;
+        self.ensure_moderator(&session.room_id, moderator_id)
+            .await?;
+        Self::ensure_status(&session, BreakoutStatus::Pending)?;
+
+        let slots = self.repo.list_rooms(session_id).await?.len() as i32;
+
+        let mut participants = Vec::new();
+        for user in self.room_service.list_room_users(&session.room_id).await? {
+            let role = self
+                .room_service
+                .get_member_role(&session.room_id, user.id)
+                .await?;
+            if role == Some(RoomMemberRole::Participant) {
+                participants.push(user.id);
+            }
+        }
+
+        participants.shuffle(&mut rand::thread_rng());
+
+        let pairs = distribute(&participants, slots);
+        self.repo.save_assignments(session_id, &pairs, true).await?;
+        self.overview(session).await
+    }
+
+    async fn open(
+        &self,
+        session_id: i32,
+        moderator_id: i32,
+        duration_minutes: Option<i64>,
+    ) -> Result<BreakoutOverview, AppError> {
+        let session = self.load(session_id).await?;
+        self.ensure_moderator(&session.room_id, moderator_id)
+            .await?;
+        Self::ensure_status(&session, BreakoutStatus::Pending)?;
+
+        let closes_at = match duration_minutes {
+            Some(minutes) if !(1..=MAX_DURATION_MINUTES).contains(&minutes) => {
+                return Err(AppError::Validation(format!(
+                    "Duration must be between 1 and {} minutes",
+                    MAX_DURATION_MINUTES
+                )));
+            }
+            Some(minutes) => Some(
+                (Utc::now() + Duration::minutes(minutes))
+                    .format(SQLITE_TIMESTAMP_FORMAT)
+                    .to_string(),
+            ),
+            None => None,
+        };
+
+        let parent = self
+            .room_service
+            .get_room(&session.room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", session.room_id)))?;
+
+        let overview = self.overview(session).await?;
+
+        let mut created = Vec::with_capacity(overview.rooms.len());
+        for slot in &overview.rooms {
+            let room = self
+                .room_service
+                .create_room(
+                    slot.name.clone(),
+                    RoomType::Instant.to_string(),
+                    moderator_id,
+                    Some(format!("Breakout room of {}", parent.name)),
+                    None,
+                )
+                .await?;
+
+            for user_id in &slot.user_ids {
+                self.room_service.admit_member(&room.id, *user_id).await?;
+            }
+
+            created.push((slot.position, room.id));
+        }
+
+        self.repo.mark_open(session_id, closes_at, &created).await?;
+
+        self.overview(self.load(session_id).await?).await
+    }
+
+    async fn close(
+        &self,
+        session_id: i32,
+        moderator_id: i32,
+    ) -> Result<BreakoutOverview, AppError> {
+        let session = self.load(session_id).await?;
+        self.ensure_moderator(&session.room_id, moderator_id)
+            .await?;
+        Self::ensure_status(&session, BreakoutStatus::Open)?;
+
+        self.finish(session).await
+    }
+
+    async fn close_expired(&self) -> Result<Vec<BreakoutOverview>, AppError> {
+        let mut closed = Vec::new();
+        for session in self.repo.list_expired_sessions().await? {
+            // A session that fails to close is retried on the next tick
+            let session_id = session.id;
+            match self.finish(session).await {
+                Ok(overview) => closed.push(overview),
+                Err(e) => eprintln!("Failed to close breakout session {}: {}", session_id, e),
+            }
+        }
+        Ok(closed)
+    }
+}
+
+/// Deals users round-robin into rooms numbered from 1, so room sizes differ
+/// by at most one.
+fn distribute(user_ids: &[i32], room_count: i32) -> Vec<(i32, i32)> {
+    user_ids
+        .iter()
+        .enumerate()
+        .map(|(index, user_id)| (*user_id, index as i32 % room_count + 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distribute_balances_rooms() {
+        let pairs = distribute(&[10, 11, 12, 13, 14], 2);
+        assert_eq!(pairs, vec![(10, 1), (11, 2), (12, 1), (13, 2), (14, 1)]);
+        assert!(distribute(&[], 3).is_empty());
+    }
+}
@@ -0,0 +1,32 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{breakouts::BreakoutService, calls::SignalingServer};
+
+const BREAKOUT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Closes breakout sessions whose timer has run out and pulls their
+/// participants back into the meeting.
+pub fn spawn_breakout_timer(
+    breakout_service: Arc<dyn BreakoutService>,
+    signaling_server: Arc<SignalingServer>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(BREAKOUT_POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let closed = match breakout_service.close_expired().await {
+                Ok(closed) => closed,
+                Err(e) => {
+                    eprintln!("Failed to close expired breakout rooms: {}", e);
+                    continue;
+                }
+            };
+
+            for overview in closed {
+                signaling_server.close_breakouts(&overview).await;
+            }
+        }
+    });
+}
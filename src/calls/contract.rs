@@ -1,6 +1,17 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
-use crate::users::User;
+use crate::{
+    calls::{
+        entities::{
+            Call, CallEvent, CallFeedback, CallHistoryEntry, CallInvite, FeedbackIssue,
+            IceCandidateType,
+        },
+        quality::QualityIssue,
+    },
+    rooms::Room,
+    users::User,
+};
 
 #[derive(Deserialize)]
 pub struct NewCall {
@@ -9,11 +20,99 @@ pub struct NewCall {
     pub status: String,
 }
 
+#[derive(Deserialize)]
+pub struct DirectCallRequest {
+    pub target_user_id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct RingRequest {
+    pub room_id: String,
+    pub user_ids: Vec<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct SubmitFeedback {
+    /// 1 (worst) to 5 (best).
+    pub rating: i32,
+    #[serde(default)]
+    pub issues: Vec<FeedbackIssue>,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssueCount {
+    pub issue: FeedbackIssue,
+    pub count: usize,
+}
+
+/// Every rating left for a call, with the average and how often each issue
+/// was reported.
+#[derive(Debug, Serialize)]
+pub struct CallFeedbackSummary {
+    pub call_id: i32,
+    pub responses: usize,
+    pub average_rating: Option<f64>,
+    pub issues: Vec<IssueCount>,
+    pub feedback: Vec<CallFeedback>,
+}
+
+#[derive(Deserialize)]
+pub struct TranscriptParams {
+    /// `vtt` (the default), `srt` or `txt`.
+    pub format: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CallEventParams {
+    /// Comma-separated event types, e.g. `joined,left`.
+    pub event_type: Option<String>,
+    pub actor_id: Option<i32>,
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+    pub after: Option<i32>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct CallEventPage {
+    /// Oldest first; pass `next_after` as `after` to fetch the next page.
+    pub events: Vec<CallEvent>,
+    pub next_after: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct CallHistoryParams {
+    /// `made` or `received`.
+    pub direction: Option<String>,
+    /// Comma-separated statuses, e.g. `missed,rejected`.
+    pub status: Option<String>,
+    /// Only calls this user also took part in, placed or was rung for.
+    pub counterpart_id: Option<i32>,
+    pub room_id: Option<String>,
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct CallHistoryPage {
+    /// Newest first; pass `next_cursor` as `cursor` to fetch the next page.
+    pub calls: Vec<CallHistoryEntry>,
+    pub next_cursor: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct UpdateCallStatus {
     pub status: String,
 }
 
+#[derive(Deserialize)]
+pub struct PasscodeQueryParam {
+    pub passcode: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PublicUser {
     pub id: i32,
@@ -32,3 +131,70 @@ impl From<User> for PublicUser {
         }
     }
 }
+
+#[derive(Debug, Serialize)]
+pub struct DirectCall {
+    pub room: Room,
+    pub call: Call,
+    pub callee: PublicUser,
+}
+
+/// Everything a client needs to open the socket for a direct call.
+#[derive(Debug, Serialize)]
+pub struct DirectCallSession {
+    #[serde(flatten)]
+    pub direct_call: DirectCall,
+    pub socket_path: String,
+    pub call_page_path: String,
+    pub ice_servers: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallRinging {
+    pub call: Call,
+    pub invites: Vec<CallInvite>,
+}
+
+/// A rung user's invite after it changed, with the call as it stands now.
+#[derive(Debug, Clone, Serialize)]
+pub struct CallInviteUpdate {
+    pub call: Call,
+    pub invite: CallInvite,
+}
+
+/// Returned to a callee who accepted, so they can open the call's socket.
+#[derive(Debug, Serialize)]
+pub struct AcceptedCall {
+    #[serde(flatten)]
+    pub update: CallInviteUpdate,
+    pub socket_path: String,
+    pub call_page_path: String,
+    pub ice_servers: serde_json::Value,
+}
+
+/// How one participant's connection held up over the call.
+#[derive(Debug, Serialize)]
+pub struct ParticipantQuality {
+    pub user_id: i32,
+    pub samples: usize,
+    pub first_sample_at: Option<String>,
+    pub last_sample_at: Option<String>,
+    pub avg_rtt_ms: Option<f64>,
+    pub max_rtt_ms: Option<f64>,
+    pub avg_jitter_ms: Option<f64>,
+    pub avg_packet_loss_pct: Option<f64>,
+    pub max_packet_loss_pct: Option<f64>,
+    pub avg_bitrate_kbps: Option<f64>,
+    pub avg_frame_rate: Option<f64>,
+    pub candidate_types: Vec<IceCandidateType>,
+    pub is_poor: bool,
+    pub issues: Vec<QualityIssue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallQualityReport {
+    pub call_id: i32,
+    pub sample_interval_secs: i64,
+    pub poor_participants: usize,
+    pub participants: Vec<ParticipantQuality>,
+}
@@ -3,7 +3,12 @@ use std::{fmt, str::FromStr};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
-use crate::shared::response::AppError;
+use crate::{
+    calls::{limits::CallLimit, recording_consent::RecordingIndicator},
+    chat::ChatMessage,
+    rooms::RoomPolicies,
+    shared::response::AppError,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
 #[sqlx(type_name = "text", rename_all = "snake_case")]
@@ -17,6 +22,31 @@ pub enum CallStatus {
     Failed,
 }
 
+impl CallStatus {
+    /// Whether the call is still ringing or in progress.
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self,
+            CallStatus::Initiated | CallStatus::Ringing | CallStatus::Active
+        )
+    }
+
+    /// Calls only move forward: a call that has finished, however it
+    /// finished, can never be reopened.
+    pub fn can_transition_to(&self, next: &CallStatus) -> bool {
+        use CallStatus::*;
+
+        matches!(
+            (self, next),
+            (
+                Initiated,
+                Ringing | Active | Ended | Missed | Rejected | Failed
+            ) | (Ringing, Active | Ended | Missed | Rejected | Failed)
+                | (Active, Ended | Failed)
+        )
+    }
+}
+
 impl FromStr for CallStatus {
     type Err = AppError;
 
@@ -62,6 +92,176 @@ pub struct Call {
     pub duration: Option<i32>,
 }
 
+/// Who ended a call: someone hanging up, or the signalling server enforcing
+/// one of the limits of the call's room type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EndedBy {
+    User(i32),
+    Limit(CallLimit),
+}
+
+/// Whether the user placed a call or was on the receiving end of it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CallDirection {
+    Made,
+    Received,
+}
+
+impl FromStr for CallDirection {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "made" => Ok(CallDirection::Made),
+            "received" => Ok(CallDirection::Received),
+            _ => Err(AppError::Validation(
+                "Invalid direction provided! Valid values are: 'made', 'received'".to_string(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryParticipant {
+    pub user_id: i32,
+    pub name: String,
+}
+
+/// A call as it appears in one user's call history.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct CallHistoryEntry {
+    pub id: i32,
+    pub room_id: String,
+    pub room_name: Option<String>,
+    pub caller_id: i32,
+    pub caller_name: String,
+    pub direction: CallDirection,
+    pub status: CallStatus,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub duration: Option<i32>,
+    /// Everyone who joined the call, in the order they first joined.
+    #[sqlx(skip)]
+    pub participants: Vec<HistoryParticipant>,
+}
+
+/// One status change of a call. The first entry of a call has no
+/// `from_status`; `changed_by` is empty when the server made the change.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CallStatusChange {
+    pub id: i32,
+    pub call_id: i32,
+    pub from_status: Option<CallStatus>,
+    pub to_status: CallStatus,
+    pub changed_by: Option<i32>,
+    pub changed_at: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CallEventType {
+    Joined,
+    Left,
+    Reconnected,
+    Muted,
+    Unmuted,
+    VideoOn,
+    VideoOff,
+    ScreenShareStarted,
+    ScreenShareStopped,
+    ChatSent,
+    ModeratorAction,
+    StatusChanged,
+}
+
+impl FromStr for CallEventType {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "joined" => Ok(Self::Joined),
+            "left" => Ok(Self::Left),
+            "reconnected" => Ok(Self::Reconnected),
+            "muted" => Ok(Self::Muted),
+            "unmuted" => Ok(Self::Unmuted),
+            "video_on" => Ok(Self::VideoOn),
+            "video_off" => Ok(Self::VideoOff),
+            "screen_share_started" => Ok(Self::ScreenShareStarted),
+            "screen_share_stopped" => Ok(Self::ScreenShareStopped),
+            "chat_sent" => Ok(Self::ChatSent),
+            "moderator_action" => Ok(Self::ModeratorAction),
+            "status_changed" => Ok(Self::StatusChanged),
+            _ => Err(AppError::Validation(format!(
+                "Invalid call event type: '{}'",
+                s
+            ))),
+        }
+    }
+}
+
+/// One entry of a call's timeline. `actor_id` is who caused the event, empty
+/// when the server did; `subject_id` is who a moderator action was aimed at.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CallEvent {
+    pub id: i32,
+    pub call_id: i32,
+    pub event_type: CallEventType,
+    pub actor_id: Option<i32>,
+    pub subject_id: Option<i32>,
+    #[serde(serialize_with = "serialize_details")]
+    pub details: Option<String>,
+    pub created_at: String,
+}
+
+/// Details are stored as JSON text and returned as JSON.
+fn serialize_details<S>(details: &Option<String>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let value = details
+        .as_deref()
+        .map(|text| serde_json::from_str(text).unwrap_or_else(|_| text.into()))
+        .unwrap_or(serde_json::Value::Null);
+    value.serialize(serializer)
+}
+
+#[derive(Debug, Clone)]
+pub struct NewCallEvent {
+    pub call_id: i32,
+    pub event_type: CallEventType,
+    pub actor_id: Option<i32>,
+    pub subject_id: Option<i32>,
+    pub details: Option<serde_json::Value>,
+}
+
+impl NewCallEvent {
+    pub fn new(call_id: i32, event_type: CallEventType, actor_id: Option<i32>) -> Self {
+        Self {
+            call_id,
+            event_type,
+            actor_id,
+            subject_id: None,
+            details: None,
+        }
+    }
+
+    pub fn with_subject(mut self, subject_id: Option<i32>) -> Self {
+        self.subject_id = subject_id;
+        self
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+}
+
+/// A user's overall presence on a call: `joined_at` is their first join,
+/// `left_at` their latest leave (`None` while connected) and `duration` the
+/// total seconds across all of their sessions.
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct CallParticipant {
     pub call_id: i32,
@@ -71,6 +271,157 @@ pub struct CallParticipant {
     pub duration: Option<i32>,
 }
 
+/// One uninterrupted stint of a user on a call; dropping and reconnecting
+/// starts a new session.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CallParticipantSession {
+    pub id: i64,
+    pub call_id: i32,
+    pub user_id: i32,
+    pub joined_at: String,
+    pub left_at: Option<String>,
+    pub duration: Option<i32>,
+}
+
+/// Which kind of ICE candidate pair carries the media: direct on the LAN,
+/// through a NAT (`srflx`/`prflx`), or relayed through TURN.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum IceCandidateType {
+    Host,
+    Srflx,
+    Prflx,
+    Relay,
+}
+
+/// A client's summary of `RTCPeerConnection.getStats()`. Every metric is
+/// optional since audio-only or freshly connected peers do not report all
+/// of them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QualityStats {
+    pub rtt_ms: Option<f64>,
+    pub jitter_ms: Option<f64>,
+    pub packet_loss_pct: Option<f64>,
+    pub bitrate_kbps: Option<f64>,
+    pub frame_rate: Option<f64>,
+    pub candidate_type: Option<IceCandidateType>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CallQualitySample {
+    pub id: i64,
+    pub call_id: i32,
+    pub user_id: i32,
+    pub rtt_ms: Option<f64>,
+    pub jitter_ms: Option<f64>,
+    pub packet_loss_pct: Option<f64>,
+    pub bitrate_kbps: Option<f64>,
+    pub frame_rate: Option<f64>,
+    pub candidate_type: Option<IceCandidateType>,
+    pub recorded_at: String,
+}
+
+/// A caption produced by a client's own speech recognition. Times are the
+/// client's clock in Unix milliseconds; the speaker is whoever sent it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewCaption {
+    pub text: String,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+/// A stored caption, timed in milliseconds from the start of the call.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CaptionSegment {
+    pub id: i64,
+    pub call_id: i32,
+    pub user_id: i32,
+    pub speaker_name: String,
+    pub text: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub created_at: String,
+}
+
+/// A participant waiting to speak. Raised hands are kept in memory only.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaisedHand {
+    pub user_id: i32,
+    pub user_name: String,
+    pub raised_at: chrono::NaiveDateTime,
+}
+
+/// Problems a participant can report when rating a call.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FeedbackIssue {
+    Echo,
+    FrozenVideo,
+    Dropped,
+}
+
+/// A participant's rating of a call. Submitting again replaces it.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CallFeedback {
+    pub id: i64,
+    pub call_id: i32,
+    pub user_id: i32,
+    pub rating: i32,
+    pub comment: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[sqlx(skip)]
+    pub issues: Vec<FeedbackIssue>,
+}
+
+/// One participant's answer to a recording request, kept as the compliance
+/// record of who agreed to be recorded and when.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RecordingConsentDecision {
+    pub id: i64,
+    pub call_id: i32,
+    pub user_id: i32,
+    pub accepted: bool,
+    pub decided_at: String,
+}
+
+/// Where a single rung user stands on a call; the call's own status only
+/// becomes `Missed` or `Rejected` once nobody is left ringing.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CallInviteStatus {
+    Ringing,
+    Accepted,
+    Declined,
+    Missed,
+}
+
+impl fmt::Display for CallInviteStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            CallInviteStatus::Ringing => "ringing",
+            CallInviteStatus::Accepted => "accepted",
+            CallInviteStatus::Declined => "declined",
+            CallInviteStatus::Missed => "missed",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CallInvite {
+    pub call_id: i32,
+    pub user_id: i32,
+    pub invited_by: i32,
+    pub status: CallInviteStatus,
+    pub invited_at: String,
+    pub expires_at: String,
+    pub responded_at: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub enum SignalingMessage {
@@ -94,13 +445,71 @@ pub enum SignalingMessage {
         sdp_m_line_index: Option<u16>,
     },
 
+    /// Author and time are assigned by the server; any extra fields sent by
+    /// older clients are ignored.
     #[serde(rename = "chat_message")]
-    ChatMessage {
-        user_id: i32,
-        user_name: String,
-        message: String,
-        timestamp: u64,
+    ChatMessage { message: String },
+
+    /// Full local media state; turning audio or screen sharing on is
+    /// checked against the room's policies.
+    #[serde(rename = "media_state")]
+    MediaState {
+        audio_enabled: bool,
+        video_enabled: bool,
+        #[serde(default)]
+        screen_sharing: bool,
     },
+
+    #[serde(rename = "lobby_admit")]
+    LobbyAdmit { user_id: i32 },
+
+    #[serde(rename = "lobby_deny")]
+    LobbyDeny { user_id: i32 },
+
+    #[serde(rename = "lobby_admit_all")]
+    LobbyAdmitAll,
+
+    #[serde(rename = "lobby_deny_all")]
+    LobbyDenyAll,
+
+    /// Periodic connection statistics; the server keeps at most one sample
+    /// per participant every few seconds.
+    #[serde(rename = "quality_stats")]
+    QualityStats(QualityStats),
+
+    /// Asks everyone on the call to consent to being recorded.
+    #[serde(rename = "recording_start")]
+    RecordingStart,
+
+    #[serde(rename = "recording_stop")]
+    RecordingStop,
+
+    #[serde(rename = "recording_consent")]
+    RecordingConsent { accepted: bool },
+
+    /// A finished caption segment, relayed to the room and kept for the
+    /// call's transcript.
+    #[serde(rename = "caption")]
+    Caption(NewCaption),
+
+    #[serde(rename = "raise_hand")]
+    RaiseHand,
+
+    /// Lowers the sender's own hand, or someone else's if a room moderator
+    /// sends it with their `user_id`.
+    #[serde(rename = "lower_hand")]
+    LowerHand {
+        #[serde(default)]
+        user_id: Option<i32>,
+    },
+
+    /// Lowers every hand on the call; room moderators only.
+    #[serde(rename = "clear_hands")]
+    ClearHands,
+
+    /// Shown briefly to everyone on the call and not stored.
+    #[serde(rename = "reaction")]
+    Reaction { emoji: String },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -109,6 +518,7 @@ pub enum ServerMessage {
     #[serde(rename = "user-joined")]
     UserJoined {
         user_id: i32,
+        call_id: i32,
         users: Vec<(i32, String)>,
     },
 
@@ -133,6 +543,179 @@ pub enum ServerMessage {
         sdp_m_line_index: Option<u16>,
     },
 
+    #[serde(rename = "lobby-waiting")]
+    LobbyWaiting { room_id: String },
+
+    #[serde(rename = "lobby-knock")]
+    LobbyKnock { user_id: i32, user_name: String },
+
+    #[serde(rename = "lobby-updated")]
+    LobbyUpdated { waiting: Vec<(i32, String)> },
+
+    #[serde(rename = "lobby-admitted")]
+    LobbyAdmitted { room_id: String },
+
+    #[serde(rename = "lobby-denied")]
+    LobbyDenied {
+        room_id: String,
+        reason: Option<String>,
+    },
+
+    #[serde(rename = "owner-changed")]
+    OwnerChanged {
+        room_id: String,
+        previous_owner_id: i32,
+        new_owner_id: i32,
+    },
+
+    #[serde(rename = "chat-message")]
+    ChatMessage(ChatMessage),
+
+    #[serde(rename = "chat-message-edited")]
+    ChatMessageEdited(ChatMessage),
+
+    #[serde(rename = "chat-message-deleted")]
+    ChatMessageDeleted { id: i32, room_id: String },
+
+    #[serde(rename = "chat-history")]
+    ChatHistory { messages: Vec<ChatMessage> },
+
+    #[serde(rename = "media-state-changed")]
+    MediaStateChanged {
+        user_id: i32,
+        audio_enabled: bool,
+        video_enabled: bool,
+        screen_sharing: bool,
+    },
+
+    #[serde(rename = "room-policies-updated")]
+    RoomPoliciesUpdated {
+        room_id: String,
+        policies: RoomPolicies,
+    },
+
+    /// Sent when the server has moved the socket into a breakout room; the
+    /// client should reset its peers and join the new room.
+    #[serde(rename = "breakout-opened")]
+    BreakoutOpened {
+        session_id: i32,
+        room_id: String,
+        name: String,
+        closes_at: Option<chrono::NaiveDateTime>,
+    },
+
+    #[serde(rename = "breakout-closed")]
+    BreakoutClosed { session_id: i32, room_id: String },
+
+    #[serde(rename = "breakout-broadcast")]
+    BreakoutBroadcast {
+        session_id: i32,
+        from: i32,
+        user_name: String,
+        message: String,
+    },
+
+    /// Pushed to a rung user on whatever socket they have open.
+    #[serde(rename = "incoming-call")]
+    IncomingCall {
+        call_id: i32,
+        room_id: String,
+        from: i32,
+        user_name: String,
+        expires_at: String,
+    },
+
+    #[serde(rename = "call-invite-updated")]
+    CallInviteUpdated {
+        call_id: i32,
+        user_id: i32,
+        status: CallInviteStatus,
+        call_status: CallStatus,
+    },
+
+    #[serde(rename = "meeting-reminder")]
+    MeetingReminder {
+        meeting_id: i32,
+        title: String,
+        room_id: String,
+        starts_at: chrono::DateTime<chrono::Utc>,
+    },
+
+    /// The persistent recording indicator, sent to everyone on the call
+    /// whenever it changes and to anyone who joins while it is shown.
+    #[serde(rename = "recording-state")]
+    RecordingState {
+        call_id: i32,
+        status: RecordingIndicator,
+        started_by: i32,
+        awaiting: Vec<i32>,
+        declined: Vec<i32>,
+    },
+
+    #[serde(rename = "recording-consent-requested")]
+    RecordingConsentRequested {
+        call_id: i32,
+        started_by: i32,
+        user_name: String,
+    },
+
+    /// Sent to a participant who refused; their microphone and camera have
+    /// been turned off and they may leave the call instead.
+    #[serde(rename = "recording-declined")]
+    RecordingDeclined { call_id: i32 },
+
+    /// Sent to someone who joins while a recording is live; their microphone
+    /// and camera stay off until they accept it.
+    #[serde(rename = "recording-consent-pending")]
+    RecordingConsentPending { call_id: i32 },
+
+    #[serde(rename = "caption")]
+    Caption(CaptionSegment),
+
+    /// Sent to everyone on a call as one of its limits approaches.
+    #[serde(rename = "call-limit-warning")]
+    CallLimitWarning {
+        call_id: i32,
+        limit: CallLimit,
+        seconds_remaining: i64,
+    },
+
+    /// The server ended the call; the client should hang up.
+    #[serde(rename = "call-ended")]
+    CallEnded { call_id: i32, reason: CallLimit },
+
+    /// The call's raised-hand queue, first raised first. Sent to everyone on
+    /// the call whenever it changes and to anyone who joins while hands are
+    /// up.
+    #[serde(rename = "raised-hands")]
+    RaisedHands {
+        call_id: i32,
+        hands: Vec<RaisedHand>,
+    },
+
+    #[serde(rename = "reaction")]
+    Reaction {
+        call_id: i32,
+        user_id: i32,
+        user_name: String,
+        emoji: String,
+    },
+
     #[serde(rename = "error")]
     Error { message: String },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_status_transitions() {
+        assert!(CallStatus::Ringing.can_transition_to(&CallStatus::Active));
+        assert!(CallStatus::Active.can_transition_to(&CallStatus::Ended));
+        assert!(!CallStatus::Ended.can_transition_to(&CallStatus::Active));
+        assert!(!CallStatus::Active.can_transition_to(&CallStatus::Ringing));
+        assert!(!CallStatus::Active.can_transition_to(&CallStatus::Active));
+        assert!(!CallStatus::Missed.can_transition_to(&CallStatus::Ended));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    calls::{
+        contract::{CallFeedbackSummary, IssueCount, SubmitFeedback},
+        entities::{CallFeedback, FeedbackIssue},
+    },
+    shared::response::AppError,
+};
+
+pub const MAX_COMMENT_LENGTH: usize = 2000;
+
+pub fn validate_feedback(feedback: &SubmitFeedback) -> Result<(), AppError> {
+    if !(1..=5).contains(&feedback.rating) {
+        return Err(AppError::Validation(
+            "Rating must be between 1 and 5".into(),
+        ));
+    }
+    if feedback
+        .comment
+        .as_deref()
+        .is_some_and(|comment| comment.chars().count() > MAX_COMMENT_LENGTH)
+    {
+        return Err(AppError::Validation(format!(
+            "Comment cannot be longer than {} characters",
+            MAX_COMMENT_LENGTH
+        )));
+    }
+    Ok(())
+}
+
+/// Each issue once, in the order first reported.
+pub fn unique_issues(issues: &[FeedbackIssue]) -> Vec<FeedbackIssue> {
+    let mut unique = Vec::new();
+    for issue in issues {
+        if !unique.contains(issue) {
+            unique.push(*issue);
+        }
+    }
+    unique
+}
+
+/// Blank comments are stored as no comment.
+pub fn clean_comment(comment: Option<&str>) -> Option<String> {
+    comment
+        .map(str::trim)
+        .filter(|comment| !comment.is_empty())
+        .map(str::to_string)
+}
+
+pub fn summarize(call_id: i32, feedback: Vec<CallFeedback>) -> CallFeedbackSummary {
+    let average_rating = (!feedback.is_empty()).then(|| {
+        let total: i32 = feedback.iter().map(|entry| entry.rating).sum();
+        (total as f64 / feedback.len() as f64 * 100.0).round() / 100.0
+    });
+
+    let issues = [
+        FeedbackIssue::Echo,
+        FeedbackIssue::FrozenVideo,
+        FeedbackIssue::Dropped,
+    ]
+    .into_iter()
+    .map(|issue| IssueCount {
+        issue,
+        count: feedback
+            .iter()
+            .filter(|entry| entry.issues.contains(&issue))
+            .count(),
+    })
+    .collect();
+
+    CallFeedbackSummary {
+        call_id,
+        responses: feedback.len(),
+        average_rating,
+        issues,
+        feedback,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(user_id: i32, rating: i32, issues: Vec<FeedbackIssue>) -> CallFeedback {
+        CallFeedback {
+            id: user_id as i64,
+            call_id: 1,
+            user_id,
+            rating,
+            comment: None,
+            created_at: "2025-10-22 09:00:00".to_string(),
+            updated_at: "2025-10-22 09:00:00".to_string(),
+            issues,
+        }
+    }
+
+    #[test]
+    fn test_summarize_feedback() {
+        let summary = summarize(
+            1,
+            vec![
+                entry(3, 5, vec![]),
+                entry(4, 2, vec![FeedbackIssue::Echo, FeedbackIssue::Dropped]),
+                entry(5, 2, vec![FeedbackIssue::Echo]),
+            ],
+        );
+
+        assert_eq!(summary.responses, 3);
+        assert_eq!(summary.average_rating, Some(3.0));
+        let counts: Vec<usize> = summary.issues.iter().map(|issue| issue.count).collect();
+        assert_eq!(counts, vec![2, 0, 1]);
+
+        assert_eq!(summarize(1, Vec::new()).average_rating, None);
+        assert_eq!(clean_comment(Some("  ")), None);
+        assert_eq!(
+            unique_issues(&[
+                FeedbackIssue::Dropped,
+                FeedbackIssue::Echo,
+                FeedbackIssue::Dropped
+            ]),
+            vec![FeedbackIssue::Dropped, FeedbackIssue::Echo]
+        );
+    }
+}
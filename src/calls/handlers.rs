@@ -2,16 +2,22 @@ use std::sync::Arc;
 
 use actix_identity::Identity;
 use actix_web::{
-    HttpRequest, HttpResponse, Result as ActixResult, get, http::header, post, rt, web,
+    HttpRequest, HttpResponse, Result as ActixResult, get, http::header, post, routes, rt, web,
 };
 use actix_ws::AggregatedMessage;
 use futures::StreamExt;
 
 use crate::{
     calls::{
-        CallService,
-        contract::{NewCall, UpdateCallStatus},
+        CallService, SignalingServer,
+        contract::{
+            AcceptedCall, CallEventParams, CallHistoryParams, DirectCallRequest, DirectCallSession,
+            NewCall, RingRequest, SubmitFeedback, TranscriptParams, UpdateCallStatus,
+        },
+        entities::{CallStatus, EndedBy},
+        transcript::TranscriptFormat,
     },
+    infrastructure::turn,
     shared::response::{AppError, respond_ok},
 };
 
@@ -45,18 +51,347 @@ pub async fn create_call(
     respond_ok(call)
 }
 
+#[post("/direct")]
+pub async fn start_direct_call(
+    identity: Identity,
+    request: web::Json<DirectCallRequest>,
+    call_service: web::Data<Arc<dyn CallService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let direct_call = call_service
+        .start_direct_call(user_id, request.target_user_id)
+        .await?;
+
+    if direct_call.call.status == CallStatus::Ringing {
+        let invites = call_service
+            .list_call_invites(direct_call.call.id, user_id)
+            .await?;
+        signaling_server.ring(&direct_call.call, &invites).await?;
+    }
+
+    let room_id = direct_call.room.id.clone();
+
+    respond_ok(DirectCallSession {
+        direct_call,
+        socket_path: format!("/call/ws/rooms/{}", room_id),
+        call_page_path: format!("/turn-credentials?room_id={}", room_id),
+        ice_servers: turn::ice_servers(user_id),
+    })
+}
+
+#[post("/ring")]
+pub async fn ring_users(
+    identity: Identity,
+    request: web::Json<RingRequest>,
+    call_service: web::Data<Arc<dyn CallService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+    let request = request.into_inner();
+
+    let ringing = call_service
+        .ring_users(user_id, &request.room_id, request.user_ids)
+        .await?;
+
+    signaling_server
+        .ring(&ringing.call, &ringing.invites)
+        .await?;
+    respond_ok(ringing)
+}
+
+#[post("/{call_id}/accept")]
+pub async fn accept_call(
+    call_id: web::Path<i32>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let update = call_service
+        .accept_call(call_id.into_inner(), user_id)
+        .await?;
+
+    signaling_server.notify_invite_updated(&update);
+
+    let room_id = update.call.room_id.clone();
+    respond_ok(AcceptedCall {
+        update,
+        socket_path: format!("/call/ws/rooms/{}", room_id),
+        call_page_path: format!("/turn-credentials?room_id={}", room_id),
+        ice_servers: turn::ice_servers(user_id),
+    })
+}
+
+#[post("/{call_id}/decline")]
+pub async fn decline_call(
+    call_id: web::Path<i32>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let update = call_service
+        .decline_call(call_id.into_inner(), user_id)
+        .await?;
+
+    signaling_server.notify_invite_updated(&update);
+    respond_ok(update)
+}
+
+#[get("/{call_id}/invites")]
+pub async fn list_call_invites(
+    call_id: web::Path<i32>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let call_id = call_id.into_inner();
+    let invites = call_service.list_call_invites(call_id, user_id).await?;
+    respond_ok(invites)
+}
+
 #[post("/{call_id}/update-status")]
 pub async fn update_call_status(
     call_id: web::Path<i32>,
     status_json: web::Json<UpdateCallStatus>,
+    identity: Identity,
     call_service: web::Data<Arc<dyn CallService>>,
 ) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let call = call_service
+        .update_call_status(call_id.into_inner(), user_id, status_json.status.clone())
+        .await?;
+
+    respond_ok(call)
+}
+
+#[get("/{call_id}/events")]
+pub async fn list_call_events(
+    call_id: web::Path<i32>,
+    query: web::Query<CallEventParams>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let page = call_service
+        .list_events(call_id.into_inner(), user_id, query.into_inner())
+        .await?;
+    respond_ok(page)
+}
+
+#[get("/history")]
+pub async fn list_call_history(
+    query: web::Query<CallHistoryParams>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let page = call_service
+        .list_history(user_id, query.into_inner())
+        .await?;
+    respond_ok(page)
+}
+
+#[get("/history/export")]
+pub async fn export_call_history(
+    query: web::Query<CallHistoryParams>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let csv = call_service
+        .export_history(user_id, query.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .append_header((
+            header::CONTENT_DISPOSITION,
+            "attachment; filename=\"call-history.csv\"",
+        ))
+        .body(csv))
+}
+
+#[get("/{call_id}/quality")]
+pub async fn get_quality_report(
+    call_id: web::Path<i32>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let report = call_service
+        .get_quality_report(call_id.into_inner(), user_id)
+        .await?;
+    respond_ok(report)
+}
+
+#[get("/{call_id}/recording-consents")]
+pub async fn list_recording_consents(
+    call_id: web::Path<i32>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let decisions = call_service
+        .list_recording_consents(call_id.into_inner(), user_id)
+        .await?;
+    respond_ok(decisions)
+}
+
+#[get("/{call_id}/transcript")]
+pub async fn export_transcript(
+    call_id: web::Path<i32>,
+    query: web::Query<TranscriptParams>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
     let call_id = call_id.into_inner();
-    call_service
-        .update_call_status(call_id, status_json.status.clone())
+    let format = query
+        .format
+        .as_deref()
+        .unwrap_or("vtt")
+        .parse::<TranscriptFormat>()?;
+
+    let transcript = call_service
+        .export_transcript(call_id, user_id, format)
         .await?;
 
-    respond_ok("Call status updated successfully")
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .append_header((
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"call-{}-transcript.{}\"",
+                call_id,
+                format.extension()
+            ),
+        ))
+        .body(transcript))
+}
+
+#[post("/{call_id}/feedback")]
+pub async fn submit_feedback(
+    call_id: web::Path<i32>,
+    payload: web::Json<SubmitFeedback>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let feedback = call_service
+        .submit_feedback(call_id.into_inner(), user_id, payload.into_inner())
+        .await?;
+    respond_ok(feedback)
+}
+
+#[get("/{call_id}/feedback")]
+pub async fn get_call_feedback(
+    call_id: web::Path<i32>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let summary = call_service
+        .get_feedback(call_id.into_inner(), user_id)
+        .await?;
+    respond_ok(summary)
+}
+
+#[get("/{call_id}/events/export")]
+pub async fn export_call_events(
+    call_id: web::Path<i32>,
+    query: web::Query<CallEventParams>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+    let call_id = call_id.into_inner();
+
+    let ndjson = call_service
+        .export_events(call_id, user_id, query.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/x-ndjson")
+        .append_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"call-{}-events.ndjson\"", call_id),
+        ))
+        .body(ndjson))
+}
+
+#[get("/{call_id}/history")]
+pub async fn get_status_history(
+    call_id: web::Path<i32>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let call_id = call_id.into_inner();
+    let history = call_service.get_status_history(call_id, user_id).await?;
+    respond_ok(history)
 }
 
 #[post("/{call_id}/end")]
@@ -78,7 +413,9 @@ pub async fn end_call(
         }
     };
 
-    call_service.end_call(call_id, user_id).await?;
+    call_service
+        .end_call(call_id, EndedBy::User(user_id))
+        .await?;
     respond_ok("Call ended successfully")
 }
 
@@ -102,6 +439,9 @@ pub async fn get_calls_by_user_id(
     respond_ok(calls)
 }
 
+/// Also served at the old `/active` path, which existing clients still call.
+#[routes]
+#[get("/user/{user_id}/participated")]
 #[get("/user/{user_id}/active")]
 pub async fn get_user_participated_calls(
     user_id: web::Path<i32>,
@@ -130,6 +470,24 @@ pub async fn list_call_participants(
     respond_ok(participants)
 }
 
+#[get("/{call_id}/participants/sessions")]
+pub async fn list_participant_sessions(
+    call_id: web::Path<i32>,
+    identity: Identity,
+    call_service: web::Data<Arc<dyn CallService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let call_id = call_id.into_inner();
+    let sessions = call_service
+        .list_participant_sessions(call_id, user_id)
+        .await?;
+    respond_ok(sessions)
+}
+
 #[post("/{call_id}/participants/add")]
 pub async fn add_call_participant(
     call_id: web::Path<i32>,
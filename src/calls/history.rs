@@ -0,0 +1,217 @@
+use base64::{Engine as _, engine::general_purpose};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    calls::{
+        contract::CallHistoryParams,
+        entities::{CallDirection, CallHistoryEntry, CallStatus},
+    },
+    shared::response::AppError,
+};
+
+pub const DEFAULT_HISTORY_PAGE_SIZE: i64 = 25;
+pub const MAX_HISTORY_PAGE_SIZE: i64 = 100;
+/// Exports matching more calls than this are refused; narrowing the date
+/// range brings them under it.
+pub const MAX_EXPORT_ROWS: i64 = 10_000;
+
+/// Position of the last call on a page; history is newest first, with the
+/// call id as a tie-breaker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryCursor {
+    pub started_at: String,
+    pub id: i32,
+}
+
+impl HistoryCursor {
+    pub fn after(entry: &CallHistoryEntry) -> Self {
+        Self {
+            started_at: entry.started_at.clone(),
+            id: entry.id,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        general_purpose::URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(value: &str) -> Result<Self, AppError> {
+        let bytes = general_purpose::URL_SAFE_NO_PAD
+            .decode(value)
+            .map_err(|_| AppError::Validation("Invalid pagination cursor".to_string()))?;
+
+        serde_json::from_slice(&bytes)
+            .map_err(|_| AppError::Validation("Invalid pagination cursor".to_string()))
+    }
+}
+
+/// A validated history query. Exports leave `limit` unset; the service caps
+/// them at [`MAX_EXPORT_ROWS`].
+#[derive(Debug, Clone, Default)]
+pub struct CallHistoryFilter {
+    pub direction: Option<CallDirection>,
+    pub statuses: Vec<CallStatus>,
+    pub counterpart_id: Option<i32>,
+    pub room_id: Option<String>,
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+    pub cursor: Option<HistoryCursor>,
+    pub limit: Option<i64>,
+}
+
+impl CallHistoryFilter {
+    pub fn from_params(params: CallHistoryParams, paginate: bool) -> Result<Self, AppError> {
+        if let (Some(from), Some(to)) = (params.from, params.to)
+            && from >= to
+        {
+            return Err(AppError::Validation("from must be before to".into()));
+        }
+
+        let (cursor, limit) = if paginate {
+            let limit = params.limit.unwrap_or(DEFAULT_HISTORY_PAGE_SIZE);
+            if !(1..=MAX_HISTORY_PAGE_SIZE).contains(&limit) {
+                return Err(AppError::Validation(format!(
+                    "Limit must be between 1 and {}",
+                    MAX_HISTORY_PAGE_SIZE
+                )));
+            }
+            let cursor = params
+                .cursor
+                .as_deref()
+                .map(HistoryCursor::decode)
+                .transpose()?;
+            (cursor, Some(limit))
+        } else {
+            (None, None)
+        };
+
+        let statuses = params
+            .status
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|status| !status.is_empty())
+            .map(str::parse::<CallStatus>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            direction: params
+                .direction
+                .as_deref()
+                .map(str::parse::<CallDirection>)
+                .transpose()?,
+            statuses,
+            counterpart_id: params.counterpart_id,
+            room_id: params.room_id,
+            from: params.from,
+            to: params.to,
+            cursor,
+            limit,
+        })
+    }
+}
+
+pub const CSV_HEADER: &str = "call_id,started_at,ended_at,duration_secs,status,direction,room_id,room_name,caller,participants";
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+/// Fields that a spreadsheet would read as a formula get a leading
+/// apostrophe, since names and room titles are user input.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// One row per call with a header line; participant names are separated by
+/// semicolons.
+pub fn to_csv(entries: &[CallHistoryEntry]) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+
+    for entry in entries {
+        let participants: Vec<&str> = entry
+            .participants
+            .iter()
+            .map(|participant| participant.name.as_str())
+            .collect();
+        let direction = match entry.direction {
+            CallDirection::Made => "made",
+            CallDirection::Received => "received",
+        };
+
+        let fields = [
+            entry.id.to_string(),
+            entry.started_at.clone(),
+            entry.ended_at.clone().unwrap_or_default(),
+            entry.duration.map(|d| d.to_string()).unwrap_or_default(),
+            entry.status.to_string(),
+            direction.to_string(),
+            entry.room_id.clone(),
+            entry.room_name.clone().unwrap_or_default(),
+            entry.caller_name.clone(),
+            participants.join("; "),
+        ];
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        out.push_str(&line.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calls::entities::HistoryParticipant;
+
+    #[test]
+    fn test_history_csv_and_cursor() {
+        let entry = CallHistoryEntry {
+            id: 4,
+            room_id: "room-1".to_string(),
+            room_name: Some("Design, weekly".to_string()),
+            caller_id: 1,
+            caller_name: "Ada \"The Countess\" Lovelace".to_string(),
+            direction: CallDirection::Received,
+            status: CallStatus::Ended,
+            started_at: "2025-10-18 09:00:00".to_string(),
+            ended_at: Some("2025-10-18 09:30:00".to_string()),
+            duration: Some(1800),
+            participants: vec![
+                HistoryParticipant {
+                    user_id: 1,
+                    name: "Ada Lovelace".to_string(),
+                },
+                HistoryParticipant {
+                    user_id: 2,
+                    name: "Alan Turing".to_string(),
+                },
+            ],
+        };
+
+        let csv = to_csv(std::slice::from_ref(&entry));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[1],
+            "4,2025-10-18 09:00:00,2025-10-18 09:30:00,1800,ended,received,room-1,\
+             \"Design, weekly\",\"Ada \"\"The Countess\"\" Lovelace\",Ada Lovelace; Alan Turing"
+        );
+
+        assert_eq!(csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_field("-1+2"), "'-1+2");
+        assert_eq!(csv_field("Ada-Lovelace"), "Ada-Lovelace");
+
+        let cursor = HistoryCursor::after(&entry);
+        assert_eq!(HistoryCursor::decode(&cursor.encode()).unwrap(), cursor);
+    }
+}
@@ -0,0 +1,273 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    calls::{CallService, SignalingServer},
+    rooms::RoomService,
+    shared::response::AppError,
+};
+
+const JANITOR_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long a call or membership must exist without a socket before the
+/// janitor treats it as abandoned. Protects work that is still being set up.
+const STALE_AFTER_SECS: i64 = 120;
+
+#[derive(Debug, Default)]
+pub struct JanitorReport {
+    pub ended_calls: u64,
+    pub closed_participants: u64,
+    pub members_left: u64,
+    pub rooms_archived: u64,
+    /// Items that could not be cleaned up; they are retried on the next pass.
+    pub failures: u64,
+}
+
+impl JanitorReport {
+    fn is_empty(&self) -> bool {
+        self.ended_calls == 0
+            && self.closed_participants == 0
+            && self.members_left == 0
+            && self.rooms_archived == 0
+            && self.failures == 0
+    }
+
+    /// Logs a failed step and counts it, so one bad row does not stop the
+    /// rest of the pass.
+    fn failed(&mut self, what: String, error: AppError) {
+        eprintln!("Janitor could not {}: {}", what, error);
+        self.failures += 1;
+    }
+}
+
+/// Brings the database in line with who is actually connected. At startup no
+/// one is connected yet, so everything left over from a previous run is closed.
+pub async fn reconcile(
+    call_service: &Arc<dyn CallService>,
+    room_service: &Arc<dyn RoomService>,
+    signaling_server: &SignalingServer,
+    stale_after_secs: i64,
+) -> JanitorReport {
+    let mut report = JanitorReport::default();
+
+    let calls = match call_service.get_unfinished_calls(stale_after_secs).await {
+        Ok(calls) => calls,
+        Err(e) => {
+            report.failed("list unfinished calls".into(), e);
+            Vec::new()
+        }
+    };
+    for call in calls {
+        if signaling_server.has_connections(&call.room_id) {
+            let participants = match call_service.list_active_participants(call.id).await {
+                Ok(participants) => participants,
+                Err(e) => {
+                    report.failed(format!("list participants of call {}", call.id), e);
+                    continue;
+                }
+            };
+            for participant in participants {
+                if signaling_server.is_on_call(participant.user_id, call.id) {
+                    continue;
+                }
+                match call_service
+                    .remove_call_participant(call.id, participant.user_id)
+                    .await
+                {
+                    Ok(()) => report.closed_participants += 1,
+                    Err(e) => report.failed(
+                        format!("remove user {} from call {}", participant.user_id, call.id),
+                        e,
+                    ),
+                }
+            }
+            continue;
+        }
+
+        match call_service.end_orphaned_call(call.id).await {
+            Ok(()) => report.ended_calls += 1,
+            Err(e) => report.failed(format!("end call {}", call.id), e),
+        }
+    }
+
+    match call_service.close_dangling_participants().await {
+        Ok(closed) => report.closed_participants += closed,
+        Err(e) => report.failed("close dangling participants".into(), e),
+    }
+
+    let members = match room_service
+        .list_session_room_members(stale_after_secs)
+        .await
+    {
+        Ok(members) => members,
+        Err(e) => {
+            report.failed("list stale room members".into(), e);
+            Vec::new()
+        }
+    };
+    for member in members {
+        if signaling_server.is_connected_to_room(member.user_id, &member.room_id) {
+            continue;
+        }
+
+        match room_service
+            .leave_room(&member.room_id, member.user_id)
+            .await
+        {
+            Ok(transfer) => {
+                if let Some(transfer) = transfer {
+                    signaling_server.notify_owner_changed(&transfer);
+                }
+                report.members_left += 1;
+            }
+            Err(e) => report.failed(
+                format!(
+                    "remove user {} from room {}",
+                    member.user_id, member.room_id
+                ),
+                e,
+            ),
+        }
+    }
+
+    match room_service.archive_empty_instant_rooms().await {
+        Ok(archived) => report.rooms_archived += archived,
+        Err(e) => report.failed("archive empty instant rooms".into(), e),
+    }
+
+    report
+}
+
+/// Runs a full reconciliation before the server accepts connections.
+pub async fn run_startup_reconciliation(
+    call_service: &Arc<dyn CallService>,
+    room_service: &Arc<dyn RoomService>,
+    signaling_server: &SignalingServer,
+) {
+    let report = reconcile(call_service, room_service, signaling_server, 0).await;
+    println!("Startup reconciliation: {:?}", report);
+}
+
+pub fn spawn_janitor_task(
+    call_service: Arc<dyn CallService>,
+    room_service: Arc<dyn RoomService>,
+    signaling_server: Arc<SignalingServer>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(JANITOR_INTERVAL);
+        // The first tick completes immediately and startup has just reconciled
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+
+            let report = reconcile(
+                &call_service,
+                &room_service,
+                &signaling_server,
+                STALE_AFTER_SECS,
+            )
+            .await;
+            if !report.is_empty() {
+                println!("Janitor: {:?}", report);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        calls::{CallServiceImpl, SqliteCallRepository},
+        chat::{ChatServiceImpl, SqliteChatRepository},
+        rooms::{RoomServiceImpl, SqliteRoomRepository},
+        shared::test_db::{self, TEST_ROOM_ID},
+        users::{SqliteUserRepository, UserServiceImpl},
+    };
+
+    #[tokio::test]
+    async fn test_reconcile_closes_what_no_socket_backs() {
+        let pool = test_db::memory_pool().await;
+        let room_service: Arc<dyn RoomService> = Arc::new(RoomServiceImpl::new(Arc::new(
+            SqliteRoomRepository::new(pool.clone()),
+        )));
+        let call_service: Arc<dyn CallService> = Arc::new(CallServiceImpl::new(
+            Arc::new(SqliteCallRepository::new(pool.clone())),
+            room_service.clone(),
+            Arc::new(UserServiceImpl::new(Arc::new(SqliteUserRepository::new(
+                pool.clone(),
+            )))),
+            30,
+        ));
+        let server = SignalingServer::new(
+            call_service.clone(),
+            room_service.clone(),
+            Arc::new(ChatServiceImpl::new(
+                Arc::new(SqliteChatRepository::new(pool.clone())),
+                room_service.clone(),
+            )),
+        );
+
+        // User 1 is connected and on the call; user 3's session has no socket
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        server
+            .add_connection(1, TEST_ROOM_ID.to_string(), None, sender)
+            .await
+            .unwrap();
+        let live_call = server.join_call(1, TEST_ROOM_ID.to_string()).await.unwrap();
+        room_service.join_room(TEST_ROOM_ID, 3, None).await.unwrap();
+        call_service
+            .add_call_participant(live_call, 3)
+            .await
+            .unwrap();
+
+        // Nobody is connected to the instant room or its call any more
+        let instant = room_service
+            .create_room("Huddle".to_string(), "instant".to_string(), 2, None, None)
+            .await
+            .unwrap();
+        let orphaned_call = server.join_call(2, instant.id.clone()).await.unwrap();
+
+        let report = reconcile(&call_service, &room_service, &server, 0).await;
+        assert_eq!(report.failures, 0);
+        assert_eq!(report.ended_calls, 1);
+        assert_eq!(report.members_left, 1);
+
+        let open = call_service
+            .get_open_call_by_room_id(TEST_ROOM_ID)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(open.id, live_call);
+        assert_eq!(
+            call_service
+                .count_active_participants(live_call)
+                .await
+                .unwrap(),
+            1
+        );
+        // Group room membership is not tied to a socket
+        assert!(room_service.is_user_in_room(TEST_ROOM_ID, 3).await.unwrap());
+
+        assert!(
+            call_service
+                .get_open_call_by_room_id(&instant.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            call_service
+                .count_active_participants(orphaned_call)
+                .await
+                .unwrap(),
+            0
+        );
+        let instant = room_service.get_room(&instant.id).await.unwrap().unwrap();
+        assert!(instant.is_archived());
+
+        // A second pass finds nothing left to do
+        let report = reconcile(&call_service, &room_service, &server, 0).await;
+        assert!(report.is_empty());
+    }
+}
@@ -0,0 +1,336 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    calls::{CallService, SignalingServer, entities::EndedBy},
+    rooms::{RoomService, RoomType, search::SQLITE_TIMESTAMP_FORMAT},
+};
+
+const LIMITS_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Participants are warned this many seconds before a limit ends the call.
+pub const WARNING_THRESHOLDS_SECS: [i64; 3] = [600, 300, 60];
+
+const MAX_LIMIT_MINS: i64 = 7 * 24 * 60;
+
+/// The limits the signalling server enforces on a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallLimit {
+    /// The call has run for as long as its room type allows.
+    MaxDuration,
+    /// A single participant has been on the call by themselves.
+    Alone,
+    /// Nobody on the call has sent media or reported a media-state change.
+    Inactive,
+}
+
+/// Limits for one room type, in seconds. `None` turns a limit off; the idle
+/// timeout is off unless configured.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CallLimits {
+    pub max_duration_secs: Option<i64>,
+    pub alone_timeout_secs: Option<i64>,
+    pub idle_timeout_secs: Option<i64>,
+}
+
+impl Default for CallLimits {
+    fn default() -> Self {
+        Self {
+            max_duration_secs: Some(4 * 60 * 60),
+            alone_timeout_secs: Some(10 * 60),
+            idle_timeout_secs: None,
+        }
+    }
+}
+
+/// What a call has been doing, as tracked by the signalling server.
+#[derive(Debug, Clone, Copy)]
+pub struct CallActivity {
+    pub started_at: NaiveDateTime,
+    pub last_media_activity: NaiveDateTime,
+    /// When the call dropped to a single participant, if it has.
+    pub alone_since: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LimitCheck {
+    Within,
+    /// The limit is this many seconds away.
+    Approaching(CallLimit, i64),
+    Reached(CallLimit),
+}
+
+/// The limits of every room type, read once at startup.
+#[derive(Debug, Clone)]
+pub struct CallLimitsConfig {
+    by_room_type: Vec<(RoomType, CallLimits)>,
+}
+
+impl CallLimitsConfig {
+    /// Reads `CALL_MAX_DURATION_MINS`, `CALL_ALONE_TIMEOUT_MINS` and
+    /// `CALL_IDLE_TIMEOUT_MINS`, each of which can be overridden for one room
+    /// type with a suffix such as `_MEETING` or `_ONE_ON_ONE`. `0` turns a
+    /// limit off, and the idle timeout only applies when one is set.
+    pub fn from_env() -> Self {
+        let by_room_type = [
+            RoomType::Public,
+            RoomType::Private,
+            RoomType::OneOnOne,
+            RoomType::Group,
+            RoomType::Meeting,
+            RoomType::Instant,
+        ]
+        .into_iter()
+        .map(|room_type| {
+            let limits = CallLimits::from_env(&room_type);
+            (room_type, limits)
+        })
+        .collect();
+
+        Self { by_room_type }
+    }
+
+    pub fn for_room_type(&self, room_type: &RoomType) -> CallLimits {
+        self.by_room_type
+            .iter()
+            .find(|(candidate, _)| candidate == room_type)
+            .map(|(_, limits)| *limits)
+            .unwrap_or_default()
+    }
+}
+
+impl CallLimits {
+    fn from_env(room_type: &RoomType) -> Self {
+        let defaults = Self::default();
+        let suffix = room_type.to_string().to_uppercase();
+
+        Self {
+            max_duration_secs: limit_from_env(
+                "CALL_MAX_DURATION_MINS",
+                &suffix,
+                defaults.max_duration_secs,
+            ),
+            alone_timeout_secs: limit_from_env(
+                "CALL_ALONE_TIMEOUT_MINS",
+                &suffix,
+                defaults.alone_timeout_secs,
+            ),
+            idle_timeout_secs: limit_from_env(
+                "CALL_IDLE_TIMEOUT_MINS",
+                &suffix,
+                defaults.idle_timeout_secs,
+            ),
+        }
+    }
+
+    /// The limit closest to ending the call, if any is reached or within
+    /// warning distance.
+    pub fn check(&self, activity: &CallActivity, now: NaiveDateTime) -> LimitCheck {
+        let deadlines = [
+            (
+                CallLimit::MaxDuration,
+                Some(activity.started_at),
+                self.max_duration_secs,
+            ),
+            (
+                CallLimit::Alone,
+                activity.alone_since,
+                self.alone_timeout_secs,
+            ),
+            (
+                CallLimit::Inactive,
+                Some(activity.last_media_activity),
+                self.idle_timeout_secs,
+            ),
+        ];
+
+        let closest = deadlines
+            .into_iter()
+            .filter_map(|(limit, since, timeout)| {
+                let elapsed = (now - since?).num_seconds();
+                Some((limit, timeout? - elapsed))
+            })
+            .min_by_key(|(_, remaining)| *remaining);
+
+        match closest {
+            Some((limit, remaining)) if remaining <= 0 => LimitCheck::Reached(limit),
+            Some((limit, remaining)) if remaining <= WARNING_THRESHOLDS_SECS[0] => {
+                LimitCheck::Approaching(limit, remaining)
+            }
+            _ => LimitCheck::Within,
+        }
+    }
+}
+
+fn limit_from_env(name: &str, suffix: &str, default: Option<i64>) -> Option<i64> {
+    let specific = format!("{}_{}", name, suffix);
+    let (name, value) = match std::env::var(&specific) {
+        Ok(value) => (specific, value),
+        Err(_) => match std::env::var(name) {
+            Ok(value) => (name.to_string(), value),
+            Err(_) => return default,
+        },
+    };
+
+    match value.trim().parse::<i64>() {
+        Ok(0) => None,
+        Ok(mins) if (1..=MAX_LIMIT_MINS).contains(&mins) => Some(mins * 60),
+        _ => {
+            eprintln!(
+                "Ignoring {}={}: expected 0 to turn it off or 1 to {} minutes",
+                name, value, MAX_LIMIT_MINS
+            );
+            default
+        }
+    }
+}
+
+/// The warning threshold to announce now, given the last one announced for
+/// the same limit. Each threshold is announced once.
+pub fn warning_due(remaining_secs: i64, last_warned: Option<i64>) -> Option<i64> {
+    let threshold = WARNING_THRESHOLDS_SECS
+        .into_iter()
+        .filter(|threshold| remaining_secs <= *threshold)
+        .min()?;
+
+    last_warned
+        .is_none_or(|warned| threshold < warned)
+        .then_some(threshold)
+}
+
+/// Ends calls that have run past their room type's limits and warns the
+/// participants beforehand.
+pub fn spawn_call_limits_task(
+    call_service: Arc<dyn CallService>,
+    room_service: Arc<dyn RoomService>,
+    signaling_server: Arc<SignalingServer>,
+    config: CallLimitsConfig,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(LIMITS_POLL_INTERVAL);
+        let mut warned: HashMap<(i32, CallLimit), i64> = HashMap::new();
+
+        loop {
+            interval.tick().await;
+
+            let calls = match call_service.get_active_calls().await {
+                Ok(calls) => calls,
+                Err(e) => {
+                    eprintln!("Failed to load active calls: {}", e);
+                    continue;
+                }
+            };
+            warned.retain(|(call_id, _), _| calls.iter().any(|call| call.id == *call_id));
+
+            let now = Utc::now().naive_utc();
+            for call in calls {
+                let Ok(started_at) =
+                    NaiveDateTime::parse_from_str(&call.started_at, SQLITE_TIMESTAMP_FORMAT)
+                else {
+                    continue;
+                };
+                // Calls nobody is connected to are left to the janitor
+                let Some(activity) = signaling_server.call_activity(call.id, started_at, now)
+                else {
+                    continue;
+                };
+                let limits = match room_service.get_room(&call.room_id).await {
+                    Ok(Some(room)) => config.for_room_type(&room.room_type),
+                    Ok(None) => continue,
+                    Err(e) => {
+                        eprintln!("Failed to load room {}: {}", call.room_id, e);
+                        continue;
+                    }
+                };
+
+                match limits.check(&activity, now) {
+                    LimitCheck::Within => {
+                        warned.retain(|(call_id, _), _| *call_id != call.id);
+                    }
+                    LimitCheck::Approaching(limit, remaining) => {
+                        let key = (call.id, limit);
+                        // A timer that was reset since the last warning starts over
+                        let last_warned = warned
+                            .get(&key)
+                            .copied()
+                            .filter(|warned| remaining <= *warned);
+                        if let Some(threshold) = warning_due(remaining, last_warned) {
+                            warned.insert(key, threshold);
+                            signaling_server.warn_call_limit(call.id, limit, remaining);
+                        }
+                    }
+                    LimitCheck::Reached(limit) => {
+                        match call_service.end_call(call.id, EndedBy::Limit(limit)).await {
+                            Ok(()) => signaling_server.close_call(call.id, limit).await,
+                            Err(e) => eprintln!("Failed to end call {}: {}", call.id, e),
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closest_limit_is_checked_and_warned_once_per_threshold() {
+        let at = |mins: i64| {
+            chrono::NaiveDate::from_ymd_opt(2025, 10, 21)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap()
+                + chrono::Duration::minutes(mins)
+        };
+        let limits = CallLimits {
+            max_duration_secs: Some(60 * 60),
+            alone_timeout_secs: Some(10 * 60),
+            idle_timeout_secs: None,
+        };
+        let mut activity = CallActivity {
+            started_at: at(0),
+            last_media_activity: at(0),
+            alone_since: None,
+        };
+
+        assert_eq!(limits.check(&activity, at(30)), LimitCheck::Within);
+        assert_eq!(
+            limits.check(&activity, at(55)),
+            LimitCheck::Approaching(CallLimit::MaxDuration, 300)
+        );
+
+        activity.alone_since = Some(at(45));
+        assert_eq!(
+            limits.check(&activity, at(55)),
+            LimitCheck::Reached(CallLimit::Alone)
+        );
+
+        // The idle timeout is opt-in
+        assert_eq!(CallLimits::default().idle_timeout_secs, None);
+        let idle = CallLimits {
+            idle_timeout_secs: Some(15 * 60),
+            ..limits
+        };
+        activity.alone_since = None;
+        activity.last_media_activity = at(40);
+        assert_eq!(
+            idle.check(&activity, at(52)),
+            LimitCheck::Approaching(CallLimit::Inactive, 180)
+        );
+        activity.last_media_activity = at(50);
+        assert_eq!(
+            idle.check(&activity, at(52)),
+            LimitCheck::Approaching(CallLimit::MaxDuration, 480)
+        );
+
+        assert_eq!(warning_due(590, None), Some(600));
+        assert_eq!(warning_due(580, Some(600)), None);
+        assert_eq!(warning_due(290, Some(600)), Some(300));
+        assert_eq!(warning_due(900, None), None);
+    }
+}
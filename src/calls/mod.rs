@@ -1,10 +1,20 @@
 pub mod contract;
 pub mod entities;
+pub mod feedback;
 pub mod handlers;
+pub mod history;
+pub mod janitor;
+pub mod limits;
+pub mod quality;
+pub mod reactions;
+pub mod recording_consent;
 pub mod repository;
+pub mod ringing;
 pub mod routes;
 pub mod service;
 pub mod signalling_server;
+pub mod timeline;
+pub mod transcript;
 pub mod websocket;
 
 pub use repository::{CallRepository, SqliteCallRepository};
@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::{
+    calls::{
+        contract::{CallQualityReport, ParticipantQuality},
+        entities::{CallQualitySample, IceCandidateType, QualityStats},
+    },
+    shared::response::AppError,
+};
+
+/// Samples closer together than this are dropped, so a client reporting every
+/// second costs the same as one reporting every ten.
+pub const SAMPLE_INTERVAL_SECS: i64 = 10;
+
+pub const POOR_RTT_MS: f64 = 300.0;
+pub const POOR_JITTER_MS: f64 = 30.0;
+pub const POOR_PACKET_LOSS_PCT: f64 = 5.0;
+pub const POOR_BITRATE_KBPS: f64 = 150.0;
+pub const POOR_FRAME_RATE: f64 = 15.0;
+
+/// Why a participant's call quality was flagged as poor.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityIssue {
+    HighLatency,
+    HighJitter,
+    PacketLoss,
+    LowBitrate,
+    LowFrameRate,
+}
+
+pub fn validate_stats(stats: &QualityStats) -> Result<(), AppError> {
+    let metrics = [
+        ("rtt_ms", stats.rtt_ms),
+        ("jitter_ms", stats.jitter_ms),
+        ("packet_loss_pct", stats.packet_loss_pct),
+        ("bitrate_kbps", stats.bitrate_kbps),
+        ("frame_rate", stats.frame_rate),
+    ];
+
+    for (name, value) in metrics {
+        if let Some(value) = value
+            && (!value.is_finite() || value < 0.0)
+        {
+            return Err(AppError::Validation(format!(
+                "{} must be a non-negative number",
+                name
+            )));
+        }
+    }
+
+    if stats.packet_loss_pct.is_some_and(|loss| loss > 100.0) {
+        return Err(AppError::Validation(
+            "packet_loss_pct must be between 0 and 100".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Averages each participant's samples and flags anyone whose averages cross
+/// the `POOR_*` thresholds. Bitrate and frame rate are only judged for
+/// participants that sent video, since audio-only streams are naturally low.
+pub fn build_report(call_id: i32, samples: &[CallQualitySample]) -> CallQualityReport {
+    let mut by_user: BTreeMap<i32, Vec<&CallQualitySample>> = BTreeMap::new();
+    for sample in samples {
+        by_user.entry(sample.user_id).or_default().push(sample);
+    }
+
+    let participants: Vec<ParticipantQuality> = by_user
+        .into_iter()
+        .map(|(user_id, samples)| summarize(user_id, &samples))
+        .collect();
+
+    CallQualityReport {
+        call_id,
+        sample_interval_secs: SAMPLE_INTERVAL_SECS,
+        poor_participants: participants.iter().filter(|p| p.is_poor).count(),
+        participants,
+    }
+}
+
+fn summarize(user_id: i32, samples: &[&CallQualitySample]) -> ParticipantQuality {
+    let average = |metric: fn(&CallQualitySample) -> Option<f64>| {
+        let values: Vec<f64> = samples.iter().filter_map(|s| metric(s)).collect();
+        (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+    };
+    let maximum = |metric: fn(&CallQualitySample) -> Option<f64>| {
+        samples.iter().filter_map(|s| metric(s)).reduce(f64::max)
+    };
+
+    let avg_rtt_ms = average(|s| s.rtt_ms);
+    let avg_jitter_ms = average(|s| s.jitter_ms);
+    let avg_packet_loss_pct = average(|s| s.packet_loss_pct);
+    let avg_bitrate_kbps = average(|s| s.bitrate_kbps);
+    let avg_frame_rate = average(|s| s.frame_rate);
+
+    let mut candidate_types: Vec<IceCandidateType> = Vec::new();
+    for candidate_type in samples.iter().filter_map(|s| s.candidate_type) {
+        if !candidate_types.contains(&candidate_type) {
+            candidate_types.push(candidate_type);
+        }
+    }
+
+    let sends_video = avg_frame_rate.is_some_and(|fps| fps > 0.0);
+    let checks = [
+        (
+            avg_rtt_ms.is_some_and(|v| v > POOR_RTT_MS),
+            QualityIssue::HighLatency,
+        ),
+        (
+            avg_jitter_ms.is_some_and(|v| v > POOR_JITTER_MS),
+            QualityIssue::HighJitter,
+        ),
+        (
+            avg_packet_loss_pct.is_some_and(|v| v > POOR_PACKET_LOSS_PCT),
+            QualityIssue::PacketLoss,
+        ),
+        (
+            sends_video && avg_bitrate_kbps.is_some_and(|v| v < POOR_BITRATE_KBPS),
+            QualityIssue::LowBitrate,
+        ),
+        (
+            sends_video && avg_frame_rate.is_some_and(|v| v < POOR_FRAME_RATE),
+            QualityIssue::LowFrameRate,
+        ),
+    ];
+    let issues: Vec<QualityIssue> = checks
+        .into_iter()
+        .filter_map(|(failed, issue)| failed.then_some(issue))
+        .collect();
+
+    ParticipantQuality {
+        user_id,
+        samples: samples.len(),
+        first_sample_at: samples.first().map(|s| s.recorded_at.clone()),
+        last_sample_at: samples.last().map(|s| s.recorded_at.clone()),
+        avg_rtt_ms,
+        max_rtt_ms: maximum(|s| s.rtt_ms),
+        avg_jitter_ms,
+        avg_packet_loss_pct,
+        max_packet_loss_pct: maximum(|s| s.packet_loss_pct),
+        avg_bitrate_kbps,
+        avg_frame_rate,
+        candidate_types,
+        is_poor: !issues.is_empty(),
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(
+        user_id: i32,
+        rtt_ms: f64,
+        packet_loss_pct: f64,
+        frame_rate: Option<f64>,
+    ) -> CallQualitySample {
+        CallQualitySample {
+            id: 0,
+            call_id: 1,
+            user_id,
+            rtt_ms: Some(rtt_ms),
+            jitter_ms: Some(5.0),
+            packet_loss_pct: Some(packet_loss_pct),
+            bitrate_kbps: Some(64.0),
+            frame_rate,
+            candidate_type: Some(IceCandidateType::Relay),
+            recorded_at: "2025-10-19 09:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_report_flags_poor_participants() {
+        let samples = vec![
+            // Audio-only with a healthy link: low bitrate is expected
+            sample(1, 40.0, 0.0, None),
+            sample(1, 60.0, 1.0, None),
+            // Video on a lossy, slow link
+            sample(2, 500.0, 12.0, Some(10.0)),
+            sample(2, 300.0, 8.0, Some(12.0)),
+        ];
+
+        let report = build_report(1, &samples);
+        assert_eq!(report.poor_participants, 1);
+
+        let healthy = &report.participants[0];
+        assert_eq!(healthy.avg_rtt_ms, Some(50.0));
+        assert!(!healthy.is_poor);
+
+        let poor = &report.participants[1];
+        assert_eq!(poor.max_rtt_ms, Some(500.0));
+        assert_eq!(poor.candidate_types, vec![IceCandidateType::Relay]);
+        assert_eq!(
+            poor.issues,
+            vec![
+                QualityIssue::HighLatency,
+                QualityIssue::PacketLoss,
+                QualityIssue::LowBitrate,
+                QualityIssue::LowFrameRate,
+            ]
+        );
+
+        assert!(
+            validate_stats(&QualityStats {
+                packet_loss_pct: Some(120.0),
+                ..Default::default()
+            })
+            .is_err()
+        );
+        assert!(
+            validate_stats(&QualityStats {
+                rtt_ms: Some(f64::NAN),
+                ..Default::default()
+            })
+            .is_err()
+        );
+    }
+}
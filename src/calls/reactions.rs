@@ -0,0 +1,187 @@
+use chrono::NaiveDateTime;
+
+use crate::{calls::entities::RaisedHand, shared::response::AppError};
+
+/// Long enough for the longest ZWJ sequences, such as a couple with two
+/// skin tones.
+pub const MAX_REACTION_CHARS: usize = 16;
+
+const VARIATION_SELECTOR_16: char = '\u{FE0F}';
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+const COMBINING_KEYCAP: char = '\u{20E3}';
+
+/// Hands raised on one call, in the order they went up.
+#[derive(Debug, Clone, Default)]
+pub struct HandQueue {
+    hands: Vec<RaisedHand>,
+}
+
+impl HandQueue {
+    /// Puts the user at the back of the queue. Returns false if their hand
+    /// was already up, in which case they keep their place.
+    pub fn raise(&mut self, user_id: i32, user_name: String, raised_at: NaiveDateTime) -> bool {
+        if self.hands.iter().any(|hand| hand.user_id == user_id) {
+            return false;
+        }
+        self.hands.push(RaisedHand {
+            user_id,
+            user_name,
+            raised_at,
+        });
+        true
+    }
+
+    /// Returns false if the user's hand was not up.
+    pub fn lower(&mut self, user_id: i32) -> bool {
+        let before = self.hands.len();
+        self.hands.retain(|hand| hand.user_id != user_id);
+        self.hands.len() != before
+    }
+
+    pub fn clear(&mut self) -> bool {
+        let had_hands = !self.hands.is_empty();
+        self.hands.clear();
+        had_hands
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hands.is_empty()
+    }
+
+    pub fn hands(&self) -> Vec<RaisedHand> {
+        self.hands.clone()
+    }
+}
+
+/// Reactions are relayed as-is, so only a single emoji is accepted;
+/// anything else is a chat message instead.
+pub fn validate_reaction(emoji: &str) -> Result<String, AppError> {
+    let emoji = emoji.trim();
+    if emoji.is_empty() {
+        return Err(AppError::Validation("Reaction cannot be empty".into()));
+    }
+    if emoji.chars().count() > MAX_REACTION_CHARS || !is_single_emoji(emoji) {
+        return Err(AppError::Validation(
+            "Reactions must be a single emoji".into(),
+        ));
+    }
+    Ok(emoji.to_string())
+}
+
+/// A keycap, a flag, or pictographs joined by ZWJ, each of which may carry
+/// a presentation selector, skin tone or tag sequence.
+fn is_single_emoji(value: &str) -> bool {
+    let chars: Vec<char> = value.chars().collect();
+    match chars.as_slice() {
+        ['0'..='9' | '#' | '*', rest @ ..] => matches!(
+            rest,
+            [COMBINING_KEYCAP] | [VARIATION_SELECTOR_16, COMBINING_KEYCAP]
+        ),
+        [first, second] if is_regional_indicator(*first) && is_regional_indicator(*second) => true,
+        _ => value.split(ZERO_WIDTH_JOINER).all(|part| {
+            let mut chars = part.chars();
+            chars.next().is_some_and(is_pictographic)
+                && chars.all(|c| c == VARIATION_SELECTOR_16 || is_skin_tone(c) || is_tag(c))
+        }),
+    }
+}
+
+/// Code points with an emoji presentation, roughly Unicode's
+/// `Extended_Pictographic` property.
+fn is_pictographic(c: char) -> bool {
+    !is_skin_tone(c)
+        && !is_regional_indicator(c)
+        && matches!(
+            c as u32,
+            0x00A9
+                | 0x00AE
+                | 0x203C
+                | 0x2049
+                | 0x2122
+                | 0x2139
+                | 0x2194..=0x2199
+                | 0x21A9..=0x21AA
+                | 0x231A..=0x231B
+                | 0x2328
+                | 0x23CF
+                | 0x23E9..=0x23F3
+                | 0x23F8..=0x23FA
+                | 0x24C2
+                | 0x25AA..=0x25AB
+                | 0x25B6
+                | 0x25C0
+                | 0x25FB..=0x25FE
+                | 0x2600..=0x27BF
+                | 0x2934..=0x2935
+                | 0x2B05..=0x2B07
+                | 0x2B1B..=0x2B1C
+                | 0x2B50
+                | 0x2B55
+                | 0x3030
+                | 0x303D
+                | 0x3297
+                | 0x3299
+                | 0x1F000..=0x1FAFF
+        )
+}
+
+fn is_skin_tone(c: char) -> bool {
+    ('\u{1F3FB}'..='\u{1F3FF}').contains(&c)
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+/// Tag characters, used by subdivision flags such as England's.
+fn is_tag(c: char) -> bool {
+    ('\u{E0020}'..='\u{E007F}').contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hand_queue_keeps_raise_order() {
+        let at = chrono::NaiveDate::from_ymd_opt(2025, 10, 22)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let mut queue = HandQueue::default();
+
+        assert!(queue.raise(2, "Bea".into(), at));
+        assert!(queue.raise(1, "Ada".into(), at));
+        assert!(!queue.raise(2, "Bea".into(), at));
+        let order: Vec<i32> = queue.hands().iter().map(|hand| hand.user_id).collect();
+        assert_eq!(order, vec![2, 1]);
+
+        assert!(queue.lower(2));
+        assert!(!queue.lower(2));
+        assert!(queue.raise(2, "Bea".into(), at));
+        let order: Vec<i32> = queue.hands().iter().map(|hand| hand.user_id).collect();
+        assert_eq!(order, vec![1, 2]);
+
+        assert!(queue.clear());
+        assert!(queue.is_empty());
+
+        assert_eq!(validate_reaction(" 👍 ").unwrap(), "👍");
+        for emoji in [
+            "👍🏽",
+            "❤️",
+            "❤",
+            "1️⃣",
+            "#⃣",
+            "🇳🇱",
+            "🏴\u{E0067}\u{E0062}\u{E0065}\u{E006E}\u{E0067}\u{E007F}",
+            "👩🏽\u{200D}💻",
+            "👨\u{200D}👩\u{200D}👧\u{200D}👦",
+        ] {
+            assert!(validate_reaction(emoji).is_ok(), "{}", emoji);
+        }
+        for text in ["lol", "", "1", "════", "→", "👍👍", "🇳", "👍\u{200D}", "🏽"]
+        {
+            assert!(validate_reaction(text).is_err(), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// What the recording indicator shows to everyone on a call.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingIndicator {
+    /// Someone asked to record and the call is waiting for consent.
+    AwaitingConsent,
+    Recording,
+    Stopped,
+}
+
+/// Consent collected for a recording on one call. Whoever starts the
+/// recording consents by doing so; everyone else on the call, including
+/// people who join later, has to answer.
+#[derive(Debug, Clone)]
+pub struct RecordingConsent {
+    pub started_by: i32,
+    pub indicator: RecordingIndicator,
+    decisions: BTreeMap<i32, bool>,
+}
+
+impl RecordingConsent {
+    pub fn new(started_by: i32) -> Self {
+        Self {
+            started_by,
+            indicator: RecordingIndicator::AwaitingConsent,
+            decisions: BTreeMap::from([(started_by, true)]),
+        }
+    }
+
+    pub fn decide(&mut self, user_id: i32, accepted: bool) {
+        self.decisions.insert(user_id, accepted);
+    }
+
+    pub fn has_decided(&self, user_id: i32) -> bool {
+        self.decisions.contains_key(&user_id)
+    }
+
+    /// People on the call who have not answered yet.
+    pub fn awaiting(&self, on_call: &[i32]) -> Vec<i32> {
+        on_call
+            .iter()
+            .copied()
+            .filter(|user_id| !self.has_decided(*user_id))
+            .collect()
+    }
+
+    /// People on the call who refused to be recorded.
+    pub fn declined(&self, on_call: &[i32]) -> Vec<i32> {
+        on_call
+            .iter()
+            .copied()
+            .filter(|user_id| self.decisions.get(user_id) == Some(&false))
+            .collect()
+    }
+
+    /// Starts recording once everyone on the call has answered. Returns true
+    /// if the indicator changed.
+    /// Refusing does not block the recording: those participants are muted
+    /// instead.
+    pub fn settle(&mut self, on_call: &[i32]) -> bool {
+        if self.indicator == RecordingIndicator::AwaitingConsent
+            && self.awaiting(on_call).is_empty()
+        {
+            self.indicator = RecordingIndicator::Recording;
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_starts_once_everyone_has_answered() {
+        let mut consent = RecordingConsent::new(1);
+        let on_call = [1, 2, 3];
+
+        assert_eq!(consent.awaiting(&on_call), vec![2, 3]);
+        assert!(!consent.settle(&on_call));
+
+        consent.decide(2, false);
+        assert_eq!(consent.declined(&on_call), vec![2]);
+        assert!(!consent.settle(&on_call));
+
+        // Leaving the call counts as no longer blocking the recording
+        assert!(consent.settle(&[1, 2]));
+        assert_eq!(consent.indicator, RecordingIndicator::Recording);
+
+        // Someone joining later is asked, but the recording carries on
+        assert_eq!(consent.awaiting(&[1, 2, 4]), vec![4]);
+        assert!(!consent.settle(&[1, 2, 4]));
+        assert_eq!(consent.indicator, RecordingIndicator::Recording);
+    }
+}
@@ -1,8 +1,18 @@
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
 
 use crate::{
-    calls::entities::{Call, CallParticipant, CallStatus},
+    calls::{
+        entities::{
+            Call, CallDirection, CallEvent, CallFeedback, CallHistoryEntry, CallInvite,
+            CallInviteStatus, CallParticipant, CallParticipantSession, CallQualitySample,
+            CallStatus, CallStatusChange, CaptionSegment, FeedbackIssue, HistoryParticipant,
+            NewCallEvent, QualityStats, RecordingConsentDecision,
+        },
+        history::CallHistoryFilter,
+        timeline::CallEventFilter,
+    },
+    rooms::search::SQLITE_TIMESTAMP_FORMAT,
     shared::response::AppError,
 };
 
@@ -17,30 +27,67 @@ pub trait CallRepository {
 
     async fn get_call_by_id(&self, call_id: i32) -> Result<Option<Call>, AppError>;
 
-    async fn update_call_status(&self, call_id: i32, status: CallStatus) -> Result<(), AppError>;
+    /// Moves the call from `from` to `to` and records the change, stamping the
+    /// end time when `to` is final. Returns false if the call was no longer
+    /// in `from`.
+    async fn transition_call(
+        &self,
+        call_id: i32,
+        from: CallStatus,
+        to: CallStatus,
+        changed_by: Option<i32>,
+    ) -> Result<bool, AppError>;
 
-    async fn end_call(&self, call_id: i32) -> Result<(), AppError>;
+    async fn list_status_history(&self, call_id: i32) -> Result<Vec<CallStatusChange>, AppError>;
 
     // Room-based queries
     async fn get_calls_by_room_id(&self, room_id: &str) -> Result<Vec<Call>, AppError>;
 
     async fn get_active_calls_by_room_id(&self, room_id: &str) -> Result<Vec<Call>, AppError>;
 
+    /// The most recent call in the room that is still ringing or in progress.
+    async fn get_open_call_by_room_id(&self, room_id: &str) -> Result<Option<Call>, AppError>;
+
     // User-based queries
     async fn get_calls_by_user_id(&self, user_id: i32) -> Result<Vec<Call>, AppError>;
 
     async fn get_user_participated_calls(&self, user_id: i32) -> Result<Vec<Call>, AppError>;
 
+    /// Calls the user placed, joined or was rung for, newest first.
+    async fn list_history(
+        &self,
+        user_id: i32,
+        filter: &CallHistoryFilter,
+    ) -> Result<Vec<CallHistoryEntry>, AppError>;
+
+    /// Names of everyone who joined each call, keyed by call id.
+    async fn list_history_participants(
+        &self,
+        call_ids: &[i32],
+    ) -> Result<Vec<(i32, HistoryParticipant)>, AppError>;
+
     // Active calls
     async fn get_active_calls(&self) -> Result<Vec<Call>, AppError>;
 
+    /// Calls that have not reached a terminal status, started at least
+    /// `min_age_secs` seconds ago and are no longer ringing anyone.
+    async fn get_unfinished_calls(&self, min_age_secs: i64) -> Result<Vec<Call>, AppError>;
+
     // Participant management
     async fn list_call_participants(&self, call_id: i32) -> Result<Vec<CallParticipant>, AppError>;
 
+    /// Opens a new session for the user, keeping their first `joined_at`.
+    /// Does nothing if the user already has an open session.
     async fn add_call_participant(&self, call_id: i32, user_id: i32) -> Result<(), AppError>;
 
+    /// Closes the user's open session and adds its length to their total.
     async fn remove_call_participant(&self, call_id: i32, user_id: i32) -> Result<(), AppError>;
 
+    async fn list_participant_sessions(
+        &self,
+        call_id: i32,
+    ) -> Result<Vec<CallParticipantSession>, AppError>;
+
     async fn list_active_participants(
         &self,
         call_id: i32,
@@ -49,6 +96,126 @@ pub trait CallRepository {
     async fn count_active_participants(&self, call_id: i32) -> Result<i64, AppError>;
 
     async fn is_user_participant(&self, call_id: i32, user_id: i32) -> Result<bool, AppError>;
+
+    /// Closes sessions still open on calls that have already finished, using
+    /// the call's end time as the leave time.
+    async fn close_dangling_participants(&self) -> Result<u64, AppError>;
+
+    // Ringing
+    /// Rings the users, restarting the timer for anyone rung before.
+    async fn ring_users(
+        &self,
+        call_id: i32,
+        invited_by: i32,
+        user_ids: &[i32],
+        timeout_secs: i64,
+    ) -> Result<Vec<CallInvite>, AppError>;
+
+    async fn get_call_invite(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<Option<CallInvite>, AppError>;
+
+    async fn list_call_invites(&self, call_id: i32) -> Result<Vec<CallInvite>, AppError>;
+
+    /// Records the answer if the user is still ringing; returns the updated invite.
+    async fn answer_invite(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        status: CallInviteStatus,
+    ) -> Result<Option<CallInvite>, AppError>;
+
+    /// Marks as missed every invite whose timer ran out or whose call has
+    /// already finished.
+    async fn expire_invites(&self) -> Result<Vec<CallInvite>, AppError>;
+
+    // Timeline
+    async fn add_event(&self, event: &NewCallEvent) -> Result<(), AppError>;
+
+    async fn list_events(
+        &self,
+        call_id: i32,
+        filter: &CallEventFilter,
+    ) -> Result<Vec<CallEvent>, AppError>;
+
+    // Quality
+    /// Stores the sample unless the user already has one on this call from
+    /// the last `min_interval_secs` seconds; returns whether it was stored.
+    async fn add_quality_sample(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        stats: &QualityStats,
+        min_interval_secs: i64,
+    ) -> Result<bool, AppError>;
+
+    async fn list_quality_samples(&self, call_id: i32) -> Result<Vec<CallQualitySample>, AppError>;
+
+    // Recording consent
+    async fn add_recording_consent(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        accepted: bool,
+    ) -> Result<RecordingConsentDecision, AppError>;
+
+    async fn list_recording_consents(
+        &self,
+        call_id: i32,
+    ) -> Result<Vec<RecordingConsentDecision>, AppError>;
+
+    // Captions
+    async fn add_caption(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        text: &str,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<CaptionSegment, AppError>;
+
+    /// Captions ordered by when they were spoken.
+    async fn list_captions(&self, call_id: i32) -> Result<Vec<CaptionSegment>, AppError>;
+
+    // Feedback
+    /// Stores the user's rating of the call, replacing any earlier one.
+    async fn upsert_feedback(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        rating: i32,
+        comment: Option<&str>,
+        issues: &[FeedbackIssue],
+    ) -> Result<CallFeedback, AppError>;
+
+    async fn list_feedback(&self, call_id: i32) -> Result<Vec<CallFeedback>, AppError>;
+}
+
+/// Call ids bound per query when loading history participants.
+const HISTORY_PARTICIPANT_BATCH: usize = 500;
+
+const SELECT_CAPTION: &str = r#"
+    SELECT cc.id, cc.call_id, cc.user_id,
+        u.first_name || ' ' || u.last_name AS speaker_name,
+        cc.text, cc.start_ms, cc.end_ms, cc.created_at
+    FROM call_captions cc
+    JOIN users u ON u.id = cc.user_id
+"#;
+
+/// Matches calls of `c` the user placed, joined or was rung for.
+fn push_involves(query: &mut QueryBuilder<'_, Sqlite>, user_id: i32) {
+    query
+        .push("(c.caller_id = ")
+        .push_bind(user_id)
+        .push(
+            " OR EXISTS (SELECT 1 FROM call_participants p WHERE p.call_id = c.id AND p.user_id = ",
+        )
+        .push_bind(user_id)
+        .push(") OR EXISTS (SELECT 1 FROM call_invites i WHERE i.call_id = c.id AND i.user_id = ")
+        .push_bind(user_id)
+        .push("))");
 }
 
 pub struct SqliteCallRepository {
@@ -69,6 +236,8 @@ impl CallRepository for SqliteCallRepository {
         caller_id: i32,
         status: CallStatus,
     ) -> Result<Call, AppError> {
+        let mut tx = self.pool.begin().await?;
+
         let call = sqlx::query_as::<_, Call>(
             r#"
             INSERT INTO calls (room_id, caller_id, status)
@@ -78,10 +247,24 @@ impl CallRepository for SqliteCallRepository {
         )
         .bind(room_id)
         .bind(caller_id)
+        .bind(&status)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO call_status_history (call_id, to_status, changed_by)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(call.id)
         .bind(status)
-        .fetch_one(&self.pool)
+        .bind(caller_id)
+        .execute(&mut *tx)
         .await?;
 
+        tx.commit().await?;
+
         Ok(call)
     }
 
@@ -94,31 +277,66 @@ impl CallRepository for SqliteCallRepository {
         Ok(call)
     }
 
-    async fn update_call_status(&self, call_id: i32, status: CallStatus) -> Result<(), AppError> {
-        sqlx::query("UPDATE calls SET status = $1 WHERE id = $2")
-            .bind(status)
-            .bind(call_id)
-            .execute(&self.pool)
-            .await?;
+    async fn transition_call(
+        &self,
+        call_id: i32,
+        from: CallStatus,
+        to: CallStatus,
+        changed_by: Option<i32>,
+    ) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let finished = !to.is_open();
 
-        Ok(())
-    }
+        let updated = sqlx::query(
+            r#"
+            UPDATE calls
+            SET status = $1,
+                ended_at = CASE WHEN $2 THEN CURRENT_TIMESTAMP ELSE ended_at END,
+                duration = CASE
+                    WHEN $2 THEN CAST((julianday(CURRENT_TIMESTAMP) - julianday(started_at)) * 86400 AS INTEGER)
+                    ELSE duration
+                END
+            WHERE id = $3 AND status = $4
+            "#,
+        )
+        .bind(&to)
+        .bind(finished)
+        .bind(call_id)
+        .bind(&from)
+        .execute(&mut *tx)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            return Ok(false);
+        }
 
-    async fn end_call(&self, call_id: i32) -> Result<(), AppError> {
         sqlx::query(
             r#"
-            UPDATE calls 
-            SET status = 'ended', 
-                ended_at = CURRENT_TIMESTAMP,
-                duration = CAST((julianday(CURRENT_TIMESTAMP) - julianday(started_at)) * 86400 AS INTEGER)
-            WHERE id = $1
-            "#
+            INSERT INTO call_status_history (call_id, from_status, to_status, changed_by)
+            VALUES ($1, $2, $3, $4)
+            "#,
         )
         .bind(call_id)
-        .execute(&self.pool)
+        .bind(from)
+        .bind(to)
+        .bind(changed_by)
+        .execute(&mut *tx)
         .await?;
 
-        Ok(())
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    async fn list_status_history(&self, call_id: i32) -> Result<Vec<CallStatusChange>, AppError> {
+        let history = sqlx::query_as::<_, CallStatusChange>(
+            "SELECT * FROM call_status_history WHERE call_id = $1 ORDER BY id",
+        )
+        .bind(call_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(history)
     }
 
     async fn get_calls_by_room_id(&self, room_id: &str) -> Result<Vec<Call>, AppError> {
@@ -143,6 +361,22 @@ impl CallRepository for SqliteCallRepository {
         Ok(calls)
     }
 
+    async fn get_open_call_by_room_id(&self, room_id: &str) -> Result<Option<Call>, AppError> {
+        let call = sqlx::query_as::<_, Call>(
+            r#"
+            SELECT * FROM calls
+            WHERE room_id = $1 AND status IN ('initiated', 'ringing', 'active')
+            ORDER BY started_at DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(room_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(call)
+    }
+
     async fn get_calls_by_user_id(&self, user_id: i32) -> Result<Vec<Call>, AppError> {
         let calls = sqlx::query_as::<_, Call>(
             "SELECT * FROM calls WHERE caller_id = $1 ORDER BY started_at DESC",
@@ -171,6 +405,122 @@ impl CallRepository for SqliteCallRepository {
         Ok(calls)
     }
 
+    async fn list_history(
+        &self,
+        user_id: i32,
+        filter: &CallHistoryFilter,
+    ) -> Result<Vec<CallHistoryEntry>, AppError> {
+        let mut query = QueryBuilder::<Sqlite>::new(
+            r#"
+            SELECT
+                c.id, c.room_id, r.name AS room_name, c.caller_id,
+                COALESCE(cu.first_name || ' ' || cu.last_name, '') AS caller_name,
+                CASE WHEN c.caller_id = "#,
+        );
+        query.push_bind(user_id).push(
+            r#" THEN 'made' ELSE 'received' END AS direction,
+                c.status, c.started_at, c.ended_at, c.duration
+            FROM calls c
+            LEFT JOIN rooms r ON r.id = c.room_id
+            LEFT JOIN users cu ON cu.id = c.caller_id
+            WHERE "#,
+        );
+        push_involves(&mut query, user_id);
+
+        match filter.direction {
+            Some(CallDirection::Made) => {
+                query.push(" AND c.caller_id = ").push_bind(user_id);
+            }
+            Some(CallDirection::Received) => {
+                query.push(" AND c.caller_id <> ").push_bind(user_id);
+            }
+            None => {}
+        }
+        if !filter.statuses.is_empty() {
+            query.push(" AND c.status IN (");
+            let mut statuses = query.separated(", ");
+            for status in &filter.statuses {
+                statuses.push_bind(status.clone());
+            }
+            statuses.push_unseparated(")");
+        }
+        if let Some(counterpart_id) = filter.counterpart_id {
+            query.push(" AND ");
+            push_involves(&mut query, counterpart_id);
+        }
+        if let Some(room_id) = &filter.room_id {
+            query.push(" AND c.room_id = ").push_bind(room_id.clone());
+        }
+        if let Some(from) = filter.from {
+            query
+                .push(" AND c.started_at >= ")
+                .push_bind(from.format(SQLITE_TIMESTAMP_FORMAT).to_string());
+        }
+        if let Some(to) = filter.to {
+            query
+                .push(" AND c.started_at < ")
+                .push_bind(to.format(SQLITE_TIMESTAMP_FORMAT).to_string());
+        }
+        if let Some(cursor) = &filter.cursor {
+            query
+                .push(" AND (c.started_at, c.id) < (")
+                .push_bind(cursor.started_at.clone())
+                .push(", ")
+                .push_bind(cursor.id)
+                .push(")");
+        }
+
+        query.push(" ORDER BY c.started_at DESC, c.id DESC");
+        if let Some(limit) = filter.limit {
+            query.push(" LIMIT ").push_bind(limit);
+        }
+
+        let entries = query
+            .build_query_as::<CallHistoryEntry>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(entries)
+    }
+
+    async fn list_history_participants(
+        &self,
+        call_ids: &[i32],
+    ) -> Result<Vec<(i32, HistoryParticipant)>, AppError> {
+        if call_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut participants = Vec::new();
+        // Kept well under SQLite's limit on bound parameters per statement
+        for batch in call_ids.chunks(HISTORY_PARTICIPANT_BATCH) {
+            let mut query = QueryBuilder::<Sqlite>::new(
+                r#"
+                SELECT p.call_id, p.user_id, u.first_name || ' ' || u.last_name
+                FROM call_participants p
+                JOIN users u ON u.id = p.user_id
+                WHERE p.call_id IN ("#,
+            );
+            let mut ids = query.separated(", ");
+            for call_id in batch {
+                ids.push_bind(*call_id);
+            }
+            ids.push_unseparated(") ORDER BY p.call_id, p.joined_at");
+
+            let rows = query
+                .build_query_as::<(i32, i32, String)>()
+                .fetch_all(&self.pool)
+                .await?;
+            participants.extend(
+                rows.into_iter().map(|(call_id, user_id, name)| {
+                    (call_id, HistoryParticipant { user_id, name })
+                }),
+            );
+        }
+
+        Ok(participants)
+    }
+
     async fn get_active_calls(&self) -> Result<Vec<Call>, AppError> {
         let calls = sqlx::query_as::<_, Call>(
             "SELECT * FROM calls WHERE status = 'active' ORDER BY started_at DESC",
@@ -181,6 +531,26 @@ impl CallRepository for SqliteCallRepository {
         Ok(calls)
     }
 
+    async fn get_unfinished_calls(&self, min_age_secs: i64) -> Result<Vec<Call>, AppError> {
+        let calls = sqlx::query_as::<_, Call>(
+            r#"
+            SELECT * FROM calls
+            WHERE status IN ('initiated', 'ringing', 'active')
+                AND started_at <= datetime('now', $1)
+                AND NOT EXISTS (
+                    SELECT 1 FROM call_invites i
+                    WHERE i.call_id = calls.id AND i.status = 'ringing'
+                )
+            ORDER BY started_at
+            "#,
+        )
+        .bind(format!("-{} seconds", min_age_secs))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(calls)
+    }
+
     async fn list_call_participants(&self, call_id: i32) -> Result<Vec<CallParticipant>, AppError> {
         let participants = sqlx::query_as::<_, CallParticipant>(
             "SELECT * FROM call_participants WHERE call_id = $1 ORDER BY joined_at",
@@ -193,44 +563,106 @@ impl CallRepository for SqliteCallRepository {
     }
 
     async fn add_call_participant(&self, call_id: i32, user_id: i32) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO call_participant_sessions (call_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT (call_id, user_id) WHERE left_at IS NULL DO NOTHING
+            "#,
+        )
+        .bind(call_id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
         sqlx::query(
             r#"
             INSERT INTO call_participants (call_id, user_id)
             VALUES ($1, $2)
-            ON CONFLICT (call_id, user_id) DO NOTHING
+            ON CONFLICT (call_id, user_id) DO UPDATE SET left_at = NULL
             "#,
         )
         .bind(call_id)
         .bind(user_id)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
+        tx.commit().await?;
+
         Ok(())
     }
 
     async fn remove_call_participant(&self, call_id: i32, user_id: i32) -> Result<(), AppError> {
-        sqlx::query(
+        let mut tx = self.pool.begin().await?;
+
+        let closed = sqlx::query(
             r#"
-            UPDATE call_participants 
+            UPDATE call_participant_sessions
             SET left_at = CURRENT_TIMESTAMP,
-                duration = CAST((julianday(CURRENT_TIMESTAMP) - julianday(joined_at)) * 86400 AS INTEGER)
+                duration = MAX(0, CAST((julianday(CURRENT_TIMESTAMP) - julianday(joined_at)) * 86400 AS INTEGER))
             WHERE call_id = $1 AND user_id = $2 AND left_at IS NULL
-            "#
+            "#,
         )
         .bind(call_id)
         .bind(user_id)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
+        if closed.rows_affected() > 0 {
+            sqlx::query(
+                r#"
+                UPDATE call_participants
+                SET left_at = CURRENT_TIMESTAMP,
+                    duration = (
+                        SELECT COALESCE(SUM(s.duration), 0)
+                        FROM call_participant_sessions s
+                        WHERE s.call_id = call_participants.call_id
+                            AND s.user_id = call_participants.user_id
+                    )
+                WHERE call_id = $1 AND user_id = $2
+                "#,
+            )
+            .bind(call_id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
         Ok(())
     }
 
+    async fn list_participant_sessions(
+        &self,
+        call_id: i32,
+    ) -> Result<Vec<CallParticipantSession>, AppError> {
+        let sessions = sqlx::query_as::<_, CallParticipantSession>(
+            "SELECT * FROM call_participant_sessions WHERE call_id = $1 ORDER BY joined_at, id",
+        )
+        .bind(call_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sessions)
+    }
+
     async fn list_active_participants(
         &self,
         call_id: i32,
     ) -> Result<Vec<CallParticipant>, AppError> {
         let participants = sqlx::query_as::<_, CallParticipant>(
-            "SELECT * FROM call_participants WHERE call_id = $1 AND left_at IS NULL",
+            r#"
+            SELECT p.* FROM call_participants p
+            WHERE p.call_id = $1
+                AND EXISTS (
+                    SELECT 1 FROM call_participant_sessions s
+                    WHERE s.call_id = p.call_id AND s.user_id = p.user_id AND s.left_at IS NULL
+                )
+            ORDER BY p.joined_at
+            "#,
         )
         .bind(call_id)
         .fetch_all(&self.pool)
@@ -241,7 +673,11 @@ impl CallRepository for SqliteCallRepository {
 
     async fn count_active_participants(&self, call_id: i32) -> Result<i64, AppError> {
         let count = sqlx::query_scalar(
-            "SELECT COUNT(*) as count FROM call_participants WHERE call_id = $1 AND left_at IS NULL",
+            r#"
+            SELECT COUNT(DISTINCT user_id) as count
+            FROM call_participant_sessions
+            WHERE call_id = $1 AND left_at IS NULL
+            "#,
         )
         .bind(call_id)
         .fetch_one(&self.pool)
@@ -267,4 +703,522 @@ impl CallRepository for SqliteCallRepository {
 
         Ok(is_partipant)
     }
+
+    async fn close_dangling_participants(&self) -> Result<u64, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE call_participant_sessions
+            SET left_at = COALESCE(
+                    (SELECT c.ended_at FROM calls c WHERE c.id = call_participant_sessions.call_id),
+                    CURRENT_TIMESTAMP
+                ),
+                duration = MAX(0, CAST((julianday(COALESCE(
+                    (SELECT c.ended_at FROM calls c WHERE c.id = call_participant_sessions.call_id),
+                    CURRENT_TIMESTAMP
+                )) - julianday(joined_at)) * 86400 AS INTEGER))
+            WHERE left_at IS NULL
+                AND call_id IN (
+                    SELECT id FROM calls
+                    WHERE status NOT IN ('initiated', 'ringing', 'active')
+                )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE call_participants
+            SET left_at = COALESCE(
+                    (SELECT c.ended_at FROM calls c WHERE c.id = call_participants.call_id),
+                    CURRENT_TIMESTAMP
+                ),
+                duration = (
+                    SELECT COALESCE(SUM(s.duration), 0)
+                    FROM call_participant_sessions s
+                    WHERE s.call_id = call_participants.call_id
+                        AND s.user_id = call_participants.user_id
+                )
+            WHERE left_at IS NULL
+                AND call_id IN (
+                    SELECT id FROM calls
+                    WHERE status NOT IN ('initiated', 'ringing', 'active')
+                )
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn ring_users(
+        &self,
+        call_id: i32,
+        invited_by: i32,
+        user_ids: &[i32],
+        timeout_secs: i64,
+    ) -> Result<Vec<CallInvite>, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let mut invites = Vec::with_capacity(user_ids.len());
+
+        for user_id in user_ids {
+            let invite = sqlx::query_as::<_, CallInvite>(
+                r#"
+                INSERT INTO call_invites (call_id, user_id, invited_by, expires_at)
+                VALUES ($1, $2, $3, datetime('now', $4))
+                ON CONFLICT (call_id, user_id) DO UPDATE SET
+                    invited_by = excluded.invited_by,
+                    status = 'ringing',
+                    invited_at = CURRENT_TIMESTAMP,
+                    expires_at = excluded.expires_at,
+                    responded_at = NULL
+                RETURNING *
+                "#,
+            )
+            .bind(call_id)
+            .bind(user_id)
+            .bind(invited_by)
+            .bind(format!("+{} seconds", timeout_secs))
+            .fetch_one(&mut *tx)
+            .await?;
+
+            invites.push(invite);
+        }
+
+        tx.commit().await?;
+
+        Ok(invites)
+    }
+
+    async fn get_call_invite(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<Option<CallInvite>, AppError> {
+        let invite = sqlx::query_as::<_, CallInvite>(
+            "SELECT * FROM call_invites WHERE call_id = $1 AND user_id = $2",
+        )
+        .bind(call_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(invite)
+    }
+
+    async fn list_call_invites(&self, call_id: i32) -> Result<Vec<CallInvite>, AppError> {
+        let invites = sqlx::query_as::<_, CallInvite>(
+            "SELECT * FROM call_invites WHERE call_id = $1 ORDER BY invited_at, user_id",
+        )
+        .bind(call_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(invites)
+    }
+
+    async fn answer_invite(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        status: CallInviteStatus,
+    ) -> Result<Option<CallInvite>, AppError> {
+        let invite = sqlx::query_as::<_, CallInvite>(
+            r#"
+            UPDATE call_invites
+            SET status = $1, responded_at = CURRENT_TIMESTAMP
+            WHERE call_id = $2 AND user_id = $3 AND status = 'ringing'
+            RETURNING *
+            "#,
+        )
+        .bind(status)
+        .bind(call_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(invite)
+    }
+
+    async fn expire_invites(&self) -> Result<Vec<CallInvite>, AppError> {
+        let invites = sqlx::query_as::<_, CallInvite>(
+            r#"
+            UPDATE call_invites
+            SET status = 'missed', responded_at = CURRENT_TIMESTAMP
+            WHERE status = 'ringing'
+                AND (
+                    expires_at <= CURRENT_TIMESTAMP
+                    OR call_id IN (
+                        SELECT id FROM calls
+                        WHERE status NOT IN ('initiated', 'ringing', 'active')
+                    )
+                )
+            RETURNING *
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(invites)
+    }
+
+    async fn add_event(&self, event: &NewCallEvent) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO call_events (call_id, event_type, actor_id, subject_id, details)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(event.call_id)
+        .bind(event.event_type)
+        .bind(event.actor_id)
+        .bind(event.subject_id)
+        .bind(event.details.as_ref().map(|details| details.to_string()))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_events(
+        &self,
+        call_id: i32,
+        filter: &CallEventFilter,
+    ) -> Result<Vec<CallEvent>, AppError> {
+        let mut query = QueryBuilder::<Sqlite>::new("SELECT * FROM call_events WHERE call_id = ");
+        query.push_bind(call_id);
+
+        if !filter.event_types.is_empty() {
+            query.push(" AND event_type IN (");
+            let mut types = query.separated(", ");
+            for event_type in &filter.event_types {
+                types.push_bind(*event_type);
+            }
+            types.push_unseparated(")");
+        }
+        if let Some(actor_id) = filter.actor_id {
+            query.push(" AND actor_id = ").push_bind(actor_id);
+        }
+        if let Some(from) = filter.from {
+            query
+                .push(" AND created_at >= ")
+                .push_bind(from.format(SQLITE_TIMESTAMP_FORMAT).to_string());
+        }
+        if let Some(to) = filter.to {
+            query
+                .push(" AND created_at < ")
+                .push_bind(to.format(SQLITE_TIMESTAMP_FORMAT).to_string());
+        }
+        if let Some(after) = filter.after {
+            query.push(" AND id > ").push_bind(after);
+        }
+
+        query.push(" ORDER BY id");
+        if let Some(limit) = filter.limit {
+            query.push(" LIMIT ").push_bind(limit);
+        }
+
+        let events = query
+            .build_query_as::<CallEvent>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(events)
+    }
+
+    async fn add_quality_sample(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        stats: &QualityStats,
+        min_interval_secs: i64,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO call_quality_samples (
+                call_id, user_id, rtt_ms, jitter_ms, packet_loss_pct,
+                bitrate_kbps, frame_rate, candidate_type
+            )
+            SELECT $1, $2, $3, $4, $5, $6, $7, $8
+            WHERE NOT EXISTS (
+                SELECT 1 FROM call_quality_samples
+                WHERE call_id = $1 AND user_id = $2
+                    AND recorded_at > datetime('now', $9)
+            )
+            "#,
+        )
+        .bind(call_id)
+        .bind(user_id)
+        .bind(stats.rtt_ms)
+        .bind(stats.jitter_ms)
+        .bind(stats.packet_loss_pct)
+        .bind(stats.bitrate_kbps)
+        .bind(stats.frame_rate)
+        .bind(stats.candidate_type)
+        .bind(format!("-{} seconds", min_interval_secs))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_quality_samples(&self, call_id: i32) -> Result<Vec<CallQualitySample>, AppError> {
+        let samples = sqlx::query_as::<_, CallQualitySample>(
+            "SELECT * FROM call_quality_samples WHERE call_id = $1 ORDER BY user_id, recorded_at, id",
+        )
+        .bind(call_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(samples)
+    }
+
+    async fn add_recording_consent(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        accepted: bool,
+    ) -> Result<RecordingConsentDecision, AppError> {
+        let decision = sqlx::query_as::<_, RecordingConsentDecision>(
+            r#"
+            INSERT INTO call_recording_consents (call_id, user_id, accepted)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(call_id)
+        .bind(user_id)
+        .bind(accepted)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(decision)
+    }
+
+    async fn list_recording_consents(
+        &self,
+        call_id: i32,
+    ) -> Result<Vec<RecordingConsentDecision>, AppError> {
+        let decisions = sqlx::query_as::<_, RecordingConsentDecision>(
+            "SELECT * FROM call_recording_consents WHERE call_id = $1 ORDER BY decided_at, id",
+        )
+        .bind(call_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(decisions)
+    }
+
+    async fn add_caption(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        text: &str,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<CaptionSegment, AppError> {
+        let id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO call_captions (call_id, user_id, text, start_ms, end_ms)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+        )
+        .bind(call_id)
+        .bind(user_id)
+        .bind(text)
+        .bind(start_ms)
+        .bind(end_ms)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let caption =
+            sqlx::query_as::<_, CaptionSegment>(&format!("{} WHERE cc.id = $1", SELECT_CAPTION))
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await?;
+
+        Ok(caption)
+    }
+
+    async fn list_captions(&self, call_id: i32) -> Result<Vec<CaptionSegment>, AppError> {
+        let captions = sqlx::query_as::<_, CaptionSegment>(&format!(
+            "{} WHERE cc.call_id = $1 ORDER BY cc.start_ms, cc.id",
+            SELECT_CAPTION
+        ))
+        .bind(call_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(captions)
+    }
+
+    async fn upsert_feedback(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        rating: i32,
+        comment: Option<&str>,
+        issues: &[FeedbackIssue],
+    ) -> Result<CallFeedback, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut feedback = sqlx::query_as::<_, CallFeedback>(
+            r#"
+            INSERT INTO call_feedback (call_id, user_id, rating, comment)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (call_id, user_id) DO UPDATE SET
+                rating = excluded.rating,
+                comment = excluded.comment,
+                updated_at = CURRENT_TIMESTAMP
+            RETURNING *
+            "#,
+        )
+        .bind(call_id)
+        .bind(user_id)
+        .bind(rating)
+        .bind(comment)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM call_feedback_issues WHERE feedback_id = $1")
+            .bind(feedback.id)
+            .execute(&mut *tx)
+            .await?;
+
+        for issue in issues {
+            sqlx::query("INSERT INTO call_feedback_issues (feedback_id, issue) VALUES ($1, $2)")
+                .bind(feedback.id)
+                .bind(issue)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        feedback.issues = issues.to_vec();
+        Ok(feedback)
+    }
+
+    async fn list_feedback(&self, call_id: i32) -> Result<Vec<CallFeedback>, AppError> {
+        let mut feedback = sqlx::query_as::<_, CallFeedback>(
+            "SELECT * FROM call_feedback WHERE call_id = $1 ORDER BY created_at, id",
+        )
+        .bind(call_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let issues: Vec<(i64, FeedbackIssue)> = sqlx::query_as(
+            r#"
+            SELECT i.feedback_id, i.issue
+            FROM call_feedback_issues i
+            JOIN call_feedback f ON f.id = i.feedback_id
+            WHERE f.call_id = $1
+            ORDER BY i.feedback_id, i.issue
+            "#,
+        )
+        .bind(call_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        for entry in &mut feedback {
+            entry.issues = issues
+                .iter()
+                .filter(|(feedback_id, _)| *feedback_id == entry.id)
+                .map(|(_, issue)| *issue)
+                .collect();
+        }
+
+        Ok(feedback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::test_db::{self, TEST_ROOM_ID};
+
+    /// Moves the user's open session back in time, as if they joined
+    /// `seconds_ago` seconds ago.
+    async fn backdate_open_session(pool: &SqlitePool, user_id: i32, seconds_ago: i64) {
+        sqlx::query(
+            "UPDATE call_participant_sessions SET joined_at = datetime('now', $1) \
+             WHERE user_id = $2 AND left_at IS NULL",
+        )
+        .bind(format!("-{} seconds", seconds_ago))
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejoining_opens_a_new_session_and_totals_add_up() {
+        let pool = test_db::memory_pool().await;
+        let repo = SqliteCallRepository::new(pool.clone());
+        let call = repo
+            .create_call(TEST_ROOM_ID.to_string(), 1, CallStatus::Active)
+            .await
+            .unwrap();
+
+        repo.add_call_participant(call.id, 2).await.unwrap();
+        // Joining again while connected keeps the open session
+        repo.add_call_participant(call.id, 2).await.unwrap();
+        assert_eq!(repo.count_active_participants(call.id).await.unwrap(), 1);
+        backdate_open_session(&pool, 2, 100).await;
+        repo.remove_call_participant(call.id, 2).await.unwrap();
+        assert_eq!(repo.count_active_participants(call.id).await.unwrap(), 0);
+
+        repo.add_call_participant(call.id, 2).await.unwrap();
+        assert_eq!(
+            repo.list_active_participants(call.id).await.unwrap().len(),
+            1
+        );
+        backdate_open_session(&pool, 2, 30).await;
+        repo.remove_call_participant(call.id, 2).await.unwrap();
+
+        // Sessions are timed from CURRENT_TIMESTAMP, which may tick over
+        // between statements, and julianday arithmetic truncates down
+        let sessions = repo.list_participant_sessions(call.id).await.unwrap();
+        let durations: Vec<i32> = sessions.iter().map(|s| s.duration.unwrap()).collect();
+        assert_eq!(durations.len(), 2);
+        assert!((99..=101).contains(&durations[0]));
+        assert!((29..=31).contains(&durations[1]));
+        assert!(sessions.iter().all(|s| s.left_at.is_some()));
+
+        let participants = repo.list_call_participants(call.id).await.unwrap();
+        assert_eq!(participants.len(), 1);
+        assert_eq!(participants[0].duration, Some(durations[0] + durations[1]));
+        assert!(participants[0].left_at.is_some());
+
+        // A session left open on a call that has since ended is closed at
+        // the call's end and added to the total
+        repo.add_call_participant(call.id, 2).await.unwrap();
+        sqlx::query(
+            "UPDATE call_participant_sessions SET joined_at = datetime('now', '-70 seconds') \
+             WHERE left_at IS NULL",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "UPDATE calls SET status = 'ended', ended_at = datetime('now', '-10 seconds') \
+             WHERE id = $1",
+        )
+        .bind(call.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert_eq!(repo.close_dangling_participants().await.unwrap(), 1);
+
+        let participants = repo.list_call_participants(call.id).await.unwrap();
+        let before = durations[0] + durations[1];
+        let total = participants[0].duration.unwrap();
+        assert!((before + 59..=before + 61).contains(&total));
+    }
 }
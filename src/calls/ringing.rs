@@ -0,0 +1,51 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::calls::{CallService, SignalingServer};
+
+const RING_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub const DEFAULT_RING_TIMEOUT_SECS: i64 = 30;
+const MIN_RING_TIMEOUT_SECS: i64 = 5;
+const MAX_RING_TIMEOUT_SECS: i64 = 300;
+
+/// How long users are rung before the call counts as missed for them, read
+/// from `CALL_RING_TIMEOUT_SECS`.
+pub fn ring_timeout_from_env() -> i64 {
+    let Ok(value) = std::env::var("CALL_RING_TIMEOUT_SECS") else {
+        return DEFAULT_RING_TIMEOUT_SECS;
+    };
+
+    match value.trim().parse::<i64>() {
+        Ok(secs) if (MIN_RING_TIMEOUT_SECS..=MAX_RING_TIMEOUT_SECS).contains(&secs) => secs,
+        _ => {
+            eprintln!(
+                "Ignoring CALL_RING_TIMEOUT_SECS={}: expected {} to {} seconds",
+                value, MIN_RING_TIMEOUT_SECS, MAX_RING_TIMEOUT_SECS
+            );
+            DEFAULT_RING_TIMEOUT_SECS
+        }
+    }
+}
+
+/// Stops ringing users who have not answered in time and tells the caller.
+pub fn spawn_ring_timeout_task(
+    call_service: Arc<dyn CallService>,
+    signaling_server: Arc<SignalingServer>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(RING_POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match call_service.expire_invites().await {
+                Ok(updates) => {
+                    for update in &updates {
+                        signaling_server.notify_invite_updated(update);
+                    }
+                }
+                Err(e) => eprintln!("Failed to expire call invites: {}", e),
+            }
+        }
+    });
+}
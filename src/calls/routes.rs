@@ -11,16 +11,32 @@ pub fn call_routes(cfg: &mut web::ServiceConfig) {
             .wrap(middleware::from_fn(auth_middleware::auth))
             .service(websocket::websocket_handler)
             .service(websocket::test_videocall)
+            .service(handlers::export_call_history)
+            .service(handlers::list_call_history)
             .service(handlers::update_call_status)
+            .service(handlers::get_status_history)
+            .service(handlers::export_call_events)
+            .service(handlers::list_call_events)
+            .service(handlers::get_quality_report)
+            .service(handlers::list_recording_consents)
+            .service(handlers::export_transcript)
+            .service(handlers::submit_feedback)
+            .service(handlers::get_call_feedback)
             .service(handlers::end_call)
             .service(handlers::list_active_participants)
             .service(handlers::count_active_participants)
+            .service(handlers::list_participant_sessions)
             .service(handlers::list_call_participants)
+            .service(handlers::list_call_invites)
+            .service(handlers::accept_call)
+            .service(handlers::decline_call)
             .service(handlers::add_call_participant)
             .service(handlers::remove_call_participant)
             .service(handlers::get_user_participated_calls)
             .service(handlers::get_calls_by_room_id)
             .service(handlers::get_calls_by_user_id)
+            .service(handlers::start_direct_call)
+            .service(handlers::ring_users)
             .service(handlers::create_call)
             .service(handlers::get_active_calls)
             .service(handlers::echo)
@@ -1,13 +1,29 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use async_trait::async_trait;
+use serde_json::json;
 
 use crate::{
     calls::{
-        entities::{Call, CallParticipant, CallStatus},
+        contract::{
+            CallEventPage, CallEventParams, CallFeedbackSummary, CallHistoryPage,
+            CallHistoryParams, CallInviteUpdate, CallQualityReport, CallRinging, DirectCall,
+            SubmitFeedback,
+        },
+        entities::{
+            Call, CallEventType, CallFeedback, CallHistoryEntry, CallInvite, CallInviteStatus,
+            CallParticipant, CallParticipantSession, CallStatus, CallStatusChange, CaptionSegment,
+            EndedBy, NewCallEvent, NewCaption, QualityStats, RecordingConsentDecision,
+        },
+        feedback,
+        history::{self, CallHistoryFilter, HistoryCursor},
+        limits::CallLimit,
+        quality,
         repository::CallRepository,
+        timeline::{self, CallEventFilter},
+        transcript::{self, TranscriptFormat},
     },
-    rooms::RoomService,
+    rooms::{RoomAction, RoomService},
     shared::response::AppError,
     users::UserService,
 };
@@ -21,28 +37,207 @@ pub trait CallService: Send + Sync {
         status: String,
     ) -> Result<Call, AppError>;
 
+    /// Finds or creates the one-on-one room for the pair and rings the target,
+    /// reusing a call that is already ringing or in progress there.
+    async fn start_direct_call(
+        &self,
+        caller_id: i32,
+        target_user_id: i32,
+    ) -> Result<DirectCall, AppError>;
+
+    /// Rings the given users into the room's open call, placing a new ringing
+    /// call if there is none. Users who are not members yet are invited.
+    async fn ring_users(
+        &self,
+        caller_id: i32,
+        room_id: &str,
+        user_ids: Vec<i32>,
+    ) -> Result<CallRinging, AppError>;
+
+    async fn accept_call(&self, call_id: i32, user_id: i32) -> Result<CallInviteUpdate, AppError>;
+
+    async fn decline_call(&self, call_id: i32, user_id: i32) -> Result<CallInviteUpdate, AppError>;
+
+    /// The call's invites, visible to the people rung, the call's
+    /// participants, the room's moderators and admins.
+    async fn list_call_invites(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<CallInvite>, AppError>;
+
+    /// Marks unanswered invites as missed once their ring timeout has passed.
+    async fn expire_invites(&self) -> Result<Vec<CallInviteUpdate>, AppError>;
+
     async fn get_call_by_id(&self, call_id: i32) -> Result<Option<Call>, AppError>;
 
-    async fn update_call_status(&self, call_id: i32, status: String) -> Result<(), AppError>;
+    /// Moves a call to a new status on behalf of its caller or a room
+    /// moderator, rejecting transitions the call lifecycle does not allow.
+    async fn update_call_status(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        status: String,
+    ) -> Result<Call, AppError>;
+
+    /// Applies a server-driven status change, bypassing the caller/moderator
+    /// check but not the lifecycle rules. `changed_by` is the user whose
+    /// action caused it, if any.
+    async fn transition_call(
+        &self,
+        call_id: i32,
+        status: CallStatus,
+        changed_by: Option<i32>,
+    ) -> Result<Call, AppError>;
+
+    /// The call's status changes, visible to its participants, the room's
+    /// moderators and admins.
+    async fn get_status_history(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<CallStatusChange>, AppError>;
+
+    /// Adds an entry to the call's timeline.
+    async fn record_event(&self, event: NewCallEvent) -> Result<(), AppError>;
 
-    async fn end_call(&self, call_id: i32, user_id: i32) -> Result<(), AppError>;
+    /// A page of the call's timeline, visible to its participants, the
+    /// room's moderators and admins.
+    async fn list_events(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        params: CallEventParams,
+    ) -> Result<CallEventPage, AppError>;
+
+    /// Every matching timeline entry as newline-delimited JSON.
+    async fn export_events(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        params: CallEventParams,
+    ) -> Result<String, AppError>;
+
+    /// Stores a participant's connection statistics, downsampled to one
+    /// sample per `quality::SAMPLE_INTERVAL_SECS`. Returns whether it was kept.
+    async fn record_quality_sample(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        stats: QualityStats,
+    ) -> Result<bool, AppError>;
+
+    /// Per-participant connection quality, with poor connections flagged.
+    /// Visible to the same people as the timeline.
+    async fn get_quality_report(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<CallQualityReport, AppError>;
+
+    /// Logs a participant's answer to a recording request.
+    async fn record_recording_consent(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        accepted: bool,
+    ) -> Result<RecordingConsentDecision, AppError>;
+
+    /// Every consent decision made on the call, oldest first. Visible to the
+    /// same people as the timeline.
+    async fn list_recording_consents(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<RecordingConsentDecision>, AppError>;
+
+    /// Stores a caption segment spoken by the user on the call.
+    async fn record_caption(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        caption: NewCaption,
+    ) -> Result<CaptionSegment, AppError>;
+
+    /// The call's captions rendered as a transcript file. Visible to the
+    /// same people as the timeline.
+    async fn export_transcript(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        format: TranscriptFormat,
+    ) -> Result<String, AppError>;
+
+    /// Ends an active call. Users must be the caller or the room owner;
+    /// limits enforced by the signalling server skip that check.
+    /// Records the user's rating of a call they joined. Submitting again
+    /// replaces the earlier rating.
+    async fn submit_feedback(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        feedback: SubmitFeedback,
+    ) -> Result<CallFeedback, AppError>;
+
+    /// All ratings of the call with their average. Visible to the same
+    /// people as the timeline.
+    async fn get_feedback(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<CallFeedbackSummary, AppError>;
+
+    async fn end_call(&self, call_id: i32, ended_by: EndedBy) -> Result<(), AppError>;
 
     async fn get_calls_by_room_id(&self, room_id: &str) -> Result<Vec<Call>, AppError>;
 
     async fn get_active_calls_by_room_id(&self, room_id: &str) -> Result<Vec<Call>, AppError>;
 
+    async fn get_open_call_by_room_id(&self, room_id: &str) -> Result<Option<Call>, AppError>;
+
     async fn get_calls_by_user_id(&self, user_id: i32) -> Result<Vec<Call>, AppError>;
 
     async fn get_user_participated_calls(&self, user_id: i32) -> Result<Vec<Call>, AppError>;
 
+    /// A page of the calls the user placed, joined or was rung for, newest
+    /// first, with the names of everyone who joined.
+    async fn list_history(
+        &self,
+        user_id: i32,
+        params: CallHistoryParams,
+    ) -> Result<CallHistoryPage, AppError>;
+
+    /// Every matching history entry as CSV.
+    async fn export_history(
+        &self,
+        user_id: i32,
+        params: CallHistoryParams,
+    ) -> Result<String, AppError>;
+
     async fn get_active_calls(&self) -> Result<Vec<Call>, AppError>;
 
+    async fn get_unfinished_calls(&self, min_age_secs: i64) -> Result<Vec<Call>, AppError>;
+
+    /// Ends a call nobody is connected to any more, bypassing the caller/owner check.
+    /// A call nobody answered becomes missed or rejected instead.
+    async fn end_orphaned_call(&self, call_id: i32) -> Result<(), AppError>;
+
+    async fn close_dangling_participants(&self) -> Result<u64, AppError>;
+
     async fn add_call_participant(&self, call_id: i32, user_id: i32) -> Result<(), AppError>;
 
     async fn remove_call_participant(&self, call_id: i32, user_id: i32) -> Result<(), AppError>;
 
     async fn list_call_participants(&self, call_id: i32) -> Result<Vec<CallParticipant>, AppError>;
 
+    /// Every join and leave on the call, visible to its participants, the
+    /// room's moderators and admins.
+    async fn list_participant_sessions(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<CallParticipantSession>, AppError>;
+
     async fn list_active_participants(
         &self,
         call_id: i32,
@@ -59,6 +254,7 @@ pub struct CallServiceImpl {
     call_repo: Arc<dyn CallRepository + Send + Sync>,
     room_service: Arc<dyn RoomService + Send + Sync>,
     user_service: Arc<dyn UserService + Send + Sync>,
+    ring_timeout_secs: i64,
 }
 
 impl CallServiceImpl {
@@ -66,13 +262,221 @@ impl CallServiceImpl {
         call_repo: Arc<dyn CallRepository + Send + Sync>,
         room_service: Arc<dyn RoomService + Send + Sync>,
         user_service: Arc<dyn UserService + Send + Sync>,
+        ring_timeout_secs: i64,
     ) -> Self {
         Self {
             call_repo,
             room_service,
             user_service,
+            ring_timeout_secs,
         }
     }
+
+    async fn load_call(&self, call_id: i32) -> Result<Call, AppError> {
+        self.call_repo
+            .get_call_by_id(call_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Call {} not found", call_id)))
+    }
+
+    async fn place_ringing_call(&self, room_id: &str, caller_id: i32) -> Result<Call, AppError> {
+        self.room_service
+            .ensure_permitted(room_id, caller_id, RoomAction::StartCall)
+            .await?;
+
+        let call = self
+            .call_repo
+            .create_call(room_id.to_string(), caller_id, CallStatus::Ringing)
+            .await?;
+
+        self.call_repo
+            .add_call_participant(call.id, caller_id)
+            .await?;
+        self.record_started(&call).await?;
+
+        Ok(call)
+    }
+
+    async fn record_started(&self, call: &Call) -> Result<(), AppError> {
+        self.record_event(
+            NewCallEvent::new(call.id, CallEventType::StatusChanged, Some(call.caller_id))
+                .with_details(json!({ "from": null, "to": call.status.to_string() })),
+        )
+        .await?;
+        self.record_event(NewCallEvent::new(
+            call.id,
+            CallEventType::Joined,
+            Some(call.caller_id),
+        ))
+        .await
+    }
+
+    async fn attach_participants(&self, calls: &mut [CallHistoryEntry]) -> Result<(), AppError> {
+        let call_ids: Vec<i32> = calls.iter().map(|entry| entry.id).collect();
+        let participants = self.call_repo.list_history_participants(&call_ids).await?;
+        let positions: HashMap<i32, usize> = call_ids
+            .iter()
+            .enumerate()
+            .map(|(position, call_id)| (*call_id, position))
+            .collect();
+
+        for (call_id, participant) in participants {
+            if let Some(&position) = positions.get(&call_id) {
+                calls[position].participants.push(participant);
+            }
+        }
+        Ok(())
+    }
+
+    async fn ensure_can_inspect_call(
+        &self,
+        call: &Call,
+        user_id: i32,
+        what: &str,
+    ) -> Result<(), AppError> {
+        if self.call_repo.is_user_participant(call.id, user_id).await?
+            || self
+                .room_service
+                .is_user_moderator(&call.room_id, user_id)
+                .await?
+            || self.user_service.is_admin(user_id).await?
+        {
+            return Ok(());
+        }
+
+        Err(AppError::Unauthorized(format!(
+            "Only call participants, room moderators and admins can view the call {}",
+            what
+        )))
+    }
+
+    /// Loads the user's invite for an open call, failing if they are no
+    /// longer being rung.
+    async fn ringing_invite(&self, call: &Call, user_id: i32) -> Result<CallInvite, AppError> {
+        if !call.status.is_open() {
+            return Err(AppError::Validation(format!(
+                "Call {} has already finished",
+                call.id
+            )));
+        }
+
+        let invite = self
+            .call_repo
+            .get_call_invite(call.id, user_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "User {} was not rung for call {}",
+                    user_id, call.id
+                ))
+            })?;
+
+        if invite.status != CallInviteStatus::Ringing {
+            return Err(AppError::Validation(format!(
+                "Call invite has already been {}",
+                invite.status
+            )));
+        }
+
+        Ok(invite)
+    }
+
+    async fn move_call(
+        &self,
+        call: &Call,
+        status: CallStatus,
+        changed_by: Option<i32>,
+    ) -> Result<Call, AppError> {
+        self.move_call_with_reason(call, status, changed_by, None)
+            .await
+    }
+
+    /// Like `move_call`, noting on the timeline which limit forced the change.
+    async fn move_call_with_reason(
+        &self,
+        call: &Call,
+        status: CallStatus,
+        changed_by: Option<i32>,
+        limit: Option<CallLimit>,
+    ) -> Result<Call, AppError> {
+        if !call.status.can_transition_to(&status) {
+            return Err(AppError::Validation(format!(
+                "Cannot change call {} from '{}' to '{}'",
+                call.id, call.status, status
+            )));
+        }
+
+        if !self
+            .call_repo
+            .transition_call(call.id, call.status.clone(), status.clone(), changed_by)
+            .await?
+        {
+            return Err(AppError::Validation(format!(
+                "Call {} changed status in the meantime, please try again",
+                call.id
+            )));
+        }
+
+        let mut details = json!({ "from": call.status.to_string(), "to": status.to_string() });
+        if let Some(limit) = limit {
+            details["reason"] = json!(limit);
+        }
+        self.record_event(
+            NewCallEvent::new(call.id, CallEventType::StatusChanged, changed_by)
+                .with_details(details),
+        )
+        .await?;
+
+        self.load_call(call.id).await
+    }
+
+    /// Gives up on a call nobody answered once no one is left ringing: it is
+    /// `rejected` if everyone declined and `missed` otherwise.
+    async fn settle_unanswered(
+        &self,
+        call: Call,
+        changed_by: Option<i32>,
+    ) -> Result<Call, AppError> {
+        if !matches!(call.status, CallStatus::Initiated | CallStatus::Ringing) {
+            return Ok(call);
+        }
+
+        let invites = self.call_repo.list_call_invites(call.id).await?;
+        if invites.is_empty()
+            || invites.iter().any(|invite| {
+                matches!(
+                    invite.status,
+                    CallInviteStatus::Ringing | CallInviteStatus::Accepted
+                )
+            })
+        {
+            return Ok(call);
+        }
+
+        let status = if invites
+            .iter()
+            .all(|invite| invite.status == CallInviteStatus::Declined)
+        {
+            CallStatus::Rejected
+        } else {
+            CallStatus::Missed
+        };
+
+        let call = self.move_call(&call, status, changed_by).await?;
+        for participant in self.call_repo.list_active_participants(call.id).await? {
+            self.call_repo
+                .remove_call_participant(call.id, participant.user_id)
+                .await?;
+            self.record_event(NewCallEvent::new(
+                call.id,
+                CallEventType::Left,
+                Some(participant.user_id),
+            ))
+            .await?;
+        }
+
+        Ok(call)
+    }
 }
 
 #[async_trait]
@@ -116,16 +520,9 @@ impl CallService for CallServiceImpl {
             .await?
             .ok_or_else(|| AppError::NotFound(format!("User {} not found", caller_id)))?;
 
-        if !self
-            .room_service
-            .is_user_in_room(&room_id, caller_id)
-            .await?
-        {
-            return Err(AppError::Unauthorized(format!(
-                "User {} is not a member of room {}",
-                caller_id, room_id
-            )));
-        }
+        self.room_service
+            .ensure_permitted(&room_id, caller_id, RoomAction::StartCall)
+            .await?;
 
         let call = self
             .call_repo
@@ -135,48 +532,473 @@ impl CallService for CallServiceImpl {
         self.call_repo
             .add_call_participant(call.id, caller_id)
             .await?;
+        self.record_started(&call).await?;
 
         Ok(call)
     }
 
-    async fn get_call_by_id(&self, call_id: i32) -> Result<Option<Call>, AppError> {
-        self.call_repo.get_call_by_id(call_id).await
+    async fn start_direct_call(
+        &self,
+        caller_id: i32,
+        target_user_id: i32,
+    ) -> Result<DirectCall, AppError> {
+        if caller_id == target_user_id {
+            return Err(AppError::Validation("You cannot call yourself".into()));
+        }
+
+        let caller = self
+            .user_service
+            .get_by_id(caller_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("User {} not found", caller_id)))?;
+
+        let callee = self
+            .user_service
+            .get_by_id(target_user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("User {} not found", target_user_id)))?;
+
+        let room_name = format!(
+            "{} {} & {} {}",
+            caller.first_name, caller.last_name, callee.first_name, callee.last_name
+        );
+
+        let room = self
+            .room_service
+            .get_or_create_direct_room(caller_id, target_user_id, room_name)
+            .await?;
+
+        let call = match self.call_repo.get_open_call_by_room_id(&room.id).await? {
+            Some(call) => call,
+            None => {
+                let call = self.place_ringing_call(&room.id, caller_id).await?;
+
+                self.call_repo
+                    .ring_users(
+                        call.id,
+                        caller_id,
+                        &[target_user_id],
+                        self.ring_timeout_secs,
+                    )
+                    .await?;
+
+                call
+            }
+        };
+
+        Ok(DirectCall {
+            room,
+            call,
+            callee: callee.into(),
+        })
     }
 
-    async fn update_call_status(&self, call_id: i32, status: String) -> Result<(), AppError> {
-        let status = status.parse::<CallStatus>()?;
+    async fn ring_users(
+        &self,
+        caller_id: i32,
+        room_id: &str,
+        mut user_ids: Vec<i32>,
+    ) -> Result<CallRinging, AppError> {
+        user_ids.sort_unstable();
+        user_ids.dedup();
+
+        if user_ids.is_empty() {
+            return Err(AppError::Validation(
+                "Choose at least one user to ring".into(),
+            ));
+        }
+        if user_ids.contains(&caller_id) {
+            return Err(AppError::Validation("You cannot call yourself".into()));
+        }
 
-        let _call = self
+        let room = self
+            .room_service
+            .get_room(room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
+
+        if !room.is_active {
+            return Err(AppError::Validation(format!(
+                "Room {} is not active",
+                room_id
+            )));
+        }
+
+        self.room_service
+            .ensure_permitted(room_id, caller_id, RoomAction::StartCall)
+            .await?;
+
+        let open_call = self.call_repo.get_open_call_by_room_id(room_id).await?;
+        let on_call = match &open_call {
+            Some(call) => self.call_repo.list_active_participants(call.id).await?,
+            None => Vec::new(),
+        };
+
+        for user_id in &user_ids {
+            self.user_service
+                .get_by_id(*user_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("User {} not found", user_id)))?;
+
+            if on_call
+                .iter()
+                .any(|participant| participant.user_id == *user_id)
+            {
+                return Err(AppError::Validation(format!(
+                    "User {} is already on this call",
+                    user_id
+                )));
+            }
+        }
+
+        for user_id in &user_ids {
+            if !self.room_service.is_user_in_room(room_id, *user_id).await? {
+                self.room_service
+                    .invite_member(room_id, caller_id, *user_id)
+                    .await?;
+            }
+        }
+
+        let call = match open_call {
+            Some(call) => call,
+            None => self.place_ringing_call(room_id, caller_id).await?,
+        };
+
+        let invites = self
             .call_repo
-            .get_call_by_id(call_id)
+            .ring_users(call.id, caller_id, &user_ids, self.ring_timeout_secs)
+            .await?;
+
+        Ok(CallRinging { call, invites })
+    }
+
+    async fn accept_call(&self, call_id: i32, user_id: i32) -> Result<CallInviteUpdate, AppError> {
+        let call = self.load_call(call_id).await?;
+        self.ringing_invite(&call, user_id).await?;
+
+        let invite = self
+            .call_repo
+            .answer_invite(call_id, user_id, CallInviteStatus::Accepted)
             .await?
-            .ok_or_else(|| AppError::NotFound(format!("Call {} not found", call_id)))?;
+            .ok_or_else(|| AppError::Validation("Call invite has already been answered".into()))?;
 
-        self.call_repo.update_call_status(call_id, status).await
+        let call = if call.status == CallStatus::Active {
+            call
+        } else {
+            self.move_call(&call, CallStatus::Active, Some(user_id))
+                .await?
+        };
+
+        Ok(CallInviteUpdate { call, invite })
     }
 
-    async fn end_call(&self, call_id: i32, user_id: i32) -> Result<(), AppError> {
-        // Check if call exists
-        let call = self
+    async fn decline_call(&self, call_id: i32, user_id: i32) -> Result<CallInviteUpdate, AppError> {
+        let call = self.load_call(call_id).await?;
+        self.ringing_invite(&call, user_id).await?;
+
+        let invite = self
             .call_repo
-            .get_call_by_id(call_id)
+            .answer_invite(call_id, user_id, CallInviteStatus::Declined)
             .await?
-            .ok_or_else(|| AppError::NotFound(format!("Call {} not found", call_id)))?;
+            .ok_or_else(|| AppError::Validation("Call invite has already been answered".into()))?;
 
-        // Check if user is the caller or a room owner
-        let is_caller = call.caller_id == user_id;
-        let is_room_owner = self
-            .room_service
-            .is_user_owner(&call.room_id, user_id)
+        Ok(CallInviteUpdate {
+            call: self.settle_unanswered(call, Some(user_id)).await?,
+            invite,
+        })
+    }
+
+    async fn list_call_invites(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<CallInvite>, AppError> {
+        let call = self.load_call(call_id).await?;
+        let invites = self.call_repo.list_call_invites(call_id).await?;
+
+        if !invites.iter().any(|invite| invite.user_id == user_id) {
+            self.ensure_can_inspect_call(&call, user_id, "invites")
+                .await?;
+        }
+
+        Ok(invites)
+    }
+
+    async fn expire_invites(&self) -> Result<Vec<CallInviteUpdate>, AppError> {
+        let mut updates = Vec::new();
+
+        // The invites are already marked missed, so a failure below must not
+        // stop the rest from being settled and announced. A call left ringing
+        // is settled by the janitor instead.
+        for invite in self.call_repo.expire_invites().await? {
+            let call = match self.load_call(invite.call_id).await {
+                Ok(call) => call,
+                Err(e) => {
+                    eprintln!("Failed to load call {}: {}", invite.call_id, e);
+                    continue;
+                }
+            };
+            let call = match self.settle_unanswered(call.clone(), None).await {
+                Ok(settled) => settled,
+                Err(e) => {
+                    eprintln!("Failed to settle call {}: {}", call.id, e);
+                    call
+                }
+            };
+            updates.push(CallInviteUpdate { call, invite });
+        }
+
+        Ok(updates)
+    }
+
+    async fn get_call_by_id(&self, call_id: i32) -> Result<Option<Call>, AppError> {
+        self.call_repo.get_call_by_id(call_id).await
+    }
+
+    async fn update_call_status(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        status: String,
+    ) -> Result<Call, AppError> {
+        let status = status.parse::<CallStatus>()?;
+        let call = self.load_call(call_id).await?;
+
+        if call.caller_id != user_id
+            && !self
+                .room_service
+                .is_user_moderator(&call.room_id, user_id)
+                .await?
+        {
+            return Err(AppError::Unauthorized(
+                "Only the caller or a room moderator can change the call status".into(),
+            ));
+        }
+
+        self.move_call(&call, status, Some(user_id)).await
+    }
+
+    async fn transition_call(
+        &self,
+        call_id: i32,
+        status: CallStatus,
+        changed_by: Option<i32>,
+    ) -> Result<Call, AppError> {
+        let call = self.load_call(call_id).await?;
+        self.move_call(&call, status, changed_by).await
+    }
+
+    async fn get_status_history(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<CallStatusChange>, AppError> {
+        let call = self.load_call(call_id).await?;
+        self.ensure_can_inspect_call(&call, user_id, "status history")
+            .await?;
+
+        self.call_repo.list_status_history(call_id).await
+    }
+
+    async fn record_event(&self, event: NewCallEvent) -> Result<(), AppError> {
+        self.call_repo.add_event(&event).await
+    }
+
+    async fn list_events(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        params: CallEventParams,
+    ) -> Result<CallEventPage, AppError> {
+        let call = self.load_call(call_id).await?;
+        self.ensure_can_inspect_call(&call, user_id, "timeline")
+            .await?;
+
+        let mut filter = CallEventFilter::from_params(params, true)?;
+        let limit = filter.limit.unwrap_or(timeline::DEFAULT_EVENT_PAGE_SIZE);
+        // Fetch one extra row to know whether another page exists
+        filter.limit = Some(limit + 1);
+
+        let mut events = self.call_repo.list_events(call_id, &filter).await?;
+
+        let next_after = if events.len() as i64 > limit {
+            events.truncate(limit as usize);
+            events.last().map(|event| event.id)
+        } else {
+            None
+        };
+
+        Ok(CallEventPage { events, next_after })
+    }
+
+    async fn export_events(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        params: CallEventParams,
+    ) -> Result<String, AppError> {
+        let call = self.load_call(call_id).await?;
+        self.ensure_can_inspect_call(&call, user_id, "timeline")
+            .await?;
+
+        let filter = CallEventFilter::from_params(params, false)?;
+        let events = self.call_repo.list_events(call_id, &filter).await?;
+        Ok(timeline::to_ndjson(&events))
+    }
+
+    async fn record_quality_sample(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        stats: QualityStats,
+    ) -> Result<bool, AppError> {
+        quality::validate_stats(&stats)?;
+        self.call_repo
+            .add_quality_sample(call_id, user_id, &stats, quality::SAMPLE_INTERVAL_SECS)
+            .await
+    }
+
+    async fn get_quality_report(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<CallQualityReport, AppError> {
+        let call = self.load_call(call_id).await?;
+        self.ensure_can_inspect_call(&call, user_id, "quality report")
             .await?;
 
-        if !is_caller && !is_room_owner {
+        let samples = self.call_repo.list_quality_samples(call_id).await?;
+        Ok(quality::build_report(call_id, &samples))
+    }
+
+    async fn record_recording_consent(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        accepted: bool,
+    ) -> Result<RecordingConsentDecision, AppError> {
+        self.call_repo
+            .add_recording_consent(call_id, user_id, accepted)
+            .await
+    }
+
+    async fn list_recording_consents(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<RecordingConsentDecision>, AppError> {
+        let call = self.load_call(call_id).await?;
+        self.ensure_can_inspect_call(&call, user_id, "recording consents")
+            .await?;
+
+        self.call_repo.list_recording_consents(call_id).await
+    }
+
+    async fn record_caption(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        caption: NewCaption,
+    ) -> Result<CaptionSegment, AppError> {
+        transcript::validate_caption(&caption)?;
+        let call = self.load_call(call_id).await?;
+        let (start_ms, end_ms) = transcript::offsets_from_call_start(&call, &caption)?;
+
+        self.call_repo
+            .add_caption(
+                call_id,
+                user_id,
+                &transcript::clean_text(&caption.text),
+                start_ms,
+                end_ms,
+            )
+            .await
+    }
+
+    async fn export_transcript(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        format: TranscriptFormat,
+    ) -> Result<String, AppError> {
+        let call = self.load_call(call_id).await?;
+        self.ensure_can_inspect_call(&call, user_id, "transcript")
+            .await?;
+
+        let captions = self.call_repo.list_captions(call_id).await?;
+        Ok(transcript::render(format, &captions))
+    }
+
+    async fn submit_feedback(
+        &self,
+        call_id: i32,
+        user_id: i32,
+        feedback: SubmitFeedback,
+    ) -> Result<CallFeedback, AppError> {
+        feedback::validate_feedback(&feedback)?;
+        self.load_call(call_id).await?;
+
+        if !self.call_repo.is_user_participant(call_id, user_id).await? {
             return Err(AppError::Unauthorized(
-                "Only the caller or room owner can end the call".into(),
+                "Only people who joined the call can rate it".into(),
             ));
         }
 
-        self.call_repo.end_call(call_id).await
+        let comment = feedback::clean_comment(feedback.comment.as_deref());
+        self.call_repo
+            .upsert_feedback(
+                call_id,
+                user_id,
+                feedback.rating,
+                comment.as_deref(),
+                &feedback::unique_issues(&feedback.issues),
+            )
+            .await
+    }
+
+    async fn get_feedback(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<CallFeedbackSummary, AppError> {
+        let call = self.load_call(call_id).await?;
+        self.ensure_can_inspect_call(&call, user_id, "feedback")
+            .await?;
+
+        let entries = self.call_repo.list_feedback(call_id).await?;
+        Ok(feedback::summarize(call_id, entries))
+    }
+
+    async fn end_call(&self, call_id: i32, ended_by: EndedBy) -> Result<(), AppError> {
+        // Check if call exists
+        let call = self
+            .call_repo
+            .get_call_by_id(call_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Call {} not found", call_id)))?;
+
+        let (changed_by, limit) = match ended_by {
+            EndedBy::User(user_id) => {
+                // Check if user is the caller or a room owner
+                let is_caller = call.caller_id == user_id;
+                let is_room_owner = self
+                    .room_service
+                    .is_user_owner(&call.room_id, user_id)
+                    .await?;
+
+                if !is_caller && !is_room_owner {
+                    return Err(AppError::Unauthorized(
+                        "Only the caller or room owner can end the call".into(),
+                    ));
+                }
+                (Some(user_id), None)
+            }
+            EndedBy::Limit(limit) => (None, Some(limit)),
+        };
+
+        self.move_call_with_reason(&call, CallStatus::Ended, changed_by, limit)
+            .await?;
+        Ok(())
     }
 
     async fn get_calls_by_room_id(&self, room_id: &str) -> Result<Vec<Call>, AppError> {
@@ -198,6 +1020,10 @@ impl CallService for CallServiceImpl {
         self.call_repo.get_active_calls_by_room_id(room_id).await
     }
 
+    async fn get_open_call_by_room_id(&self, room_id: &str) -> Result<Option<Call>, AppError> {
+        self.call_repo.get_open_call_by_room_id(room_id).await
+    }
+
     async fn get_calls_by_user_id(&self, user_id: i32) -> Result<Vec<Call>, AppError> {
         // Validate user exists
         self.user_service
@@ -218,10 +1044,74 @@ impl CallService for CallServiceImpl {
         self.call_repo.get_user_participated_calls(user_id).await
     }
 
+    async fn list_history(
+        &self,
+        user_id: i32,
+        params: CallHistoryParams,
+    ) -> Result<CallHistoryPage, AppError> {
+        let mut filter = CallHistoryFilter::from_params(params, true)?;
+        let limit = filter.limit.unwrap_or(history::DEFAULT_HISTORY_PAGE_SIZE);
+        // Fetch one extra row to know whether another page exists
+        filter.limit = Some(limit + 1);
+
+        let mut calls = self.call_repo.list_history(user_id, &filter).await?;
+
+        let next_cursor = if calls.len() as i64 > limit {
+            calls.truncate(limit as usize);
+            calls
+                .last()
+                .map(|entry| HistoryCursor::after(entry).encode())
+        } else {
+            None
+        };
+
+        self.attach_participants(&mut calls).await?;
+        Ok(CallHistoryPage { calls, next_cursor })
+    }
+
+    async fn export_history(
+        &self,
+        user_id: i32,
+        params: CallHistoryParams,
+    ) -> Result<String, AppError> {
+        let mut filter = CallHistoryFilter::from_params(params, false)?;
+        // Fetch one extra row to know whether the export would be cut short
+        filter.limit = Some(history::MAX_EXPORT_ROWS + 1);
+
+        let mut calls = self.call_repo.list_history(user_id, &filter).await?;
+        if calls.len() as i64 > history::MAX_EXPORT_ROWS {
+            return Err(AppError::Validation(format!(
+                "The export matches more than {} calls; narrow the date range",
+                history::MAX_EXPORT_ROWS
+            )));
+        }
+
+        self.attach_participants(&mut calls).await?;
+        Ok(history::to_csv(&calls))
+    }
+
     async fn get_active_calls(&self) -> Result<Vec<Call>, AppError> {
         self.call_repo.get_active_calls().await
     }
 
+    async fn get_unfinished_calls(&self, min_age_secs: i64) -> Result<Vec<Call>, AppError> {
+        self.call_repo.get_unfinished_calls(min_age_secs).await
+    }
+
+    async fn end_orphaned_call(&self, call_id: i32) -> Result<(), AppError> {
+        // A call nobody answered is missed or rejected rather than ended
+        let call = self.load_call(call_id).await?;
+        let call = self.settle_unanswered(call, None).await?;
+        if call.status.is_open() {
+            self.move_call(&call, CallStatus::Ended, None).await?;
+        }
+        Ok(())
+    }
+
+    async fn close_dangling_participants(&self) -> Result<u64, AppError> {
+        self.call_repo.close_dangling_participants().await
+    }
+
     async fn add_call_participant(&self, call_id: i32, user_id: i32) -> Result<(), AppError> {
         // Check if call exists and is active
         let call = self
@@ -271,7 +1161,24 @@ impl CallService for CallServiceImpl {
             )));
         }
 
-        self.call_repo.add_call_participant(call_id, user_id).await
+        let previous = self
+            .call_repo
+            .list_call_participants(call_id)
+            .await?
+            .into_iter()
+            .find(|participant| participant.user_id == user_id);
+
+        self.call_repo
+            .add_call_participant(call_id, user_id)
+            .await?;
+
+        let event_type = match previous {
+            None => CallEventType::Joined,
+            Some(participant) if participant.left_at.is_some() => CallEventType::Reconnected,
+            Some(_) => return Ok(()),
+        };
+        self.record_event(NewCallEvent::new(call_id, event_type, Some(user_id)))
+            .await
     }
 
     async fn remove_call_participant(&self, call_id: i32, user_id: i32) -> Result<(), AppError> {
@@ -291,14 +1198,34 @@ impl CallService for CallServiceImpl {
             )));
         }
 
+        let was_active = self
+            .call_repo
+            .list_active_participants(call_id)
+            .await?
+            .iter()
+            .any(|participant| participant.user_id == user_id);
+
         self.call_repo
             .remove_call_participant(call_id, user_id)
             .await?;
 
+        if was_active {
+            self.record_event(NewCallEvent::new(
+                call_id,
+                CallEventType::Left,
+                Some(user_id),
+            ))
+            .await?;
+        }
+
         // If no participants remain, end the call
         let remaining_participants = self.call_repo.count_active_participants(call_id).await?;
         if remaining_participants == 0 {
-            self.call_repo.end_call(call_id).await?;
+            let call = self.load_call(call_id).await?;
+            if call.status.is_open() {
+                self.move_call(&call, CallStatus::Ended, Some(user_id))
+                    .await?;
+            }
         }
 
         Ok(())
@@ -313,6 +1240,18 @@ impl CallService for CallServiceImpl {
         self.call_repo.list_call_participants(call_id).await
     }
 
+    async fn list_participant_sessions(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<CallParticipantSession>, AppError> {
+        let call = self.load_call(call_id).await?;
+        self.ensure_can_inspect_call(&call, user_id, "participant sessions")
+            .await?;
+
+        self.call_repo.list_participant_sessions(call_id).await
+    }
+
     async fn list_active_participants(
         &self,
         call_id: i32,
@@ -359,3 +1298,94 @@ impl CallService for CallServiceImpl {
         Ok((user.id, format!("{} {}", user.first_name, user.last_name)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        calls::SqliteCallRepository,
+        rooms::{RoomServiceImpl, SqliteRoomRepository},
+        shared::test_db::{self, TEST_ROOM_ID},
+        users::{SqliteUserRepository, UserServiceImpl},
+    };
+
+    /// A call from user 1, who is on it, ringing users 2 and 3 whose invites
+    /// have the given statuses.
+    async fn call_with_invites(statuses: [&str; 2]) -> (CallServiceImpl, Call) {
+        let pool = test_db::memory_pool().await;
+        let call_repo = Arc::new(SqliteCallRepository::new(pool.clone()));
+        let service = CallServiceImpl::new(
+            call_repo.clone(),
+            Arc::new(RoomServiceImpl::new(Arc::new(SqliteRoomRepository::new(
+                pool.clone(),
+            )))),
+            Arc::new(UserServiceImpl::new(Arc::new(SqliteUserRepository::new(
+                pool.clone(),
+            )))),
+            30,
+        );
+
+        let call_id: i32 = sqlx::query_scalar(
+            "INSERT INTO calls (room_id, caller_id, status) VALUES ($1, 1, 'ringing') RETURNING id",
+        )
+        .bind(TEST_ROOM_ID)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        for (user_id, status) in [(2, statuses[0]), (3, statuses[1])] {
+            sqlx::query(
+                "INSERT INTO call_invites (call_id, user_id, invited_by, status, expires_at) \
+                 VALUES ($1, $2, 1, $3, CURRENT_TIMESTAMP)",
+            )
+            .bind(call_id)
+            .bind(user_id)
+            .bind(status)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        call_repo.add_call_participant(call_id, 1).await.unwrap();
+
+        let call = service.load_call(call_id).await.unwrap();
+        (service, call)
+    }
+
+    #[tokio::test]
+    async fn test_unanswered_call_is_rejected_only_when_everyone_declined() {
+        let (service, call) = call_with_invites(["declined", "declined"]).await;
+        let settled = service.settle_unanswered(call, None).await.unwrap();
+        assert_eq!(settled.status, CallStatus::Rejected);
+        assert_eq!(
+            service
+                .call_repo
+                .count_active_participants(settled.id)
+                .await
+                .unwrap(),
+            0
+        );
+
+        let (service, call) = call_with_invites(["declined", "missed"]).await;
+        let settled = service.settle_unanswered(call, None).await.unwrap();
+        assert_eq!(settled.status, CallStatus::Missed);
+
+        // Calls the janitor finds orphaned are settled the same way
+        let (service, call) = call_with_invites(["missed", "missed"]).await;
+        service.end_orphaned_call(call.id).await.unwrap();
+        assert_eq!(
+            service.load_call(call.id).await.unwrap().status,
+            CallStatus::Missed
+        );
+
+        let (service, call) = call_with_invites(["missed", "ringing"]).await;
+        let settled = service.settle_unanswered(call, None).await.unwrap();
+        assert_eq!(settled.status, CallStatus::Ringing);
+        assert_eq!(
+            service
+                .call_repo
+                .count_active_participants(settled.id)
+                .await
+                .unwrap(),
+            1
+        );
+    }
+}
@@ -1,8 +1,21 @@
+use crate::breakouts::entities::BreakoutOverview;
 use crate::calls::service::CallService;
-use crate::calls::{entities::CallStatus, websocket::OutgoingMessage};
-use crate::rooms::service::RoomService;
+use crate::calls::{
+    contract::CallInviteUpdate,
+    entities::{
+        Call, CallEventType, CallInvite, CallInviteStatus, CallStatus, NewCallEvent, NewCaption,
+        QualityStats, ServerMessage,
+    },
+    limits::{CallActivity, CallLimit},
+    reactions::{HandQueue, validate_reaction},
+    recording_consent::{RecordingConsent, RecordingIndicator},
+    websocket::OutgoingMessage,
+};
+use crate::chat::ChatService;
+use crate::rooms::{OwnershipTransfer, Room, RoomType, service::RoomService};
 use crate::shared::response::AppError;
-use dashmap::DashMap;
+use chrono::{NaiveDateTime, Utc};
+use dashmap::{DashMap, mapref::entry::Entry};
 use std::sync::Arc;
 use tokio::sync::mpsc::UnboundedSender;
 
@@ -13,24 +26,75 @@ pub struct Connection {
     pub user_id: i32,
     pub room_id: String,
     pub call_id: Option<i32>,
+    pub in_lobby: bool,
+    pub media: MediaFlags,
     pub sender: Sender,
 }
 
+/// The media state a socket last reported, used to tell which parts changed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MediaFlags {
+    pub audio_enabled: bool,
+    pub video_enabled: bool,
+    pub screen_sharing: bool,
+}
+
+impl Default for MediaFlags {
+    fn default() -> Self {
+        Self {
+            audio_enabled: true,
+            video_enabled: true,
+            screen_sharing: false,
+        }
+    }
+}
+
+/// Activity on a call that its limits are measured against.
+#[derive(Debug, Clone, Copy)]
+struct CallTracking {
+    last_media_activity: NaiveDateTime,
+    alone_since: Option<NaiveDateTime>,
+}
+
+/// Whether a new socket went straight into the room or is waiting in the lobby.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConnectionState {
+    Joined,
+    InLobby,
+}
+
 pub struct SignalingServer {
     connections: DashMap<i32, Connection>,
     rooms: DashMap<String, Vec<(i32, String)>>,
+    lobby: DashMap<String, Vec<(i32, String)>>,
+    /// Recordings requested on a call, keyed by call id.
+    recordings: DashMap<i32, RecordingConsent>,
+    /// Keyed by call id.
+    call_tracking: DashMap<i32, CallTracking>,
+    /// Keyed by call id.
+    raised_hands: DashMap<i32, HandQueue>,
 
     call_service: Arc<dyn CallService>,
     room_service: Arc<dyn RoomService>,
+    chat_service: Arc<dyn ChatService>,
 }
 
 impl SignalingServer {
-    pub fn new(call_service: Arc<dyn CallService>, room_service: Arc<dyn RoomService>) -> Self {
+    pub fn new(
+        call_service: Arc<dyn CallService>,
+        room_service: Arc<dyn RoomService>,
+        chat_service: Arc<dyn ChatService>,
+    ) -> Self {
         Self {
             connections: DashMap::new(),
             rooms: DashMap::new(),
+            lobby: DashMap::new(),
+            recordings: DashMap::new(),
+            call_tracking: DashMap::new(),
+            raised_hands: DashMap::new(),
             call_service,
             room_service,
+            chat_service,
         }
     }
 
@@ -38,78 +102,444 @@ impl SignalingServer {
         &self,
         user_id: i32,
         room_id: String,
+        passcode: Option<&str>,
         sender: Sender,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        // 1. Add to in-memory tracking
+    ) -> Result<ConnectionState, AppError> {
+        // 1. Make sure the user is allowed in the room before tracking them
+        let room = self
+            .room_service
+            .verify_room_access(&room_id, user_id, passcode)
+            .await?;
+
+        let user = self.call_service.get_caller_info(user_id).await?;
+
+        // 2. Meeting rooms hold anyone who is not already a member in the lobby
+        let needs_admission = room.room_type == RoomType::Meeting
+            && !self.room_service.is_user_in_room(&room_id, user_id).await?;
+
+        if !needs_admission {
+            self.room_service.admit_member(&room_id, user_id).await?;
+        }
+
+        // 3. Add to in-memory tracking
         let connection = Connection {
             user_id,
             room_id: room_id.clone(),
             call_id: None,
+            in_lobby: needs_admission,
+            media: MediaFlags::default(),
             sender,
         };
 
         self.connections.insert(user_id, connection);
 
-        let user = self.call_service.get_caller_info(user_id).await?;
+        if needs_admission {
+            self.lobby
+                .entry(room_id.clone())
+                .or_default()
+                .push((user_id, user.1.clone()));
+
+            self.send_message(
+                user_id,
+                &ServerMessage::LobbyWaiting {
+                    room_id: room_id.clone(),
+                },
+            );
+            self.notify_moderators(
+                &room_id,
+                &ServerMessage::LobbyKnock {
+                    user_id,
+                    user_name: user.1,
+                },
+            )
+            .await;
+            self.broadcast_lobby_state(&room_id).await;
+
+            return Ok(ConnectionState::InLobby);
+        }
 
         self.rooms
             .entry(room_id.clone())
             .or_default()
             .push((user_id, user.1));
 
-        self.room_service.join_room(&room_id, user_id).await?;
+        Ok(ConnectionState::Joined)
+    }
 
+    pub fn is_in_lobby(&self, user_id: i32) -> bool {
+        self.connections
+            .get(&user_id)
+            .is_some_and(|connection| connection.in_lobby)
+    }
+
+    pub async fn get_lobby_users(&self, room_id: &str) -> Vec<(i32, String)> {
+        self.lobby
+            .get(room_id)
+            .map(|entry| entry.value().clone())
+            .unwrap_or_default()
+    }
+
+    /// Lets waiting users into the room. `None` admits everyone in the lobby.
+    pub async fn admit_from_lobby(
+        &self,
+        moderator_id: i32,
+        room_id: &str,
+        user_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        self.ensure_moderator(room_id, moderator_id).await?;
+
+        for (waiting_id, user_name) in self.take_from_lobby(room_id, user_id)? {
+            if let Err(e) = self.room_service.admit_member(room_id, waiting_id).await {
+                eprintln!(
+                    "Failed to admit user {} to room {}: {}",
+                    waiting_id, room_id, e
+                );
+                // Rule violations such as a full room are worth showing;
+                // anything else stays in the server log
+                let reason = match e {
+                    AppError::Validation(message) => message,
+                    _ => "You could not be admitted to the room".to_string(),
+                };
+                self.send_message(
+                    waiting_id,
+                    &ServerMessage::LobbyDenied {
+                        room_id: room_id.to_string(),
+                        reason: Some(reason),
+                    },
+                );
+                self.close_connection(waiting_id);
+                continue;
+            }
+
+            if let Some(mut connection) = self.connections.get_mut(&waiting_id) {
+                connection.in_lobby = false;
+            }
+
+            self.rooms
+                .entry(room_id.to_string())
+                .or_default()
+                .push((waiting_id, user_name));
+
+            self.send_message(
+                waiting_id,
+                &ServerMessage::LobbyAdmitted {
+                    room_id: room_id.to_string(),
+                },
+            );
+            self.record_moderator_action(room_id, moderator_id, Some(waiting_id), "lobby_admit")
+                .await;
+        }
+
+        self.broadcast_lobby_state(room_id).await;
         Ok(())
     }
 
+    /// Turns waiting users away and closes their sockets. `None` denies everyone.
+    pub async fn deny_from_lobby(
+        &self,
+        moderator_id: i32,
+        room_id: &str,
+        user_id: Option<i32>,
+    ) -> Result<(), AppError> {
+        self.ensure_moderator(room_id, moderator_id).await?;
+
+        for (waiting_id, _) in self.take_from_lobby(room_id, user_id)? {
+            self.send_message(
+                waiting_id,
+                &ServerMessage::LobbyDenied {
+                    room_id: room_id.to_string(),
+                    reason: None,
+                },
+            );
+            self.close_connection(waiting_id);
+            self.record_moderator_action(room_id, moderator_id, Some(waiting_id), "lobby_deny")
+                .await;
+        }
+
+        self.broadcast_lobby_state(room_id).await;
+        Ok(())
+    }
+
+    fn take_from_lobby(
+        &self,
+        room_id: &str,
+        user_id: Option<i32>,
+    ) -> Result<Vec<(i32, String)>, AppError> {
+        let mut waiting = self.lobby.entry(room_id.to_string()).or_default();
+
+        match user_id {
+            Some(user_id) => {
+                let position = waiting
+                    .iter()
+                    .position(|(id, _)| *id == user_id)
+                    .ok_or_else(|| {
+                        AppError::NotFound(format!("User {} is not waiting in the lobby", user_id))
+                    })?;
+                Ok(vec![waiting.remove(position)])
+            }
+            None => Ok(std::mem::take(&mut *waiting)),
+        }
+    }
+
+    async fn ensure_moderator(&self, room_id: &str, user_id: i32) -> Result<(), AppError> {
+        if !self
+            .room_service
+            .is_user_moderator(room_id, user_id)
+            .await?
+        {
+            return Err(AppError::Unauthorized(
+                "Only room owners and moderators can manage the lobby".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn notify_moderators(&self, room_id: &str, message: &ServerMessage) {
+        for (user_id, _) in self.get_room_users(room_id).await {
+            if let Ok(true) = self.room_service.is_user_moderator(room_id, user_id).await {
+                self.send_message(user_id, message);
+            }
+        }
+    }
+
+    pub async fn broadcast_lobby_state(&self, room_id: &str) {
+        let message = ServerMessage::LobbyUpdated {
+            waiting: self.get_lobby_users(room_id).await,
+        };
+        self.notify_moderators(room_id, &message).await;
+    }
+
+    fn close_connection(&self, user_id: i32) {
+        if let Some((_, connection)) = self.connections.remove(&user_id) {
+            let _ = connection.sender.send(OutgoingMessage::Close(None));
+        }
+    }
+
     pub async fn join_call(&self, user_id: i32, room_id: String) -> Result<i32, AppError> {
-        let active_calls = self
-            .call_service
-            .get_active_calls_by_room_id(&room_id)
-            .await?;
+        let open_call = self.call_service.get_open_call_by_room_id(&room_id).await?;
 
-        let call_id = if let Some(active_call) = active_calls.first() {
-            self.call_service
-                .add_call_participant(active_call.id, user_id)
-                .await?;
+        let call_id = match open_call {
+            // The caller joined when the call was placed; it goes live once someone answers
+            Some(call) if call.status != CallStatus::Active && call.caller_id == user_id => call.id,
+            Some(call) => {
+                if call.status != CallStatus::Active {
+                    self.call_service
+                        .transition_call(call.id, CallStatus::Active, Some(user_id))
+                        .await?;
+                }
 
-            active_call.id
-        } else {
-            let call = self
-                .call_service
-                .create_call(room_id.clone(), user_id, CallStatus::Active.to_string())
-                .await?;
-            call.id
+                self.call_service
+                    .add_call_participant(call.id, user_id)
+                    .await?;
+
+                call.id
+            }
+            None => {
+                let call = self
+                    .call_service
+                    .create_call(room_id.clone(), user_id, CallStatus::Active.to_string())
+                    .await?;
+                call.id
+            }
         };
 
         if let Some(mut connection) = self.connections.get_mut(&user_id) {
             connection.call_id = Some(call_id);
         }
 
+        self.touch_media_activity(call_id);
+        self.announce_recording(call_id, user_id).await;
+        self.announce_raised_hands(call_id, user_id);
         Ok(call_id)
     }
 
-    pub async fn remove_connection(&self, user_id: i32) {
-        if let Some((_, connection)) = self.connections.remove(&user_id) {
-            if let Some(mut room_users) = self.rooms.get_mut(&connection.room_id) {
-                room_users.retain(|(id, _)| *id != user_id);
+    /// Drops a socket and returns true if the user was in the room itself
+    /// rather than waiting in the lobby.
+    pub async fn remove_connection(&self, user_id: i32) -> bool {
+        let Some((_, connection)) = self.connections.remove(&user_id) else {
+            return false;
+        };
+
+        if connection.in_lobby {
+            if let Some(mut waiting) = self.lobby.get_mut(&connection.room_id) {
+                waiting.retain(|(id, _)| *id != user_id);
             }
+            self.broadcast_lobby_state(&connection.room_id).await;
+            return false;
+        }
 
-            if let Some(call_id) = connection.call_id {
-                let _ = self
-                    .call_service
-                    .remove_call_participant(call_id, user_id)
-                    .await;
+        if let Some(mut room_users) = self.rooms.get_mut(&connection.room_id) {
+            room_users.retain(|(id, _)| *id != user_id);
+        }
+
+        if let Some(call_id) = connection.call_id {
+            let _ = self
+                .call_service
+                .remove_call_participant(call_id, user_id)
+                .await;
+            self.settle_recording(call_id);
+            self.drop_raised_hand(call_id, user_id);
+        }
+
+        // Uncomment if needed
+        // let _ = self
+        //     .room_service
+        //     .leave_room(&connection.room_id, user_id)
+        //     .await;
+
+        true
+    }
+
+    /// The room a socket is currently in, which differs from the room it
+    /// connected to while the user is in a breakout room.
+    pub fn current_room(&self, user_id: i32) -> Option<String> {
+        self.connections
+            .get(&user_id)
+            .map(|connection| connection.room_id.clone())
+    }
+
+    /// Moves a connected socket into another room, leaving any call in the
+    /// old one. Returns false if the user was not connected or already there.
+    pub async fn move_to_room(&self, user_id: i32, room_id: &str) -> Result<bool, AppError> {
+        let (previous_room, call_id) = match self.connections.get(&user_id) {
+            Some(connection) if !connection.in_lobby && connection.room_id != room_id => {
+                (connection.room_id.clone(), connection.call_id)
             }
+            _ => return Ok(false),
+        };
+
+        if let Some(mut room_users) = self.rooms.get_mut(&previous_room) {
+            room_users.retain(|(id, _)| *id != user_id);
+        }
+
+        if let Some(call_id) = call_id {
+            let _ = self
+                .call_service
+                .remove_call_participant(call_id, user_id)
+                .await;
+        }
+
+        let user = self.call_service.get_caller_info(user_id).await?;
+        self.broadcast_message(
+            &previous_room,
+            &ServerMessage::UserLeft {
+                user_id,
+                user_name: user.1.clone(),
+            },
+        );
+
+        if let Some(mut connection) = self.connections.get_mut(&user_id) {
+            connection.room_id = room_id.to_string();
+            connection.call_id = None;
+        }
+        if let Some(call_id) = call_id {
+            self.settle_recording(call_id);
+            self.drop_raised_hand(call_id, user_id);
+        }
+
+        self.rooms
+            .entry(room_id.to_string())
+            .or_default()
+            .push((user_id, user.1));
+
+        Ok(true)
+    }
+
+    /// Moves assigned users who are in the meeting into their breakout rooms.
+    pub async fn open_breakouts(&self, overview: &BreakoutOverview) {
+        for room in &overview.rooms {
+            let Some(room_id) = &room.room_id else {
+                continue;
+            };
+
+            for user_id in &room.user_ids {
+                if !self.is_connected_to_room(*user_id, &overview.session.room_id) {
+                    continue;
+                }
 
-            // Uncomment if needed
-            // let _ = self
-            //     .room_service
-            //     .leave_room(&connection.room_id, user_id)
-            //     .await;
+                match self.move_to_room(*user_id, room_id).await {
+                    Ok(true) => self.send_message(
+                        *user_id,
+                        &ServerMessage::BreakoutOpened {
+                            session_id: overview.session.id,
+                            room_id: room_id.clone(),
+                            name: room.name.clone(),
+                            closes_at: overview.session.closes_at,
+                        },
+                    ),
+                    Ok(false) => {}
+                    Err(e) => eprintln!("Failed to move user {} to breakout: {}", user_id, e),
+                }
+            }
         }
     }
 
+    /// Pulls everyone still in a breakout room back into the meeting.
+    pub async fn close_breakouts(&self, overview: &BreakoutOverview) {
+        let main_room = &overview.session.room_id;
+
+        for room_id in overview.room_ids() {
+            for (user_id, _) in self.get_room_users(room_id).await {
+                match self.move_to_room(user_id, main_room).await {
+                    Ok(true) => self.send_message(
+                        user_id,
+                        &ServerMessage::BreakoutClosed {
+                            session_id: overview.session.id,
+                            room_id: main_room.clone(),
+                        },
+                    ),
+                    Ok(false) => {}
+                    Err(e) => eprintln!("Failed to return user {} from breakout: {}", user_id, e),
+                }
+            }
+        }
+    }
+
+    /// Sends a moderator's message to the meeting and every breakout room.
+    pub async fn broadcast_to_breakouts(
+        &self,
+        overview: &BreakoutOverview,
+        user_id: i32,
+        message: &str,
+    ) -> Result<(), AppError> {
+        let message = message.trim();
+        if message.is_empty() {
+            return Err(AppError::Validation("Message cannot be empty".into()));
+        }
+
+        let user = self.call_service.get_caller_info(user_id).await?;
+        let message = ServerMessage::BreakoutBroadcast {
+            session_id: overview.session.id,
+            from: user_id,
+            user_name: user.1,
+            message: message.to_string(),
+        };
+
+        self.broadcast_message(&overview.session.room_id, &message);
+        for room_id in overview.room_ids() {
+            self.broadcast_message(room_id, &message);
+        }
+        Ok(())
+    }
+
+    pub fn is_connected_to_room(&self, user_id: i32, room_id: &str) -> bool {
+        self.connections
+            .get(&user_id)
+            .is_some_and(|connection| connection.room_id == room_id)
+    }
+
+    pub fn is_on_call(&self, user_id: i32, call_id: i32) -> bool {
+        self.connections
+            .get(&user_id)
+            .is_some_and(|connection| connection.call_id == Some(call_id))
+    }
+
+    pub fn has_connections(&self, room_id: &str) -> bool {
+        self.rooms
+            .get(room_id)
+            .is_some_and(|room_users| !room_users.is_empty())
+    }
+
     pub fn send_to_user(&self, user_id: i32, message: &str) -> Result<(), String> {
         if let Some(connection) = self.connections.get(&user_id) {
             let msg = OutgoingMessage::Text(message.into());
@@ -123,6 +553,734 @@ impl SignalingServer {
         }
     }
 
+    pub fn send_message(&self, user_id: i32, message: &ServerMessage) {
+        if let Ok(json) = serde_json::to_string(message) {
+            let _ = self.send_to_user(user_id, &json);
+        }
+    }
+
+    /// Sends a message to everyone connected to the room, including the sender.
+    pub fn broadcast_message(&self, room_id: &str, message: &ServerMessage) {
+        if let Some(room_users) = self.rooms.get(room_id) {
+            for (id, _) in room_users.iter() {
+                self.send_message(*id, message);
+            }
+        }
+    }
+
+    /// Pushes an incoming call to every user still being rung, on whichever
+    /// socket they have open, and shows the caller who is ringing.
+    pub async fn ring(&self, call: &Call, invites: &[CallInvite]) -> Result<(), AppError> {
+        let (_, user_name) = self.call_service.get_caller_info(call.caller_id).await?;
+
+        for invite in invites {
+            if invite.status != CallInviteStatus::Ringing {
+                continue;
+            }
+
+            self.send_message(
+                invite.user_id,
+                &ServerMessage::IncomingCall {
+                    call_id: call.id,
+                    room_id: call.room_id.clone(),
+                    from: call.caller_id,
+                    user_name: user_name.clone(),
+                    expires_at: invite.expires_at.clone(),
+                },
+            );
+            self.notify_invite_updated(&CallInviteUpdate {
+                call: call.clone(),
+                invite: invite.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Tells the caller, the rung user and everyone in the room how an
+    /// invite changed.
+    pub fn notify_invite_updated(&self, update: &CallInviteUpdate) {
+        let message = ServerMessage::CallInviteUpdated {
+            call_id: update.call.id,
+            user_id: update.invite.user_id,
+            status: update.invite.status,
+            call_status: update.call.status.clone(),
+        };
+
+        let mut recipients = vec![update.call.caller_id, update.invite.user_id];
+        if let Some(room_users) = self.rooms.get(&update.call.room_id) {
+            recipients.extend(room_users.iter().map(|(id, _)| *id));
+        }
+        recipients.sort_unstable();
+        recipients.dedup();
+
+        for user_id in recipients {
+            self.send_message(user_id, &message);
+        }
+    }
+
+    pub fn notify_owner_changed(&self, transfer: &OwnershipTransfer) {
+        self.broadcast_message(
+            &transfer.room_id,
+            &ServerMessage::OwnerChanged {
+                room_id: transfer.room_id.clone(),
+                previous_owner_id: transfer.previous_owner_id,
+                new_owner_id: transfer.new_owner_id,
+            },
+        );
+    }
+
+    pub fn notify_policies_updated(&self, room: &Room) {
+        self.broadcast_message(
+            &room.id,
+            &ServerMessage::RoomPoliciesUpdated {
+                room_id: room.id.clone(),
+                policies: room.policies.clone(),
+            },
+        );
+    }
+
+    pub async fn update_media_state(
+        &self,
+        user_id: i32,
+        room_id: &str,
+        audio_enabled: bool,
+        video_enabled: bool,
+        screen_sharing: bool,
+    ) -> Result<(), AppError> {
+        if (audio_enabled || video_enabled) && self.recording_locks_media(user_id) {
+            return Err(AppError::Validation(
+                "Accept the recording on this call to turn your microphone or camera on".into(),
+            ));
+        }
+
+        self.room_service
+            .update_media_state(
+                room_id,
+                user_id,
+                audio_enabled,
+                video_enabled,
+                screen_sharing,
+            )
+            .await?;
+
+        let reported = MediaFlags {
+            audio_enabled,
+            video_enabled,
+            screen_sharing,
+        };
+        let previous = self.connections.get_mut(&user_id).map(|mut connection| {
+            let previous = connection.media;
+            connection.media = reported;
+            (previous, connection.call_id)
+        });
+
+        if let Some((previous, Some(call_id))) = previous {
+            self.touch_media_activity(call_id);
+            let changes = [
+                (
+                    previous.audio_enabled != audio_enabled,
+                    audio_enabled,
+                    CallEventType::Unmuted,
+                    CallEventType::Muted,
+                ),
+                (
+                    previous.video_enabled != video_enabled,
+                    video_enabled,
+                    CallEventType::VideoOn,
+                    CallEventType::VideoOff,
+                ),
+                (
+                    previous.screen_sharing != screen_sharing,
+                    screen_sharing,
+                    CallEventType::ScreenShareStarted,
+                    CallEventType::ScreenShareStopped,
+                ),
+            ];
+
+            for (changed, enabled, on, off) in changes {
+                if changed {
+                    let event_type = if enabled { on } else { off };
+                    self.record_event(NewCallEvent::new(call_id, event_type, Some(user_id)))
+                        .await;
+                }
+            }
+        }
+
+        self.broadcast_message(
+            room_id,
+            &ServerMessage::MediaStateChanged {
+                user_id,
+                audio_enabled,
+                video_enabled,
+                screen_sharing,
+            },
+        );
+        Ok(())
+    }
+
+    fn current_call(&self, user_id: i32) -> Result<(i32, String), AppError> {
+        self.connections
+            .get(&user_id)
+            .and_then(|connection| {
+                connection
+                    .call_id
+                    .map(|call_id| (call_id, connection.room_id.clone()))
+            })
+            .ok_or_else(|| AppError::Validation("Join the call first".into()))
+    }
+
+    /// Users whose socket is on the call.
+    pub fn call_members(&self, call_id: i32) -> Vec<i32> {
+        let mut members: Vec<i32> = self
+            .connections
+            .iter()
+            .filter(|connection| connection.call_id == Some(call_id))
+            .map(|connection| connection.user_id)
+            .collect();
+        members.sort_unstable();
+        members
+    }
+
+    /// Whether everyone on the call has been asked and the recording is live.
+    pub fn is_recording(&self, call_id: i32) -> bool {
+        self.recordings
+            .get(&call_id)
+            .is_some_and(|consent| consent.indicator == RecordingIndicator::Recording)
+    }
+
+    /// Whether the user's microphone and camera have to stay off: they
+    /// declined the recording on their call, or joined while it was live and
+    /// have not accepted it yet.
+    fn recording_locks_media(&self, user_id: i32) -> bool {
+        let Some(call_id) = self
+            .connections
+            .get(&user_id)
+            .and_then(|connection| connection.call_id)
+        else {
+            return false;
+        };
+
+        self.recordings.get(&call_id).is_some_and(|consent| {
+            !consent.declined(&[user_id]).is_empty()
+                || (consent.indicator == RecordingIndicator::Recording
+                    && !consent.has_decided(user_id))
+        })
+    }
+
+    /// Asks everyone on the requester's call to consent to a recording. The
+    /// requester consents by asking.
+    pub async fn start_recording(&self, user_id: i32) -> Result<(), AppError> {
+        let (call_id, _) = self.current_call(user_id)?;
+
+        match self.recordings.entry(call_id) {
+            Entry::Occupied(_) => {
+                return Err(AppError::Validation(format!(
+                    "Call {} is already being recorded",
+                    call_id
+                )));
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(RecordingConsent::new(user_id));
+            }
+        }
+
+        if let Err(e) = self
+            .call_service
+            .record_recording_consent(call_id, user_id, true)
+            .await
+        {
+            self.recordings.remove(&call_id);
+            return Err(e);
+        }
+
+        let (_, user_name) = self.call_service.get_caller_info(user_id).await?;
+        let message = ServerMessage::RecordingConsentRequested {
+            call_id,
+            started_by: user_id,
+            user_name,
+        };
+        for member in self.call_members(call_id) {
+            if member != user_id {
+                self.send_message(member, &message);
+            }
+        }
+
+        self.settle_recording(call_id);
+        Ok(())
+    }
+
+    /// Stops the recording on the user's call and clears the indicator. Only
+    /// whoever started it or a room moderator may stop it.
+    pub async fn stop_recording(&self, user_id: i32) -> Result<(), AppError> {
+        let (call_id, room_id) = self.current_call(user_id)?;
+        let started_by = self
+            .recordings
+            .get(&call_id)
+            .map(|consent| consent.started_by)
+            .ok_or_else(|| AppError::NotFound(format!("Call {} is not being recorded", call_id)))?;
+
+        if started_by != user_id
+            && !self
+                .room_service
+                .is_user_moderator(&room_id, user_id)
+                .await?
+        {
+            return Err(AppError::Unauthorized(
+                "Only the person who started the recording or a room moderator can stop it".into(),
+            ));
+        }
+
+        self.recordings.remove(&call_id);
+        let message = ServerMessage::RecordingState {
+            call_id,
+            status: RecordingIndicator::Stopped,
+            started_by,
+            awaiting: Vec::new(),
+            declined: Vec::new(),
+        };
+        for member in self.call_members(call_id) {
+            self.send_message(member, &message);
+        }
+        Ok(())
+    }
+
+    /// Logs a participant's answer to the recording on their call. Anyone who
+    /// refuses has their microphone and camera turned off.
+    pub async fn decide_recording_consent(
+        &self,
+        user_id: i32,
+        accepted: bool,
+    ) -> Result<(), AppError> {
+        let (call_id, room_id) = self.current_call(user_id)?;
+        let started_by = self
+            .recordings
+            .get(&call_id)
+            .map(|consent| consent.started_by)
+            .ok_or_else(|| AppError::NotFound(format!("Call {} is not being recorded", call_id)))?;
+
+        if started_by == user_id && !accepted {
+            return Err(AppError::Validation(
+                "Stop the recording instead of declining it".into(),
+            ));
+        }
+
+        self.call_service
+            .record_recording_consent(call_id, user_id, accepted)
+            .await?;
+
+        match self.recordings.get_mut(&call_id) {
+            Some(mut consent) => consent.decide(user_id, accepted),
+            // Stopped while the decision was being logged
+            None => return Ok(()),
+        }
+
+        if !accepted {
+            let screen_sharing = self
+                .connections
+                .get(&user_id)
+                .is_some_and(|connection| connection.media.screen_sharing);
+            self.update_media_state(user_id, &room_id, false, false, screen_sharing)
+                .await?;
+            self.send_message(user_id, &ServerMessage::RecordingDeclined { call_id });
+        }
+
+        self.settle_recording(call_id);
+        Ok(())
+    }
+
+    /// Shows the recording indicator to someone joining a call that is being
+    /// recorded and asks for their consent if they have not given it yet.
+    /// If the recording is already live, their microphone and camera are
+    /// turned off until they accept.
+    async fn announce_recording(&self, call_id: i32, user_id: i32) {
+        let Some((started_by, decided, live)) = self.recordings.get(&call_id).map(|consent| {
+            (
+                consent.started_by,
+                consent.has_decided(user_id),
+                consent.indicator == RecordingIndicator::Recording,
+            )
+        }) else {
+            return;
+        };
+
+        if !decided && live {
+            let media = self
+                .connections
+                .get(&user_id)
+                .map(|connection| (connection.room_id.clone(), connection.media.screen_sharing));
+            if let Some((room_id, screen_sharing)) = media
+                && let Err(e) = self
+                    .update_media_state(user_id, &room_id, false, false, screen_sharing)
+                    .await
+            {
+                eprintln!(
+                    "Failed to turn off media for user {} joining recorded call {}: {}",
+                    user_id, call_id, e
+                );
+            }
+            self.send_message(user_id, &ServerMessage::RecordingConsentPending { call_id });
+        }
+
+        if !decided {
+            match self.call_service.get_caller_info(started_by).await {
+                Ok((_, user_name)) => self.send_message(
+                    user_id,
+                    &ServerMessage::RecordingConsentRequested {
+                        call_id,
+                        started_by,
+                        user_name,
+                    },
+                ),
+                Err(e) => eprintln!("Failed to look up user {}: {}", started_by, e),
+            }
+        }
+
+        self.settle_recording(call_id);
+    }
+
+    /// Re-evaluates the recording after someone answered, joined or left and
+    /// sends the indicator to everyone on the call. The recording is dropped
+    /// once nobody is left on the call.
+    fn settle_recording(&self, call_id: i32) {
+        let members = self.call_members(call_id);
+        if members.is_empty() {
+            self.recordings.remove(&call_id);
+            return;
+        }
+
+        let message = match self.recordings.get_mut(&call_id) {
+            Some(mut consent) => {
+                consent.settle(&members);
+                ServerMessage::RecordingState {
+                    call_id,
+                    status: consent.indicator,
+                    started_by: consent.started_by,
+                    awaiting: consent.awaiting(&members),
+                    declined: consent.declined(&members),
+                }
+            }
+            None => return,
+        };
+
+        for member in members {
+            self.send_message(member, &message);
+        }
+    }
+
+    fn touch_media_activity(&self, call_id: i32) {
+        let now = Utc::now().naive_utc();
+        self.call_tracking
+            .entry(call_id)
+            .and_modify(|tracking| tracking.last_media_activity = now)
+            .or_insert(CallTracking {
+                last_media_activity: now,
+                alone_since: None,
+            });
+    }
+
+    /// How the call has been used so far, or `None` if nobody is on it.
+    /// Calls first seen here count as active since they started.
+    pub fn call_activity(
+        &self,
+        call_id: i32,
+        started_at: NaiveDateTime,
+        now: NaiveDateTime,
+    ) -> Option<CallActivity> {
+        let members = self.call_members(call_id);
+        if members.is_empty() {
+            self.call_tracking.remove(&call_id);
+            return None;
+        }
+
+        let mut tracking = self.call_tracking.entry(call_id).or_insert(CallTracking {
+            last_media_activity: started_at,
+            alone_since: None,
+        });
+        tracking.alone_since = match members.len() {
+            1 => Some(tracking.alone_since.unwrap_or(now)),
+            _ => None,
+        };
+
+        Some(CallActivity {
+            started_at,
+            last_media_activity: tracking.last_media_activity,
+            alone_since: tracking.alone_since,
+        })
+    }
+
+    pub fn warn_call_limit(&self, call_id: i32, limit: CallLimit, seconds_remaining: i64) {
+        let message = ServerMessage::CallLimitWarning {
+            call_id,
+            limit,
+            seconds_remaining,
+        };
+        for member in self.call_members(call_id) {
+            self.send_message(member, &message);
+        }
+    }
+
+    /// Takes everyone off a call the server has ended. Their sockets stay in
+    /// the room so they can start a new call.
+    pub async fn close_call(&self, call_id: i32, reason: CallLimit) {
+        let message = ServerMessage::CallEnded { call_id, reason };
+
+        for member in self.call_members(call_id) {
+            if let Some(mut connection) = self.connections.get_mut(&member) {
+                connection.call_id = None;
+            }
+            if let Err(e) = self
+                .call_service
+                .remove_call_participant(call_id, member)
+                .await
+            {
+                eprintln!(
+                    "Failed to remove user {} from call {}: {}",
+                    member, call_id, e
+                );
+            }
+            self.send_message(member, &message);
+        }
+
+        self.recordings.remove(&call_id);
+        self.call_tracking.remove(&call_id);
+        self.raised_hands.remove(&call_id);
+    }
+
+    /// Puts the user at the back of their call's raised-hand queue.
+    pub async fn raise_hand(&self, user_id: i32) -> Result<(), AppError> {
+        let (call_id, _) = self.current_call(user_id)?;
+        let (_, user_name) = self.call_service.get_caller_info(user_id).await?;
+
+        let raised = self.raised_hands.entry(call_id).or_default().raise(
+            user_id,
+            user_name,
+            Utc::now().naive_utc(),
+        );
+        if raised {
+            self.broadcast_raised_hands(call_id);
+        }
+        Ok(())
+    }
+
+    /// Lowers the user's own hand, or `target`'s if the user moderates the
+    /// room.
+    pub async fn lower_hand(&self, user_id: i32, target: Option<i32>) -> Result<(), AppError> {
+        let (call_id, room_id) = self.current_call(user_id)?;
+        let target = target.unwrap_or(user_id);
+        if target != user_id {
+            self.ensure_can_manage_hands(&room_id, user_id).await?;
+        }
+
+        let lowered = self
+            .raised_hands
+            .get_mut(&call_id)
+            .is_some_and(|mut queue| queue.lower(target));
+        if lowered {
+            self.broadcast_raised_hands(call_id);
+        }
+        Ok(())
+    }
+
+    pub async fn clear_raised_hands(&self, user_id: i32) -> Result<(), AppError> {
+        let (call_id, room_id) = self.current_call(user_id)?;
+        self.ensure_can_manage_hands(&room_id, user_id).await?;
+
+        let cleared = self
+            .raised_hands
+            .get_mut(&call_id)
+            .is_some_and(|mut queue| queue.clear());
+        if cleared {
+            self.broadcast_raised_hands(call_id);
+        }
+        Ok(())
+    }
+
+    async fn ensure_can_manage_hands(&self, room_id: &str, user_id: i32) -> Result<(), AppError> {
+        if !self
+            .room_service
+            .is_user_moderator(room_id, user_id)
+            .await?
+        {
+            return Err(AppError::Unauthorized(
+                "Only room owners and moderators can lower other people's hands".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sends the queue to someone joining a call while hands are up.
+    fn announce_raised_hands(&self, call_id: i32, user_id: i32) {
+        let Some(hands) = self
+            .raised_hands
+            .get(&call_id)
+            .filter(|queue| !queue.is_empty())
+            .map(|queue| queue.hands())
+        else {
+            return;
+        };
+
+        self.send_message(user_id, &ServerMessage::RaisedHands { call_id, hands });
+    }
+
+    /// Takes a participant who left the call out of the queue. The queue is
+    /// dropped once nobody is left on the call.
+    fn drop_raised_hand(&self, call_id: i32, user_id: i32) {
+        if self.call_members(call_id).is_empty() {
+            self.raised_hands.remove(&call_id);
+            return;
+        }
+
+        let lowered = self
+            .raised_hands
+            .get_mut(&call_id)
+            .is_some_and(|mut queue| queue.lower(user_id));
+        if lowered {
+            self.broadcast_raised_hands(call_id);
+        }
+    }
+
+    fn broadcast_raised_hands(&self, call_id: i32) {
+        let Some(hands) = self.raised_hands.get(&call_id).map(|queue| queue.hands()) else {
+            return;
+        };
+
+        let message = ServerMessage::RaisedHands { call_id, hands };
+        for member in self.call_members(call_id) {
+            self.send_message(member, &message);
+        }
+    }
+
+    /// Relays an emoji reaction to everyone on the user's call.
+    pub async fn send_reaction(&self, user_id: i32, emoji: &str) -> Result<(), AppError> {
+        let (call_id, _) = self.current_call(user_id)?;
+        let emoji = validate_reaction(emoji)?;
+        let (_, user_name) = self.call_service.get_caller_info(user_id).await?;
+
+        let message = ServerMessage::Reaction {
+            call_id,
+            user_id,
+            user_name,
+            emoji,
+        };
+        for member in self.call_members(call_id) {
+            self.send_message(member, &message);
+        }
+        Ok(())
+    }
+
+    /// Stores a caption from the user's own speech recognition and relays it
+    /// to everyone in their room.
+    pub async fn relay_caption(&self, user_id: i32, caption: NewCaption) -> Result<(), AppError> {
+        let (call_id, room_id) = self.current_call(user_id)?;
+        let segment = self
+            .call_service
+            .record_caption(call_id, user_id, caption)
+            .await?;
+
+        self.broadcast_message(&room_id, &ServerMessage::Caption(segment));
+        Ok(())
+    }
+
+    /// Stores connection statistics for the call the socket is on. Stats sent
+    /// before joining a call are ignored.
+    pub async fn record_quality_stats(
+        &self,
+        user_id: i32,
+        stats: QualityStats,
+    ) -> Result<(), AppError> {
+        let call_id = self
+            .connections
+            .get(&user_id)
+            .and_then(|connection| connection.call_id);
+
+        if let Some(call_id) = call_id {
+            // Media flowing keeps the call from counting as idle
+            if stats.bitrate_kbps.is_some_and(|kbps| kbps > 0.0) {
+                self.touch_media_activity(call_id);
+            }
+            self.call_service
+                .record_quality_sample(call_id, user_id, stats)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Adds to a call's timeline without failing the action that caused it.
+    async fn record_event(&self, event: NewCallEvent) {
+        if let Err(e) = self.call_service.record_event(event).await {
+            eprintln!("Failed to record call event: {}", e);
+        }
+    }
+
+    /// Records a moderator action on the timeline of the room's open call,
+    /// if there is one.
+    pub async fn record_moderator_action(
+        &self,
+        room_id: &str,
+        moderator_id: i32,
+        subject_id: Option<i32>,
+        action: &str,
+    ) {
+        let call = match self.call_service.get_open_call_by_room_id(room_id).await {
+            Ok(Some(call)) => call,
+            Ok(None) => return,
+            Err(e) => {
+                eprintln!("Failed to look up the open call of room {}: {}", room_id, e);
+                return;
+            }
+        };
+
+        self.record_event(
+            NewCallEvent::new(call.id, CallEventType::ModeratorAction, Some(moderator_id))
+                .with_subject(subject_id)
+                .with_details(serde_json::json!({ "action": action })),
+        )
+        .await;
+    }
+
+    pub async fn post_chat_message(
+        &self,
+        user_id: i32,
+        room_id: &str,
+        message: &str,
+    ) -> Result<(), AppError> {
+        let message = self
+            .chat_service
+            .post_message(room_id, user_id, message)
+            .await?;
+
+        let call_id = self
+            .connections
+            .get(&user_id)
+            .and_then(|connection| connection.call_id);
+        if let Some(call_id) = call_id {
+            self.record_event(
+                NewCallEvent::new(call_id, CallEventType::ChatSent, Some(user_id))
+                    .with_details(serde_json::json!({ "message_id": message.id })),
+            )
+            .await;
+        }
+
+        self.broadcast_message(room_id, &ServerMessage::ChatMessage(message));
+        Ok(())
+    }
+
+    pub async fn send_chat_history(&self, user_id: i32, room_id: &str) -> Result<(), AppError> {
+        let page = self
+            .chat_service
+            .history(room_id, user_id, None, None)
+            .await?;
+
+        self.send_message(
+            user_id,
+            &ServerMessage::ChatHistory {
+                messages: page.messages,
+            },
+        );
+        Ok(())
+    }
+
     pub fn broadcast_to_room(&self, room_id: &str, sender_id: i32, message: &str) {
         if let Some(room_users) = self.rooms.get(room_id) {
             for (id, _) in room_users.iter() {
@@ -144,3 +1302,114 @@ impl SignalingServer {
         self.call_service.get_caller_info(user_id).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        calls::{CallServiceImpl, SqliteCallRepository},
+        chat::{ChatServiceImpl, SqliteChatRepository},
+        rooms::{RoomServiceImpl, SqliteRoomRepository},
+        shared::test_db::{self, TEST_ROOM_ID},
+        users::{SqliteUserRepository, UserServiceImpl},
+    };
+    use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
+
+    /// Message types a socket has been sent, and whether it was closed.
+    fn drain(receiver: &mut UnboundedReceiver<OutgoingMessage>) -> (Vec<String>, bool) {
+        let mut types = Vec::new();
+        let mut closed = false;
+        while let Ok(message) = receiver.try_recv() {
+            match message {
+                OutgoingMessage::Text(json) => {
+                    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+                    types.push(value["type"].as_str().unwrap_or_default().to_string());
+                }
+                OutgoingMessage::Close(_) => closed = true,
+                OutgoingMessage::Binary(_) => {}
+            }
+        }
+        (types, closed)
+    }
+
+    #[tokio::test]
+    async fn test_lobby_admits_and_denies_waiting_users() {
+        let pool = test_db::memory_pool().await;
+        sqlx::query("UPDATE rooms SET room_type = 'meeting' WHERE id = $1")
+            .bind(TEST_ROOM_ID)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let room_service: Arc<dyn RoomService> = Arc::new(RoomServiceImpl::new(Arc::new(
+            SqliteRoomRepository::new(pool.clone()),
+        )));
+        let server = SignalingServer::new(
+            Arc::new(CallServiceImpl::new(
+                Arc::new(SqliteCallRepository::new(pool.clone())),
+                room_service.clone(),
+                Arc::new(UserServiceImpl::new(Arc::new(SqliteUserRepository::new(
+                    pool.clone(),
+                )))),
+                30,
+            )),
+            room_service.clone(),
+            Arc::new(ChatServiceImpl::new(
+                Arc::new(SqliteChatRepository::new(pool.clone())),
+                room_service.clone(),
+            )),
+        );
+
+        let mut receivers = Vec::new();
+        for user_id in 1..=3 {
+            let (sender, receiver) = unbounded_channel();
+            let state = server
+                .add_connection(user_id, TEST_ROOM_ID.to_string(), None, sender)
+                .await
+                .unwrap();
+            let expected = if user_id == 1 {
+                ConnectionState::Joined
+            } else {
+                ConnectionState::InLobby
+            };
+            assert_eq!(state, expected);
+            receivers.push(receiver);
+        }
+        assert_eq!(server.get_lobby_users(TEST_ROOM_ID).await.len(), 2);
+        let (owner_messages, _) = drain(&mut receivers[0]);
+        assert!(owner_messages.contains(&"lobby-knock".to_string()));
+
+        // Only moderators decide who gets in
+        assert!(matches!(
+            server.admit_from_lobby(2, TEST_ROOM_ID, Some(2)).await,
+            Err(AppError::Unauthorized(_))
+        ));
+        assert!(server.is_in_lobby(2));
+
+        server
+            .admit_from_lobby(1, TEST_ROOM_ID, Some(2))
+            .await
+            .unwrap();
+        assert!(!server.is_in_lobby(2));
+        assert!(room_service.is_user_in_room(TEST_ROOM_ID, 2).await.unwrap());
+        let (messages, closed) = drain(&mut receivers[1]);
+        assert!(messages.contains(&"lobby-admitted".to_string()));
+        assert!(!closed);
+
+        server
+            .deny_from_lobby(1, TEST_ROOM_ID, Some(3))
+            .await
+            .unwrap();
+        assert!(!server.is_in_lobby(3));
+        assert!(!room_service.is_user_in_room(TEST_ROOM_ID, 3).await.unwrap());
+        let (messages, closed) = drain(&mut receivers[2]);
+        assert!(messages.contains(&"lobby-denied".to_string()));
+        assert!(closed);
+
+        assert!(server.get_lobby_users(TEST_ROOM_ID).await.is_empty());
+        assert!(matches!(
+            server.admit_from_lobby(1, TEST_ROOM_ID, Some(3)).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::NaiveDateTime;
+
+use crate::{
+    calls::{
+        contract::CallEventParams,
+        entities::{CallEvent, CallEventType},
+    },
+    shared::response::AppError,
+};
+
+pub const DEFAULT_EVENT_PAGE_SIZE: i64 = 100;
+pub const MAX_EVENT_PAGE_SIZE: i64 = 500;
+
+/// A validated timeline query. Exports leave `limit` unset to get every match.
+#[derive(Debug, Clone, Default)]
+pub struct CallEventFilter {
+    pub event_types: Vec<CallEventType>,
+    pub actor_id: Option<i32>,
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+    pub after: Option<i32>,
+    pub limit: Option<i64>,
+}
+
+impl CallEventFilter {
+    pub fn from_params(params: CallEventParams, paginate: bool) -> Result<Self, AppError> {
+        if let (Some(from), Some(to)) = (params.from, params.to)
+            && from >= to
+        {
+            return Err(AppError::Validation("from must be before to".into()));
+        }
+
+        let limit = if paginate {
+            let limit = params.limit.unwrap_or(DEFAULT_EVENT_PAGE_SIZE);
+            if !(1..=MAX_EVENT_PAGE_SIZE).contains(&limit) {
+                return Err(AppError::Validation(format!(
+                    "Limit must be between 1 and {}",
+                    MAX_EVENT_PAGE_SIZE
+                )));
+            }
+            Some(limit)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            event_types: parse_event_types(params.event_type.as_deref())?,
+            actor_id: params.actor_id,
+            from: params.from,
+            to: params.to,
+            after: params.after,
+            limit,
+        })
+    }
+}
+
+/// Parses a comma-separated list such as `muted,unmuted`.
+fn parse_event_types(value: Option<&str>) -> Result<Vec<CallEventType>, AppError> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::parse::<CallEventType>)
+        .collect()
+}
+
+/// One JSON object per line, oldest first.
+pub fn to_ndjson(events: &[CallEvent]) -> String {
+    let mut out = String::new();
+    for event in events {
+        if let Ok(line) = serde_json::to_string(event) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_type_list_and_ndjson() {
+        assert_eq!(
+            parse_event_types(Some("muted, unmuted,,")).unwrap(),
+            vec![CallEventType::Muted, CallEventType::Unmuted]
+        );
+        assert!(parse_event_types(None).unwrap().is_empty());
+        assert!(parse_event_types(Some("muted,shouted")).is_err());
+
+        let event = CallEvent {
+            id: 1,
+            call_id: 9,
+            event_type: CallEventType::StatusChanged,
+            actor_id: None,
+            subject_id: None,
+            details: Some(r#"{"from":"ringing","to":"active"}"#.to_string()),
+            created_at: "2025-10-17 09:40:00".to_string(),
+        };
+        let ndjson = to_ndjson(&[event.clone(), event]);
+        let lines: Vec<&str> = ndjson.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains(r#""details":{"from":"ringing","to":"active"}"#));
+        assert!(lines[0].contains(r#""event_type":"status_changed""#));
+    }
+}
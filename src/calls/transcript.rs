@@ -0,0 +1,205 @@
+use std::{fmt::Write as _, str::FromStr};
+
+use chrono::NaiveDateTime;
+
+use crate::{
+    calls::entities::{Call, CaptionSegment, NewCaption},
+    rooms::search::SQLITE_TIMESTAMP_FORMAT,
+    shared::response::AppError,
+};
+
+pub const MAX_CAPTION_LENGTH: usize = 1000;
+/// Speech recognisers emit short phrases; anything longer is a client bug.
+pub const MAX_SEGMENT_MS: i64 = 60_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TranscriptFormat {
+    WebVtt,
+    Srt,
+    Text,
+}
+
+impl TranscriptFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TranscriptFormat::WebVtt => "text/vtt; charset=utf-8",
+            TranscriptFormat::Srt => "application/x-subrip; charset=utf-8",
+            TranscriptFormat::Text => "text/plain; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TranscriptFormat::WebVtt => "vtt",
+            TranscriptFormat::Srt => "srt",
+            TranscriptFormat::Text => "txt",
+        }
+    }
+}
+
+impl FromStr for TranscriptFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "vtt" | "webvtt" => Ok(TranscriptFormat::WebVtt),
+            "srt" => Ok(TranscriptFormat::Srt),
+            "txt" | "text" => Ok(TranscriptFormat::Text),
+            _ => Err(AppError::Validation(
+                "Invalid transcript format provided! Valid values are: 'vtt', 'srt', 'txt'"
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+/// Collapses line breaks and runs of whitespace, which would otherwise end
+/// a cue early in the exported files.
+pub fn clean_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub fn validate_caption(caption: &NewCaption) -> Result<(), AppError> {
+    let text = clean_text(&caption.text);
+    if text.is_empty() {
+        return Err(AppError::Validation("Caption text cannot be empty".into()));
+    }
+    if text.chars().count() > MAX_CAPTION_LENGTH {
+        return Err(AppError::Validation(format!(
+            "Caption text cannot be longer than {} characters",
+            MAX_CAPTION_LENGTH
+        )));
+    }
+    if caption.end_time < caption.start_time {
+        return Err(AppError::Validation(
+            "Caption cannot end before it starts".into(),
+        ));
+    }
+    if caption.end_time - caption.start_time > MAX_SEGMENT_MS {
+        return Err(AppError::Validation(format!(
+            "Caption segments cannot be longer than {} seconds",
+            MAX_SEGMENT_MS / 1000
+        )));
+    }
+    Ok(())
+}
+
+/// Converts a caption's client timestamps into offsets from the start of
+/// the call. Speech heard just before the call was recorded as started is
+/// pinned to the beginning.
+pub fn offsets_from_call_start(call: &Call, caption: &NewCaption) -> Result<(i64, i64), AppError> {
+    let started_at = NaiveDateTime::parse_from_str(&call.started_at, SQLITE_TIMESTAMP_FORMAT)
+        .map_err(|e| AppError::InternalServerError(format!("Invalid call start time: {}", e)))?
+        .and_utc()
+        .timestamp_millis();
+
+    Ok((
+        (caption.start_time - started_at).max(0),
+        (caption.end_time - started_at).max(0),
+    ))
+}
+
+/// `HH:MM:SS.mmm`, with a comma before the milliseconds for SRT.
+fn timestamp(ms: i64, millis_separator: char) -> String {
+    let ms = ms.max(0);
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        millis_separator,
+        ms % 1000
+    )
+}
+
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Renders captions, already ordered by start time, as a transcript file.
+pub fn render(format: TranscriptFormat, captions: &[CaptionSegment]) -> String {
+    let mut out = String::new();
+
+    match format {
+        TranscriptFormat::WebVtt => {
+            out.push_str("WEBVTT\n");
+            for caption in captions {
+                let _ = write!(
+                    out,
+                    "\n{}\n{} --> {}\n<v {}>{}\n",
+                    caption.id,
+                    timestamp(caption.start_ms, '.'),
+                    timestamp(caption.end_ms, '.'),
+                    escape_vtt(&caption.speaker_name),
+                    escape_vtt(&caption.text)
+                );
+            }
+        }
+        TranscriptFormat::Srt => {
+            for (index, caption) in captions.iter().enumerate() {
+                let _ = write!(
+                    out,
+                    "{}\n{} --> {}\n{}: {}\n\n",
+                    index + 1,
+                    timestamp(caption.start_ms, ','),
+                    timestamp(caption.end_ms, ','),
+                    caption.speaker_name,
+                    caption.text
+                );
+            }
+        }
+        TranscriptFormat::Text => {
+            for caption in captions {
+                let _ = writeln!(
+                    out,
+                    "[{}] {}: {}",
+                    &timestamp(caption.start_ms, '.')[..8],
+                    caption.speaker_name,
+                    caption.text
+                );
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_transcript_formats() {
+        let captions = vec![CaptionSegment {
+            id: 4,
+            call_id: 1,
+            user_id: 3,
+            speaker_name: "Ada Lovelace".to_string(),
+            text: "Hello <there>".to_string(),
+            start_ms: 3_723_045,
+            end_ms: 3_725_500,
+            created_at: "2025-10-21 09:00:00".to_string(),
+        }];
+
+        assert_eq!(
+            render(TranscriptFormat::WebVtt, &captions),
+            "WEBVTT\n\n4\n01:02:03.045 --> 01:02:05.500\n<v Ada Lovelace>Hello &lt;there&gt;\n"
+        );
+        assert_eq!(
+            render(TranscriptFormat::Srt, &captions),
+            "1\n01:02:03,045 --> 01:02:05,500\nAda Lovelace: Hello <there>\n\n"
+        );
+        assert_eq!(
+            render(TranscriptFormat::Text, &captions),
+            "[01:02:03] Ada Lovelace: Hello <there>\n"
+        );
+        assert_eq!(clean_text("  two\n lines "), "two lines");
+        assert_eq!(
+            "srt".parse::<TranscriptFormat>().unwrap(),
+            TranscriptFormat::Srt
+        );
+        assert!("doc".parse::<TranscriptFormat>().is_err());
+    }
+}
@@ -10,6 +10,7 @@ use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
 
 use crate::{
     calls::{
+        contract::PasscodeQueryParam,
         entities::{ServerMessage, SignalingMessage},
         signalling_server::SignalingServer,
     },
@@ -30,6 +31,7 @@ pub async fn websocket_handler(
     stream: web::Payload,
     identity: Identity,
     room_id: web::Path<String>,
+    query: web::Query<PasscodeQueryParam>,
     server: web::Data<Arc<SignalingServer>>,
 ) -> ActixResult<HttpResponse> {
     println!("Hit by client");
@@ -53,11 +55,16 @@ pub async fn websocket_handler(
     ) = unbounded_channel();
 
     if let Err(e) = server
-        .add_connection(user_id, room_id.clone(), tx.clone())
+        .add_connection(
+            user_id,
+            room_id.clone(),
+            query.passcode.as_deref(),
+            tx.clone(),
+        )
         .await
     {
         eprintln!("[{}] Failed to add connection: {:?}", user_id, e);
-        return Err(AppError::InternalServerError(e.to_string()).into());
+        return Err(e.into());
     }
 
     let user_id_clone = user_id;
@@ -118,7 +125,10 @@ pub async fn websocket_handler(
         }
 
         println!("[{}] Connection closed, cleaning up", user_id);
-        server.remove_connection(user_id).await;
+        let room_id = server.current_room(user_id).unwrap_or(room_id);
+        if !server.remove_connection(user_id).await {
+            return;
+        }
 
         let user = match server.get_caller_info(user_id).await {
             Ok(user) => user,
@@ -142,7 +152,7 @@ pub async fn websocket_handler(
 
 async fn handle_text_message(
     user_id: i32,
-    _room_id: &str,
+    room_id: &str,
     text: &str,
     server: &Arc<SignalingServer>,
     tx: &UnboundedSender<OutgoingMessage>,
@@ -150,17 +160,46 @@ async fn handle_text_message(
     let message: SignalingMessage = serde_json::from_str(text)?;
     println!("Signaling message is {:?}", message);
 
+    // Breakout rooms move the socket away from the room it connected to
+    let current_room = server
+        .current_room(user_id)
+        .unwrap_or_else(|| room_id.to_string());
+    let room_id = current_room.as_str();
+
+    if server.is_in_lobby(user_id) {
+        if let SignalingMessage::Join { .. } = message {
+            let waiting = ServerMessage::LobbyWaiting {
+                room_id: room_id.to_string(),
+            };
+            tx.send(OutgoingMessage::Text(serde_json::to_string(&waiting)?))
+                .map_err(|e| format!("Failed to send message: {}", e))?;
+            return Ok(());
+        }
+        return Err("Waiting in the lobby for a moderator to admit you".into());
+    }
+
     match message {
+        // The socket's user and room are already authenticated and
+        // admitted; the ids in the message are only checked, never trusted
         SignalingMessage::Join {
             room_id: msg_room_id,
-            user_id: msg_user_id,
+            ..
         } => {
-            let call_id = server.join_call(msg_user_id, msg_room_id.clone()).await?;
+            if msg_room_id != room_id {
+                return Err(format!(
+                    "This connection is for room {}; reconnect to join room {}",
+                    room_id, msg_room_id
+                )
+                .into());
+            }
+
+            let call_id = server.join_call(user_id, room_id.to_string()).await?;
 
-            let users = server.get_room_users(&msg_room_id).await;
+            let users = server.get_room_users(room_id).await;
 
             let response = ServerMessage::UserJoined {
-                user_id: msg_user_id,
+                user_id,
+                call_id,
                 users: users.clone(),
             };
             let json = serde_json::to_string(&response)?;
@@ -168,20 +207,27 @@ async fn handle_text_message(
                 .map_err(|e| format!("Failed to send message: {}", e))?;
 
             let broadcast_msg = ServerMessage::UserJoined {
-                user_id: msg_user_id,
+                user_id,
+                call_id,
                 users: users.clone(),
             };
             let broadcast_json = serde_json::to_string(&broadcast_msg)?;
-            server.broadcast_to_room(&msg_room_id, msg_user_id, &broadcast_json);
+            server.broadcast_to_room(room_id, user_id, &broadcast_json);
+
+            server.send_chat_history(user_id, room_id).await?;
 
-            println!(
-                "[{}] Joined call {} in room {}",
-                msg_user_id, call_id, msg_room_id
-            );
+            let waiting = server.get_lobby_users(room_id).await;
+            if !waiting.is_empty() {
+                server.broadcast_lobby_state(room_id).await;
+            }
+
+            println!("[{}] Joined call {} in room {}", user_id, call_id, room_id);
         }
 
         SignalingMessage::Leave { room_id } => {
-            server.remove_connection(user_id).await;
+            if !server.remove_connection(user_id).await {
+                return Ok(());
+            }
             let user = server.get_caller_info(user_id).await?;
 
             let message = ServerMessage::UserLeft {
@@ -197,6 +243,7 @@ async fn handle_text_message(
             target_user_id,
             sdp,
         } => {
+            ensure_same_room(server, target_user_id, room_id)?;
             let user = server.get_caller_info(user_id).await?;
             let message = ServerMessage::Offer {
                 from: user_id,
@@ -212,6 +259,7 @@ async fn handle_text_message(
             target_user_id,
             sdp,
         } => {
+            ensure_same_room(server, target_user_id, room_id)?;
             let message = ServerMessage::Answer { from: user_id, sdp };
             let json = serde_json::to_string(&message)?;
             server.send_to_user(target_user_id, &json)?;
@@ -224,6 +272,7 @@ async fn handle_text_message(
             sdp_mid,
             sdp_m_line_index,
         } => {
+            ensure_same_room(server, target_user_id, room_id)?;
             let message = ServerMessage::IceCandidate {
                 from: user_id,
                 candidate,
@@ -234,9 +283,84 @@ async fn handle_text_message(
             server.send_to_user(target_user_id, &json)?;
         }
 
-        SignalingMessage::ChatMessage { .. } => {
-            let json = serde_json::to_string(&message)?;
-            server.broadcast_to_room(_room_id, user_id, &json);
+        SignalingMessage::ChatMessage { message } => {
+            server.post_chat_message(user_id, room_id, &message).await?;
+        }
+
+        SignalingMessage::MediaState {
+            audio_enabled,
+            video_enabled,
+            screen_sharing,
+        } => {
+            server
+                .update_media_state(
+                    user_id,
+                    room_id,
+                    audio_enabled,
+                    video_enabled,
+                    screen_sharing,
+                )
+                .await?;
+        }
+
+        SignalingMessage::QualityStats(stats) => {
+            server.record_quality_stats(user_id, stats).await?;
+        }
+
+        SignalingMessage::Caption(caption) => {
+            server.relay_caption(user_id, caption).await?;
+        }
+
+        SignalingMessage::RaiseHand => {
+            server.raise_hand(user_id).await?;
+        }
+
+        SignalingMessage::LowerHand { user_id: target } => {
+            server.lower_hand(user_id, target).await?;
+        }
+
+        SignalingMessage::ClearHands => {
+            server.clear_raised_hands(user_id).await?;
+        }
+
+        SignalingMessage::Reaction { emoji } => {
+            server.send_reaction(user_id, &emoji).await?;
+        }
+
+        SignalingMessage::RecordingStart => {
+            server.start_recording(user_id).await?;
+        }
+
+        SignalingMessage::RecordingStop => {
+            server.stop_recording(user_id).await?;
+        }
+
+        SignalingMessage::RecordingConsent { accepted } => {
+            server.decide_recording_consent(user_id, accepted).await?;
+        }
+
+        SignalingMessage::LobbyAdmit {
+            user_id: waiting_id,
+        } => {
+            server
+                .admit_from_lobby(user_id, room_id, Some(waiting_id))
+                .await?;
+        }
+
+        SignalingMessage::LobbyDeny {
+            user_id: waiting_id,
+        } => {
+            server
+                .deny_from_lobby(user_id, room_id, Some(waiting_id))
+                .await?;
+        }
+
+        SignalingMessage::LobbyAdmitAll => {
+            server.admit_from_lobby(user_id, room_id, None).await?;
+        }
+
+        SignalingMessage::LobbyDenyAll => {
+            server.deny_from_lobby(user_id, room_id, None).await?;
         }
     }
 
@@ -256,3 +380,18 @@ pub async fn test_videocall() -> actix_web::Result<HttpResponse> {
         .content_type(ContentType::html())
         .body(rendered))
 }
+
+/// Signalling only flows between users connected to the same room.
+fn ensure_same_room(
+    server: &SignalingServer,
+    target_user_id: i32,
+    room_id: &str,
+) -> Result<(), AppError> {
+    if !server.is_connected_to_room(target_user_id, room_id) {
+        return Err(AppError::NotFound(format!(
+            "User {} is not in this room",
+            target_user_id
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+use crate::chat::ChatMessage;
+
+#[derive(Deserialize)]
+pub struct ChatHistoryParams {
+    pub before: Option<i32>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct EditChatMessage {
+    pub message: String,
+}
+
+#[derive(Serialize)]
+pub struct ChatPage {
+    /// Oldest first, so a page can be prepended to what the client already shows.
+    pub messages: Vec<ChatMessage>,
+    pub next_before: Option<i32>,
+}
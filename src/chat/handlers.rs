@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use actix_identity::Identity;
+use actix_web::{HttpResponse, Result as ActixResult, delete, get, patch, web};
+
+use crate::{
+    calls::{SignalingServer, entities::ServerMessage},
+    chat::{
+        ChatService,
+        contract::{ChatHistoryParams, EditChatMessage},
+    },
+    shared::response::{AppError, respond_ok},
+};
+
+#[get("/rooms/{room_id}/messages")]
+pub async fn chat_history(
+    room_id: web::Path<String>,
+    query: web::Query<ChatHistoryParams>,
+    identity: Identity,
+    chat_service: web::Data<Arc<dyn ChatService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let page = chat_service
+        .history(&room_id.into_inner(), user_id, query.before, query.limit)
+        .await?;
+    respond_ok(page)
+}
+
+#[patch("/messages/{message_id}")]
+pub async fn edit_message(
+    message_id: web::Path<i32>,
+    payload: web::Json<EditChatMessage>,
+    identity: Identity,
+    chat_service: web::Data<Arc<dyn ChatService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let message = chat_service
+        .edit_message(message_id.into_inner(), user_id, &payload.message)
+        .await?;
+
+    signaling_server.broadcast_message(
+        &message.room_id,
+        &ServerMessage::ChatMessageEdited(message.clone()),
+    );
+    respond_ok(message)
+}
+
+#[delete("/messages/{message_id}")]
+pub async fn delete_message(
+    message_id: web::Path<i32>,
+    identity: Identity,
+    chat_service: web::Data<Arc<dyn ChatService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let message = chat_service
+        .delete_message(message_id.into_inner(), user_id)
+        .await?;
+
+    signaling_server.broadcast_message(
+        &message.room_id,
+        &ServerMessage::ChatMessageDeleted {
+            id: message.id,
+            room_id: message.room_id.clone(),
+        },
+    );
+    respond_ok("Message deleted successfully")
+}
@@ -0,0 +1,10 @@
+pub mod contract;
+pub mod entities;
+pub mod handlers;
+pub mod repository;
+pub mod routes;
+pub mod service;
+
+pub use entities::ChatMessage;
+pub use repository::{ChatRepository, SqliteChatRepository};
+pub use service::{ChatService, ChatServiceImpl};
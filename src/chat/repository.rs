@@ -0,0 +1,147 @@
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+
+use crate::{chat::entities::ChatMessage, shared::response::AppError};
+
+const SELECT_MESSAGE: &str = r#"
+    SELECT
+        cm.id,
+        cm.room_id,
+        cm.user_id,
+        u.first_name || ' ' || u.last_name AS user_name,
+        cm.message,
+        cm.created_at,
+        cm.edited_at
+    FROM chat_messages cm
+    JOIN users u ON u.id = cm.user_id
+"#;
+
+#[async_trait]
+pub trait ChatRepository {
+    async fn create(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        message: &str,
+    ) -> Result<ChatMessage, AppError>;
+
+    async fn get_by_id(&self, message_id: i32) -> Result<Option<ChatMessage>, AppError>;
+
+    /// Newest first, skipping deleted messages.
+    async fn list_before(
+        &self,
+        room_id: &str,
+        before: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<ChatMessage>, AppError>;
+
+    async fn update_message(&self, message_id: i32, message: &str) -> Result<(), AppError>;
+
+    async fn delete(&self, message_id: i32) -> Result<(), AppError>;
+}
+
+pub struct SqliteChatRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteChatRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ChatRepository for SqliteChatRepository {
+    async fn create(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        message: &str,
+    ) -> Result<ChatMessage, AppError> {
+        let message_id: i32 = sqlx::query_scalar(
+            r#"
+            INSERT INTO chat_messages (room_id, user_id, message)
+            VALUES ($1, $2, $3)
+            RETURNING id
+            "#,
+        )
+        .bind(room_id)
+        .bind(user_id)
+        .bind(message)
+        .fetch_one(&self.pool)
+        .await?;
+
+        self.get_by_id(message_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Message {} not found", message_id)))
+    }
+
+    async fn get_by_id(&self, message_id: i32) -> Result<Option<ChatMessage>, AppError> {
+        let message = sqlx::query_as::<_, ChatMessage>(&format!(
+            "{} WHERE cm.id = $1 AND cm.deleted_at IS NULL",
+            SELECT_MESSAGE
+        ))
+        .bind(message_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(message)
+    }
+
+    async fn list_before(
+        &self,
+        room_id: &str,
+        before: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<ChatMessage>, AppError> {
+        let messages = sqlx::query_as::<_, ChatMessage>(&format!(
+            r#"
+            {}
+            WHERE cm.room_id = $1
+                AND cm.deleted_at IS NULL
+                AND ($2 IS NULL OR cm.id < $2)
+            ORDER BY cm.id DESC
+            LIMIT $3
+            "#,
+            SELECT_MESSAGE
+        ))
+        .bind(room_id)
+        .bind(before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(messages)
+    }
+
+    async fn update_message(&self, message_id: i32, message: &str) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE chat_messages
+            SET message = $1, edited_at = CURRENT_TIMESTAMP
+            WHERE id = $2 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(message)
+        .bind(message_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, message_id: i32) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE chat_messages
+            SET message = '', deleted_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            "#,
+        )
+        .bind(message_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,13 @@
+use actix_web::{middleware, web};
+
+use crate::{chat::handlers, infrastructure::middlewares::auth_middleware};
+
+pub fn chat_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/chat")
+            .wrap(middleware::from_fn(auth_middleware::auth))
+            .service(handlers::chat_history)
+            .service(handlers::edit_message)
+            .service(handlers::delete_message),
+    );
+}
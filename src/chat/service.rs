@@ -0,0 +1,232 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    chat::{contract::ChatPage, entities::ChatMessage, repository::ChatRepository},
+    rooms::{RoomAction, RoomService},
+    shared::response::AppError,
+};
+
+const MAX_MESSAGE_LENGTH: usize = 2000;
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 100;
+
+#[async_trait]
+pub trait ChatService: Send + Sync {
+    async fn post_message(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        message: &str,
+    ) -> Result<ChatMessage, AppError>;
+
+    async fn history(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        before: Option<i32>,
+        limit: Option<i64>,
+    ) -> Result<ChatPage, AppError>;
+
+    async fn edit_message(
+        &self,
+        message_id: i32,
+        user_id: i32,
+        message: &str,
+    ) -> Result<ChatMessage, AppError>;
+
+    /// Authors can delete their own messages; room moderators can delete any.
+    async fn delete_message(&self, message_id: i32, user_id: i32) -> Result<ChatMessage, AppError>;
+}
+
+pub struct ChatServiceImpl {
+    repo: Arc<dyn ChatRepository + Send + Sync>,
+    room_service: Arc<dyn RoomService + Send + Sync>,
+}
+
+impl ChatServiceImpl {
+    pub fn new(
+        repo: Arc<dyn ChatRepository + Send + Sync>,
+        room_service: Arc<dyn RoomService + Send + Sync>,
+    ) -> Self {
+        Self { repo, room_service }
+    }
+
+    fn validate_message(message: &str) -> Result<String, AppError> {
+        let message = message.trim();
+        if message.is_empty() {
+            return Err(AppError::Validation("Message cannot be empty".into()));
+        }
+        if message.chars().count() > MAX_MESSAGE_LENGTH {
+            return Err(AppError::Validation(format!(
+                "Message cannot be longer than {} characters",
+                MAX_MESSAGE_LENGTH
+            )));
+        }
+        Ok(message.to_string())
+    }
+
+    async fn ensure_member(&self, room_id: &str, user_id: i32) -> Result<(), AppError> {
+        if !self.room_service.is_user_in_room(room_id, user_id).await? {
+            return Err(AppError::Unauthorized(format!(
+                "User {} is not a member of room {}",
+                user_id, room_id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn get_message(&self, message_id: i32) -> Result<ChatMessage, AppError> {
+        self.repo
+            .get_by_id(message_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Message {} not found", message_id)))
+    }
+}
+
+#[async_trait]
+impl ChatService for ChatServiceImpl {
+    async fn post_message(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        message: &str,
+    ) -> Result<ChatMessage, AppError> {
+        let message = Self::validate_message(message)?;
+        self.room_service
+            .ensure_permitted(room_id, user_id, RoomAction::Chat)
+            .await?;
+
+        self.repo.create(room_id, user_id, &message).await
+    }
+
+    async fn history(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        before: Option<i32>,
+        limit: Option<i64>,
+    ) -> Result<ChatPage, AppError> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+            return Err(AppError::Validation(format!(
+                "Limit must be between 1 and {}",
+                MAX_PAGE_SIZE
+            )));
+        }
+
+        self.ensure_member(room_id, user_id).await?;
+
+        let mut messages = self.repo.list_before(room_id, before, limit + 1).await?;
+
+        let next_before = if messages.len() as i64 > limit {
+            messages.truncate(limit as usize);
+            messages.last().map(|m| m.id)
+        } else {
+            None
+        };
+
+        messages.reverse();
+
+        Ok(ChatPage {
+            messages,
+            next_before,
+        })
+    }
+
+    async fn edit_message(
+        &self,
+        message_id: i32,
+        user_id: i32,
+        message: &str,
+    ) -> Result<ChatMessage, AppError> {
+        let message = Self::validate_message(message)?;
+        let existing = self.get_message(message_id).await?;
+
+        if existing.user_id != user_id {
+            return Err(AppError::Unauthorized(
+                "You can only edit your own messages".into(),
+            ));
+        }
+
+        self.repo.update_message(message_id, &message).await?;
+        self.get_message(message_id).await
+    }
+
+    async fn delete_message(&self, message_id: i32, user_id: i32) -> Result<ChatMessage, AppError> {
+        let existing = self.get_message(message_id).await?;
+
+        if existing.user_id != user_id
+            && !self
+                .room_service
+                .is_user_moderator(&existing.room_id, user_id)
+                .await?
+        {
+            return Err(AppError::Unauthorized(
+                "Only the author or a room moderator can delete this message".into(),
+            ));
+        }
+
+        self.repo.delete(message_id).await?;
+        Ok(existing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chat::SqliteChatRepository,
+        rooms::{RoomServiceImpl, SqliteRoomRepository},
+        shared::test_db::{self, TEST_ROOM_ID},
+    };
+
+    #[tokio::test]
+    async fn test_only_authors_edit_and_authors_or_moderators_delete() {
+        let pool = test_db::memory_pool().await;
+        let room_service = Arc::new(RoomServiceImpl::new(Arc::new(SqliteRoomRepository::new(
+            pool.clone(),
+        ))));
+        room_service.join_room(TEST_ROOM_ID, 2, None).await.unwrap();
+        room_service.join_room(TEST_ROOM_ID, 3, None).await.unwrap();
+        let service = ChatServiceImpl::new(Arc::new(SqliteChatRepository::new(pool)), room_service);
+
+        let first = service
+            .post_message(TEST_ROOM_ID, 2, "hello")
+            .await
+            .unwrap();
+        let second = service
+            .post_message(TEST_ROOM_ID, 2, "again")
+            .await
+            .unwrap();
+
+        // Not even the room owner may put words in someone else's mouth
+        for user_id in [1, 3] {
+            assert!(matches!(
+                service.edit_message(first.id, user_id, "changed").await,
+                Err(AppError::Unauthorized(_))
+            ));
+        }
+        let edited = service
+            .edit_message(first.id, 2, "hello there")
+            .await
+            .unwrap();
+        assert_eq!(edited.message, "hello there");
+        assert!(edited.edited_at.is_some());
+
+        assert!(matches!(
+            service.delete_message(first.id, 3).await,
+            Err(AppError::Unauthorized(_))
+        ));
+        service.delete_message(first.id, 2).await.unwrap();
+        service.delete_message(second.id, 1).await.unwrap();
+
+        let page = service.history(TEST_ROOM_ID, 3, None, None).await.unwrap();
+        assert!(page.messages.is_empty());
+        assert!(matches!(
+            service.edit_message(first.id, 2, "back").await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+}
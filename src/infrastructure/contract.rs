@@ -10,4 +10,5 @@ pub struct TurnCredentials {
 #[derive(Deserialize)]
 pub struct RoomIdQueryParam {
     pub room_id: String,
+    pub passcode: Option<String>,
 }
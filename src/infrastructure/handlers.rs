@@ -2,14 +2,11 @@ use std::sync::Arc;
 
 use actix_identity::Identity;
 use actix_web::{HttpResponse, get, http::header::ContentType, web};
-use base64::{Engine as _, engine::general_purpose};
-use hmac::{Hmac, Mac};
 use serde_json::json;
-use sha1::Sha1;
 use tera::Context;
 
 use crate::{
-    infrastructure::{contract::RoomIdQueryParam, templates::TEMPLATES},
+    infrastructure::{contract::RoomIdQueryParam, templates::TEMPLATES, turn},
     shared::response::AppError,
     users::UserService,
 };
@@ -62,29 +59,7 @@ pub async fn get_turn_credentials(
         .parse::<i32>()
         .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
 
-    let shared_secret = "MyVerySecretKey12345";
-    let ttl = 86400;
-
-    let timestamp = chrono::Utc::now().timestamp() + ttl;
-    let username = format!("{}:user{}", timestamp, user_id);
-
-    let mut mac = Hmac::<Sha1>::new_from_slice(shared_secret.as_bytes())
-        .expect("HMAC can take key of any size");
-    mac.update(username.as_bytes());
-    let result = mac.finalize();
-    let credential = general_purpose::STANDARD.encode(result.into_bytes());
-
-    let ice_servers = json!([
-        { "urls": "stun:stun.l.google.com:19302" },
-        {
-            "urls": vec![
-                "stun:159.13.60.202:3478".to_string(),
-                "turn:159.13.60.202:3478".to_string()
-            ],
-            "username": username,
-            "credential": credential
-        }
-    ]);
+    let ice_servers = turn::ice_servers(user_id);
 
     let user_name = user_service
         .get_by_id(user_id)
@@ -97,6 +72,7 @@ pub async fn get_turn_credentials(
     context.insert("user_id", &user_id);
     context.insert("user_name", &user_name);
     context.insert("room_id", &room_id.room_id);
+    context.insert("passcode", &room_id.passcode.clone().unwrap_or_default());
     context.insert("ice_servers", &ice_servers.to_string());
 
     let rendered = TEMPLATES
@@ -5,3 +5,4 @@ mod handlers;
 pub mod middlewares;
 pub mod routes;
 pub mod templates;
+pub mod turn;
@@ -0,0 +1,31 @@
+use base64::{Engine as _, engine::general_purpose};
+use hmac::{Hmac, Mac};
+use serde_json::{Value, json};
+use sha1::Sha1;
+
+const TURN_SHARED_SECRET: &str = "MyVerySecretKey12345";
+const TURN_CREDENTIAL_TTL_SECS: i64 = 86400;
+
+/// ICE server list with short-lived TURN credentials for the given user.
+pub fn ice_servers(user_id: i32) -> Value {
+    let timestamp = chrono::Utc::now().timestamp() + TURN_CREDENTIAL_TTL_SECS;
+    let username = format!("{}:user{}", timestamp, user_id);
+
+    let mut mac = Hmac::<Sha1>::new_from_slice(TURN_SHARED_SECRET.as_bytes())
+        .expect("HMAC can take key of any size");
+    mac.update(username.as_bytes());
+    let result = mac.finalize();
+    let credential = general_purpose::STANDARD.encode(result.into_bytes());
+
+    json!([
+        { "urls": "stun:stun.l.google.com:19302" },
+        {
+            "urls": vec![
+                "stun:159.13.60.202:3478".to_string(),
+                "turn:159.13.60.202:3478".to_string()
+            ],
+            "username": username,
+            "credential": credential
+        }
+    ])
+}
@@ -1,6 +1,11 @@
+pub mod analytics;
 pub mod auth;
+pub mod breakouts;
 pub mod calls;
+pub mod chat;
 pub mod infrastructure;
+pub mod meetings;
+pub mod recordings;
 pub mod rooms;
 pub mod shared;
 pub mod users;
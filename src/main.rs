@@ -6,9 +6,9 @@ use actix_session::{SessionMiddleware, config::PersistentSession, storage::Cooki
 use actix_web::{App, HttpServer, cookie::Key, middleware, web::Data};
 use base64::{Engine, engine::general_purpose};
 use vibecall::{
-    auth,
+    analytics, auth, breakouts,
     calls::{self, SignalingServer},
-    infrastructure, rooms,
+    chat, infrastructure, meetings, recordings, rooms,
     shared::file_service::{FileService, LocalFileService},
     users,
 };
@@ -27,13 +27,7 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("Failed to run database migrations");
 
-    let base_url =
-        std::env::var("BASE_URL").unwrap_or_else(|_| format!("http://localhost:{}", server_port));
-
-    let file_service: Arc<dyn FileService> = Arc::new(LocalFileService::new(
-        "./media",
-        format!("{}/media", base_url),
-    ));
+    let file_service: Arc<dyn FileService> = Arc::new(LocalFileService::new("./media"));
 
     let user_repo = Arc::new(users::SqliteUserRepository::new(sqlite_pool.clone()));
     let user_service: Arc<dyn users::UserService> =
@@ -48,13 +42,74 @@ async fn main() -> std::io::Result<()> {
         call_repo,
         room_service.clone(),
         user_service.clone(),
+        calls::ringing::ring_timeout_from_env(),
     ));
 
+    let chat_repo = Arc::new(chat::SqliteChatRepository::new(sqlite_pool.clone()));
+    let chat_service: Arc<dyn chat::ChatService> =
+        Arc::new(chat::ChatServiceImpl::new(chat_repo, room_service.clone()));
+
     let signaling_server = Arc::new(SignalingServer::new(
         call_service.clone(),
         room_service.clone(),
+        chat_service.clone(),
     ));
 
+    let meeting_repo = Arc::new(meetings::SqliteMeetingRepository::new(sqlite_pool.clone()));
+    let meeting_service: Arc<dyn meetings::MeetingService> = Arc::new(
+        meetings::MeetingServiceImpl::new(meeting_repo, room_service.clone(), user_service.clone()),
+    );
+
+    meetings::reminders::spawn_reminder_task(meeting_service.clone(), signaling_server.clone());
+
+    let breakout_repo = Arc::new(breakouts::SqliteBreakoutRepository::new(
+        sqlite_pool.clone(),
+    ));
+    let breakout_service: Arc<dyn breakouts::BreakoutService> = Arc::new(
+        breakouts::BreakoutServiceImpl::new(breakout_repo, room_service.clone()),
+    );
+
+    breakouts::timer::spawn_breakout_timer(breakout_service.clone(), signaling_server.clone());
+
+    calls::ringing::spawn_ring_timeout_task(call_service.clone(), signaling_server.clone());
+    calls::limits::spawn_call_limits_task(
+        call_service.clone(),
+        room_service.clone(),
+        signaling_server.clone(),
+        calls::limits::CallLimitsConfig::from_env(),
+    );
+
+    calls::janitor::run_startup_reconciliation(&call_service, &room_service, &signaling_server)
+        .await;
+    calls::janitor::spawn_janitor_task(
+        call_service.clone(),
+        room_service.clone(),
+        signaling_server.clone(),
+    );
+
+    let recording_repo = Arc::new(recordings::SqliteRecordingRepository::new(
+        sqlite_pool.clone(),
+    ));
+    let recording_service: Arc<dyn recordings::RecordingService> =
+        Arc::new(recordings::RecordingServiceImpl::new(
+            recording_repo,
+            call_service.clone(),
+            room_service.clone(),
+            file_service.clone(),
+        ));
+
+    recordings::retention::spawn_retention_task(
+        recording_service.clone(),
+        recordings::retention::retention_days_from_env(),
+    );
+
+    let analytics_repo = Arc::new(analytics::SqliteAnalyticsRepository::new(
+        sqlite_pool.clone(),
+    ));
+    let analytics_service: Arc<dyn analytics::AnalyticsService> = Arc::new(
+        analytics::AnalyticsServiceImpl::new(analytics_repo, user_service.clone()),
+    );
+
     println!("Server started on {}:{}", server_address, server_port);
 
     HttpServer::new(move || {
@@ -69,6 +124,11 @@ async fn main() -> std::io::Result<()> {
             .app_data(Data::new(room_service.clone()))
             .app_data(Data::new(call_service.clone()))
             .app_data(Data::new(signaling_server.clone()))
+            .app_data(Data::new(meeting_service.clone()))
+            .app_data(Data::new(chat_service.clone()))
+            .app_data(Data::new(breakout_service.clone()))
+            .app_data(Data::new(analytics_service.clone()))
+            .app_data(Data::new(recording_service.clone()))
             .wrap(IdentityMiddleware::default())
             .wrap(
                 SessionMiddleware::builder(
@@ -103,8 +163,13 @@ async fn main() -> std::io::Result<()> {
             .configure(calls::routes::call_routes)
             .configure(auth::routes::auth_routes)
             .configure(users::routes::user_routes)
-            .configure(infrastructure::routes::infrastructure_routes)
             .configure(rooms::routes::room_routes)
+            .configure(meetings::routes::meeting_routes)
+            .configure(chat::routes::chat_routes)
+            .configure(breakouts::routes::breakout_routes)
+            .configure(analytics::routes::analytics_routes)
+            .configure(recordings::routes::recording_routes)
+            .configure(infrastructure::routes::infrastructure_routes)
     })
     .bind((server_address, server_port))?
     .run()
@@ -0,0 +1,31 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+pub struct NewMeeting {
+    pub title: String,
+    pub description: Option<String>,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    pub timezone: String,
+    pub rrule: Option<String>,
+    pub reminder_minutes: Option<i32>,
+    #[serde(default)]
+    pub attendee_ids: Vec<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct OccurrenceRangeParams {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+pub struct OccurrenceStartParam {
+    pub starts_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct CalendarFeed {
+    pub feed_url: String,
+}
@@ -0,0 +1,62 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::shared::response::AppError;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ScheduledMeeting {
+    pub id: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub created_by: i32,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    pub timezone: String,
+    pub rrule: Option<String>,
+    pub reminder_minutes: i32,
+    pub is_cancelled: bool,
+    pub created_at: NaiveDateTime,
+}
+
+impl ScheduledMeeting {
+    pub fn tz(&self) -> Result<Tz, AppError> {
+        parse_timezone(&self.timezone)
+    }
+}
+
+pub fn parse_timezone(timezone: &str) -> Result<Tz, AppError> {
+    timezone
+        .parse::<Tz>()
+        .map_err(|_| AppError::Validation(format!("Unknown timezone '{}'", timezone)))
+}
+
+/// A single expanded instance of a (possibly recurring) meeting.
+#[derive(Debug, Clone, Serialize)]
+pub struct MeetingOccurrence {
+    pub meeting_id: i32,
+    pub title: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub local_starts_at: NaiveDateTime,
+    pub timezone: String,
+    pub room_id: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct OccurrenceRoom {
+    pub meeting_id: i32,
+    pub occurrence_start: NaiveDateTime,
+    pub room_id: String,
+    pub reminder_sent_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DueReminder {
+    pub meeting_id: i32,
+    pub title: String,
+    pub room_id: String,
+    pub starts_at: DateTime<Utc>,
+    pub recipients: Vec<i32>,
+}
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use actix_identity::Identity;
+use actix_web::{HttpResponse, Result as ActixResult, delete, get, http::header, post, web};
+
+use crate::{
+    meetings::{
+        MeetingService,
+        contract::{CalendarFeed, NewMeeting, OccurrenceRangeParams, OccurrenceStartParam},
+    },
+    shared::response::{AppError, respond_ok},
+};
+
+const CALENDAR_CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+
+#[post("")]
+pub async fn schedule_meeting(
+    identity: Identity,
+    meeting_json: web::Json<NewMeeting>,
+    meeting_service: web::Data<Arc<dyn MeetingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let meeting = meeting_service
+        .schedule_meeting(user_id, meeting_json.into_inner())
+        .await?;
+
+    respond_ok(meeting)
+}
+
+#[get("")]
+pub async fn list_meetings(
+    identity: Identity,
+    meeting_service: web::Data<Arc<dyn MeetingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let meetings = meeting_service.list_meetings(user_id).await?;
+    respond_ok(meetings)
+}
+
+#[get("/{meeting_id}")]
+pub async fn get_meeting(
+    meeting_id: web::Path<i32>,
+    identity: Identity,
+    meeting_service: web::Data<Arc<dyn MeetingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let meeting = meeting_service
+        .get_meeting(meeting_id.into_inner(), user_id)
+        .await?;
+    respond_ok(meeting)
+}
+
+#[delete("/{meeting_id}")]
+pub async fn cancel_meeting(
+    meeting_id: web::Path<i32>,
+    identity: Identity,
+    meeting_service: web::Data<Arc<dyn MeetingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    meeting_service
+        .cancel_meeting(meeting_id.into_inner(), user_id)
+        .await?;
+    respond_ok("Meeting cancelled successfully")
+}
+
+#[get("/{meeting_id}/occurrences")]
+pub async fn list_occurrences(
+    meeting_id: web::Path<i32>,
+    query: web::Query<OccurrenceRangeParams>,
+    identity: Identity,
+    meeting_service: web::Data<Arc<dyn MeetingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let occurrences = meeting_service
+        .list_occurrences(meeting_id.into_inner(), user_id, query.from, query.to)
+        .await?;
+    respond_ok(occurrences)
+}
+
+#[post("/{meeting_id}/occurrences/room")]
+pub async fn get_occurrence_room(
+    meeting_id: web::Path<i32>,
+    payload: web::Json<OccurrenceStartParam>,
+    identity: Identity,
+    meeting_service: web::Data<Arc<dyn MeetingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let room = meeting_service
+        .get_occurrence_room(meeting_id.into_inner(), user_id, payload.starts_at)
+        .await?;
+    respond_ok(room)
+}
+
+#[get("/{meeting_id}/ics")]
+pub async fn export_meeting(
+    meeting_id: web::Path<i32>,
+    identity: Identity,
+    meeting_service: web::Data<Arc<dyn MeetingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let meeting_id = meeting_id.into_inner();
+    let calendar = meeting_service.export_meeting(meeting_id, user_id).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(CALENDAR_CONTENT_TYPE)
+        .append_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"meeting-{}.ics\"", meeting_id),
+        ))
+        .body(calendar))
+}
+
+#[post("/feed-token")]
+pub async fn rotate_feed_token(
+    identity: Identity,
+    meeting_service: web::Data<Arc<dyn MeetingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let token = meeting_service.rotate_feed_token(user_id).await?;
+
+    let base_url =
+        std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8085".to_string());
+
+    respond_ok(CalendarFeed {
+        feed_url: format!("{}/meeting/feed/{}", base_url.trim_end_matches('/'), token),
+    })
+}
+
+#[get("/feed/{token}")]
+pub async fn calendar_feed(
+    token: web::Path<String>,
+    meeting_service: web::Data<Arc<dyn MeetingService>>,
+) -> ActixResult<HttpResponse> {
+    let calendar = meeting_service.calendar_feed(&token.into_inner()).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(CALENDAR_CONTENT_TYPE)
+        .body(calendar))
+}
@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz, TzOffset};
+
+use crate::meetings::entities::ScheduledMeeting;
+
+const ICS_DATE_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// How far past today a timezone's transitions are listed. Clients apply the
+/// last listed observance to anything later.
+const VTIMEZONE_YEARS: i64 = 10;
+
+/// Renders meetings as an iCalendar (RFC 5545) document.
+pub fn render_calendar(name: &str, meetings: &[ScheduledMeeting]) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//VibeCall//Meetings//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+
+    // Every TZID used by an event needs a matching VTIMEZONE
+    let mut earliest_by_zone: BTreeMap<&str, NaiveDateTime> = BTreeMap::new();
+    for meeting in meetings {
+        earliest_by_zone
+            .entry(&meeting.timezone)
+            .and_modify(|earliest| *earliest = (*earliest).min(meeting.starts_at))
+            .or_insert(meeting.starts_at);
+    }
+    for (timezone, earliest) in earliest_by_zone {
+        if let Ok(tz) = timezone.parse::<Tz>() {
+            lines.extend(vtimezone(tz, earliest));
+        }
+    }
+
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    for meeting in meetings {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:meeting-{}@vibecall", meeting.id));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format_local(
+            "DTSTART",
+            &meeting.timezone,
+            meeting.starts_at,
+        ));
+        lines.push(format_local("DTEND", &meeting.timezone, meeting.ends_at));
+        if let Some(rrule) = &meeting.rrule {
+            lines.push(format!(
+                "RRULE:{}",
+                rrule.trim().trim_start_matches("RRULE:")
+            ));
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&meeting.title)));
+        if let Some(description) = &meeting.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if meeting.is_cancelled {
+            lines.push("STATUS:CANCELLED".to_string());
+        }
+        if meeting.reminder_minutes > 0 {
+            lines.push("BEGIN:VALARM".to_string());
+            lines.push("ACTION:DISPLAY".to_string());
+            lines.push(format!("DESCRIPTION:{}", escape_text(&meeting.title)));
+            lines.push(format!("TRIGGER:-PT{}M", meeting.reminder_minutes));
+            lines.push("END:VALARM".to_string());
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+/// Falls back to a floating time for timezones chrono-tz does not know, as
+/// there is no VTIMEZONE to point at.
+fn format_local(property: &str, timezone: &str, value: NaiveDateTime) -> String {
+    if timezone.parse::<Tz>().is_err() {
+        return format!("{}:{}", property, value.format(ICS_DATE_FORMAT));
+    }
+    format!(
+        "{};TZID={}:{}",
+        property,
+        timezone,
+        value.format(ICS_DATE_FORMAT)
+    )
+}
+
+/// Describes the offsets `tz` uses from just before `from` until
+/// `VTIMEZONE_YEARS` from now, one observance per transition.
+fn vtimezone(tz: Tz, from: NaiveDateTime) -> Vec<String> {
+    let offset_at = |utc: NaiveDateTime| tz.offset_from_utc_datetime(&utc);
+    let seconds = |offset: &TzOffset| offset.fix().local_minus_utc();
+
+    // A day either side of the local start covers any UTC offset
+    let start = from - Duration::days(1);
+    let end = from.max(Utc::now().naive_utc()) + Duration::days(365 * VTIMEZONE_YEARS);
+
+    let mut lines = vec!["BEGIN:VTIMEZONE".to_string(), format!("TZID:{}", tz.name())];
+    let mut current = offset_at(start);
+    push_observance(
+        &mut lines,
+        &current,
+        seconds(&current),
+        start + Duration::seconds(seconds(&current) as i64),
+    );
+
+    let mut day = start;
+    while day < end {
+        let next_day = day + Duration::days(1);
+        let next = offset_at(next_day);
+        if seconds(&next) != seconds(&current) {
+            // Narrow the change down to the second it happens
+            let (mut before, mut after) = (day, next_day);
+            while after - before > Duration::seconds(1) {
+                let middle = before + (after - before) / 2;
+                if seconds(&offset_at(middle)) == seconds(&current) {
+                    before = middle;
+                } else {
+                    after = middle;
+                }
+            }
+            // Onsets are given in the local time that was in effect before
+            let onset = after + Duration::seconds(seconds(&current) as i64);
+            push_observance(&mut lines, &next, seconds(&current), onset);
+            current = next;
+        }
+        day = next_day;
+    }
+
+    lines.push("END:VTIMEZONE".to_string());
+    lines
+}
+
+fn push_observance(
+    lines: &mut Vec<String>,
+    offset: &TzOffset,
+    from_seconds: i32,
+    onset: NaiveDateTime,
+) {
+    let kind = if offset.dst_offset().is_zero() {
+        "STANDARD"
+    } else {
+        "DAYLIGHT"
+    };
+
+    lines.push(format!("BEGIN:{}", kind));
+    lines.push(format!("DTSTART:{}", onset.format(ICS_DATE_FORMAT)));
+    lines.push(format!("TZOFFSETFROM:{}", format_offset(from_seconds)));
+    lines.push(format!(
+        "TZOFFSETTO:{}",
+        format_offset(offset.fix().local_minus_utc())
+    ));
+    if let Some(name) = offset.abbreviation() {
+        lines.push(format!("TZNAME:{}", escape_text(name)));
+    }
+    lines.push(format!("END:{}", kind));
+}
+
+/// `+HHMM`, or `+HHMMSS` for the odd historical offset with seconds.
+fn format_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if seconds == 0 {
+        format!("{}{:02}{:02}", sign, hours, minutes)
+    } else {
+        format!("{}{:02}{:02}{:02}", sign, hours, minutes, seconds)
+    }
+}
+
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Folds content lines longer than 75 octets as required by RFC 5545.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut current = 0;
+
+    for ch in line.chars() {
+        let width = ch.len_utf8();
+        if current + width > 75 {
+            folded.push_str("\r\n ");
+            current = 1;
+        }
+        folded.push(ch);
+        current += width;
+    }
+
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_reference_a_matching_vtimezone() {
+        let at = |month: u32, day: u32, hour: u32| {
+            chrono::NaiveDate::from_ymd_opt(2025, month, day)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap()
+        };
+        let meeting = ScheduledMeeting {
+            id: 7,
+            title: "Standup".to_string(),
+            description: None,
+            created_by: 1,
+            starts_at: at(1, 6, 9),
+            ends_at: at(1, 6, 10),
+            timezone: "Europe/London".to_string(),
+            rrule: Some("FREQ=WEEKLY".to_string()),
+            reminder_minutes: 0,
+            is_cancelled: false,
+            created_at: at(1, 1, 0),
+        };
+
+        let calendar = render_calendar("Team", &[meeting]);
+
+        assert!(calendar.contains("DTSTART;TZID=Europe/London:20250106T090000\r\n"));
+        assert!(calendar.contains("BEGIN:VTIMEZONE\r\nTZID:Europe/London\r\n"));
+        assert!(calendar.contains(
+            "BEGIN:DAYLIGHT\r\nDTSTART:20250330T010000\r\nTZOFFSETFROM:+0000\r\n\
+             TZOFFSETTO:+0100\r\nTZNAME:BST\r\nEND:DAYLIGHT"
+        ));
+        assert!(calendar.contains(
+            "BEGIN:STANDARD\r\nDTSTART:20251026T020000\r\nTZOFFSETFROM:+0100\r\n\
+             TZOFFSETTO:+0000\r\nTZNAME:GMT\r\nEND:STANDARD"
+        ));
+        assert!(calendar.find("END:VTIMEZONE") < calendar.find("BEGIN:VEVENT"));
+    }
+}
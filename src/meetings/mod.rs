@@ -0,0 +1,13 @@
+pub mod contract;
+pub mod entities;
+pub mod handlers;
+pub mod ical;
+pub mod recurrence;
+pub mod reminders;
+pub mod repository;
+pub mod routes;
+pub mod service;
+
+pub use entities::ScheduledMeeting;
+pub use repository::{MeetingRepository, SqliteMeetingRepository};
+pub use service::{MeetingService, MeetingServiceImpl};
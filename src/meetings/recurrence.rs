@@ -0,0 +1,273 @@
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, Weekday};
+
+use crate::shared::response::AppError;
+
+/// Hard cap on expanded occurrences so a bad rule cannot spin forever.
+const MAX_OCCURRENCES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The subset of RFC 5545 RRULE we support:
+/// FREQ, INTERVAL, COUNT, UNTIL and BYDAY (weekly rules only).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<NaiveDateTime>,
+    pub by_day: Vec<Weekday>,
+}
+
+impl Recurrence {
+    pub fn parse(rule: &str) -> Result<Self, AppError> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = Vec::new();
+
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("Malformed RRULE part '{}'", part)))?;
+
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(invalid(format!("Unsupported FREQ '{}'", value))),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|i| *i > 0)
+                        .ok_or_else(|| invalid(format!("Invalid INTERVAL '{}'", value)))?;
+                }
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|c| *c > 0)
+                            .ok_or_else(|| invalid(format!("Invalid COUNT '{}'", value)))?,
+                    );
+                }
+                "UNTIL" => until = Some(parse_until(value)?),
+                "BYDAY" => {
+                    by_day = value
+                        .split(',')
+                        .map(parse_weekday)
+                        .collect::<Result<Vec<_>, _>>()?;
+                }
+                _ => return Err(invalid(format!("Unsupported RRULE part '{}'", key))),
+            }
+        }
+
+        let frequency = frequency.ok_or_else(|| invalid("RRULE requires FREQ".into()))?;
+
+        if count.is_some() && until.is_some() {
+            return Err(invalid("RRULE cannot have both COUNT and UNTIL".into()));
+        }
+
+        if !by_day.is_empty() && frequency != Frequency::Weekly {
+            return Err(invalid("BYDAY is only supported for weekly rules".into()));
+        }
+
+        by_day.sort_by_key(|d| d.num_days_from_monday());
+        by_day.dedup();
+
+        Ok(Self {
+            frequency,
+            interval,
+            count,
+            until,
+            by_day,
+        })
+    }
+
+    /// Expands the rule from `dtstart` and returns the occurrences starting
+    /// within `[from, to)`. All times are wall-clock times in the meeting's
+    /// timezone.
+    pub fn occurrences_between(
+        &self,
+        dtstart: NaiveDateTime,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Vec<NaiveDateTime> {
+        let mut result = Vec::new();
+
+        for (emitted, candidate) in self.iter(dtstart).enumerate() {
+            if emitted >= MAX_OCCURRENCES * 10 || candidate >= to {
+                break;
+            }
+            if self.until.is_some_and(|until| candidate > until) {
+                break;
+            }
+            if self.count.is_some_and(|count| emitted >= count as usize) {
+                break;
+            }
+
+            if candidate >= from {
+                result.push(candidate);
+                if result.len() >= MAX_OCCURRENCES {
+                    break;
+                }
+            }
+        }
+
+        result
+    }
+
+    fn iter(&self, dtstart: NaiveDateTime) -> impl Iterator<Item = NaiveDateTime> + '_ {
+        let mut period = 0u32;
+        let mut pending: Vec<NaiveDateTime> = Vec::new();
+
+        std::iter::from_fn(move || {
+            loop {
+                if let Some(next) = pending.pop() {
+                    return Some(next);
+                }
+                if period as usize > MAX_OCCURRENCES * 10 {
+                    return None;
+                }
+
+                let offset = period * self.interval;
+                period += 1;
+
+                let mut batch = self.period_candidates(dtstart, offset);
+                batch.retain(|c| *c >= dtstart);
+                batch.reverse();
+                pending = batch;
+            }
+        })
+    }
+
+    fn period_candidates(&self, dtstart: NaiveDateTime, offset: u32) -> Vec<NaiveDateTime> {
+        let time = dtstart.time();
+        match self.frequency {
+            Frequency::Daily => vec![dtstart + Duration::days(offset as i64)],
+            Frequency::Weekly => {
+                if self.by_day.is_empty() {
+                    return vec![dtstart + Duration::weeks(offset as i64)];
+                }
+                let week_start = dtstart.date()
+                    - Duration::days(dtstart.weekday().num_days_from_monday() as i64)
+                    + Duration::weeks(offset as i64);
+                self.by_day
+                    .iter()
+                    .map(|d| {
+                        (week_start + Duration::days(d.num_days_from_monday() as i64))
+                            .and_time(time)
+                    })
+                    .collect()
+            }
+            Frequency::Monthly => dtstart
+                .date()
+                .with_day(1)
+                .and_then(|first| first.checked_add_months(Months::new(offset)))
+                .and_then(|month| month.with_day(dtstart.day()))
+                .map(|date| vec![date.and_time(time)])
+                .unwrap_or_default(),
+            Frequency::Yearly => NaiveDate::from_ymd_opt(
+                dtstart.year() + offset as i32,
+                dtstart.month(),
+                dtstart.day(),
+            )
+            .map(|date| vec![date.and_time(time)])
+            .unwrap_or_default(),
+        }
+    }
+}
+
+fn invalid(message: String) -> AppError {
+    AppError::Validation(format!("Invalid recurrence rule: {}", message))
+}
+
+fn parse_weekday(value: &str) -> Result<Weekday, AppError> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        other => Err(invalid(format!("Unsupported BYDAY value '{}'", other))),
+    }
+}
+
+fn parse_until(value: &str) -> Result<NaiveDateTime, AppError> {
+    let value = value.trim_end_matches('Z');
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .or_else(|_| {
+            NaiveDate::parse_from_str(value, "%Y%m%d")
+                .map(|d| d.and_hms_opt(23, 59, 59).unwrap_or_default())
+        })
+        .map_err(|_| invalid(format!("Invalid UNTIL '{}'", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_weekly_by_day_with_count() {
+        let rule = Recurrence::parse("FREQ=WEEKLY;BYDAY=MO,WE;COUNT=4").unwrap();
+        let occurrences = rule.occurrences_between(
+            at("2025-10-01 09:00"),
+            at("2025-01-01 00:00"),
+            at("2026-01-01 00:00"),
+        );
+
+        assert_eq!(
+            occurrences,
+            vec![
+                at("2025-10-01 09:00"),
+                at("2025-10-06 09:00"),
+                at("2025-10-08 09:00"),
+                at("2025-10-13 09:00"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monthly_skips_missing_days_and_respects_window() {
+        let rule = Recurrence::parse("RRULE:FREQ=MONTHLY;UNTIL=20250630").unwrap();
+        let occurrences = rule.occurrences_between(
+            at("2025-01-31 10:00"),
+            at("2025-02-01 00:00"),
+            at("2025-12-31 00:00"),
+        );
+
+        assert_eq!(
+            occurrences,
+            vec![at("2025-03-31 10:00"), at("2025-05-31 10:00")]
+        );
+    }
+
+    #[test]
+    fn test_rejects_unsupported_rules() {
+        assert!(Recurrence::parse("INTERVAL=2").is_err());
+        assert!(Recurrence::parse("FREQ=HOURLY").is_err());
+        assert!(Recurrence::parse("FREQ=DAILY;BYDAY=MO").is_err());
+        assert!(Recurrence::parse("FREQ=DAILY;COUNT=2;UNTIL=20250101").is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::Utc;
+
+use crate::{
+    calls::{SignalingServer, entities::ServerMessage},
+    meetings::MeetingService,
+};
+
+const REMINDER_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Periodically pushes reminders for upcoming meeting occurrences to invited
+/// users over their open sockets. Reminders are socket-only: users who are
+/// offline are retried on every poll until the occurrence starts.
+pub fn spawn_reminder_task(
+    meeting_service: Arc<dyn MeetingService>,
+    signaling_server: Arc<SignalingServer>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(REMINDER_POLL_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            let reminders = match meeting_service.collect_due_reminders(Utc::now()).await {
+                Ok(reminders) => reminders,
+                Err(e) => {
+                    eprintln!("Failed to collect meeting reminders: {}", e);
+                    continue;
+                }
+            };
+
+            for reminder in reminders {
+                let message = ServerMessage::MeetingReminder {
+                    meeting_id: reminder.meeting_id,
+                    title: reminder.title,
+                    room_id: reminder.room_id,
+                    starts_at: reminder.starts_at,
+                };
+
+                let Ok(json) = serde_json::to_string(&message) else {
+                    continue;
+                };
+
+                for user_id in reminder.recipients {
+                    if signaling_server.send_to_user(user_id, &json).is_err() {
+                        continue;
+                    }
+
+                    if let Err(e) = meeting_service
+                        .record_reminder_delivery(reminder.meeting_id, reminder.starts_at, user_id)
+                        .await
+                    {
+                        eprintln!(
+                            "Failed to record reminder for meeting {} to user {}: {}",
+                            reminder.meeting_id, user_id, e
+                        );
+                    }
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,370 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::SqlitePool;
+
+use crate::{
+    meetings::entities::{OccurrenceRoom, ScheduledMeeting},
+    shared::response::AppError,
+};
+
+#[async_trait]
+pub trait MeetingRepository {
+    #[allow(clippy::too_many_arguments)]
+    async fn create(
+        &self,
+        title: String,
+        description: Option<String>,
+        created_by: i32,
+        starts_at: NaiveDateTime,
+        ends_at: NaiveDateTime,
+        timezone: String,
+        rrule: Option<String>,
+        reminder_minutes: i32,
+    ) -> Result<ScheduledMeeting, AppError>;
+
+    async fn get_by_id(&self, meeting_id: i32) -> Result<Option<ScheduledMeeting>, AppError>;
+
+    async fn list_for_user(&self, user_id: i32) -> Result<Vec<ScheduledMeeting>, AppError>;
+
+    async fn list_active(&self) -> Result<Vec<ScheduledMeeting>, AppError>;
+
+    async fn cancel(&self, meeting_id: i32) -> Result<(), AppError>;
+
+    // Attendees
+    async fn add_attendee(&self, meeting_id: i32, user_id: i32) -> Result<(), AppError>;
+
+    async fn list_attendees(&self, meeting_id: i32) -> Result<Vec<i32>, AppError>;
+
+    async fn is_attendee(&self, meeting_id: i32, user_id: i32) -> Result<bool, AppError>;
+
+    // Occurrence rooms
+    async fn get_occurrence_room(
+        &self,
+        meeting_id: i32,
+        occurrence_start: NaiveDateTime,
+    ) -> Result<Option<OccurrenceRoom>, AppError>;
+
+    async fn list_occurrence_rooms(
+        &self,
+        meeting_id: i32,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<OccurrenceRoom>, AppError>;
+
+    async fn create_occurrence_room(
+        &self,
+        meeting_id: i32,
+        occurrence_start: NaiveDateTime,
+        room_id: &str,
+    ) -> Result<(), AppError>;
+
+    async fn mark_reminder_sent(
+        &self,
+        meeting_id: i32,
+        occurrence_start: NaiveDateTime,
+    ) -> Result<(), AppError>;
+
+    async fn list_reminder_deliveries(
+        &self,
+        meeting_id: i32,
+        occurrence_start: NaiveDateTime,
+    ) -> Result<Vec<i32>, AppError>;
+
+    async fn record_reminder_delivery(
+        &self,
+        meeting_id: i32,
+        occurrence_start: NaiveDateTime,
+        user_id: i32,
+    ) -> Result<(), AppError>;
+
+    // Calendar feeds
+    async fn get_feed_user_id(&self, token: &str) -> Result<Option<i32>, AppError>;
+
+    async fn upsert_feed_token(&self, user_id: i32, token: &str) -> Result<(), AppError>;
+}
+
+pub struct SqliteMeetingRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteMeetingRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MeetingRepository for SqliteMeetingRepository {
+    async fn create(
+        &self,
+        title: String,
+        description: Option<String>,
+        created_by: i32,
+        starts_at: NaiveDateTime,
+        ends_at: NaiveDateTime,
+        timezone: String,
+        rrule: Option<String>,
+        reminder_minutes: i32,
+    ) -> Result<ScheduledMeeting, AppError> {
+        let meeting = sqlx::query_as::<_, ScheduledMeeting>(
+            r#"
+            INSERT INTO scheduled_meetings (
+                title, description, created_by, starts_at,
+                ends_at, timezone, rrule, reminder_minutes
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(title)
+        .bind(description)
+        .bind(created_by)
+        .bind(starts_at)
+        .bind(ends_at)
+        .bind(timezone)
+        .bind(rrule)
+        .bind(reminder_minutes)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(meeting)
+    }
+
+    async fn get_by_id(&self, meeting_id: i32) -> Result<Option<ScheduledMeeting>, AppError> {
+        let meeting =
+            sqlx::query_as::<_, ScheduledMeeting>("SELECT * FROM scheduled_meetings WHERE id = $1")
+                .bind(meeting_id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(meeting)
+    }
+
+    async fn list_for_user(&self, user_id: i32) -> Result<Vec<ScheduledMeeting>, AppError> {
+        let meetings = sqlx::query_as::<_, ScheduledMeeting>(
+            r#"
+            SELECT DISTINCT m.*
+            FROM scheduled_meetings m
+            LEFT JOIN meeting_attendees ma ON m.id = ma.meeting_id
+            WHERE m.created_by = $1 OR ma.user_id = $1
+            ORDER BY m.starts_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(meetings)
+    }
+
+    async fn list_active(&self) -> Result<Vec<ScheduledMeeting>, AppError> {
+        let meetings = sqlx::query_as::<_, ScheduledMeeting>(
+            "SELECT * FROM scheduled_meetings WHERE is_cancelled = FALSE",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(meetings)
+    }
+
+    async fn cancel(&self, meeting_id: i32) -> Result<(), AppError> {
+        sqlx::query("UPDATE scheduled_meetings SET is_cancelled = TRUE WHERE id = $1")
+            .bind(meeting_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn add_attendee(&self, meeting_id: i32, user_id: i32) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO meeting_attendees (meeting_id, user_id)
+            VALUES ($1, $2)
+            "#,
+        )
+        .bind(meeting_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_attendees(&self, meeting_id: i32) -> Result<Vec<i32>, AppError> {
+        let attendees =
+            sqlx::query_scalar("SELECT user_id FROM meeting_attendees WHERE meeting_id = $1")
+                .bind(meeting_id)
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(attendees)
+    }
+
+    async fn is_attendee(&self, meeting_id: i32, user_id: i32) -> Result<bool, AppError> {
+        let exists = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM meeting_attendees
+                WHERE meeting_id = $1 AND user_id = $2
+            )
+            "#,
+        )
+        .bind(meeting_id)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    async fn get_occurrence_room(
+        &self,
+        meeting_id: i32,
+        occurrence_start: NaiveDateTime,
+    ) -> Result<Option<OccurrenceRoom>, AppError> {
+        let occurrence = sqlx::query_as::<_, OccurrenceRoom>(
+            r#"
+            SELECT meeting_id, occurrence_start, room_id, reminder_sent_at
+            FROM meeting_occurrences
+            WHERE meeting_id = $1 AND occurrence_start = $2
+            "#,
+        )
+        .bind(meeting_id)
+        .bind(occurrence_start)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(occurrence)
+    }
+
+    async fn list_occurrence_rooms(
+        &self,
+        meeting_id: i32,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<OccurrenceRoom>, AppError> {
+        let occurrences = sqlx::query_as::<_, OccurrenceRoom>(
+            r#"
+            SELECT meeting_id, occurrence_start, room_id, reminder_sent_at
+            FROM meeting_occurrences
+            WHERE meeting_id = $1 AND occurrence_start >= $2 AND occurrence_start < $3
+            "#,
+        )
+        .bind(meeting_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(occurrences)
+    }
+
+    async fn create_occurrence_room(
+        &self,
+        meeting_id: i32,
+        occurrence_start: NaiveDateTime,
+        room_id: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO meeting_occurrences (meeting_id, occurrence_start, room_id)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(meeting_id)
+        .bind(occurrence_start)
+        .bind(room_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_reminder_sent(
+        &self,
+        meeting_id: i32,
+        occurrence_start: NaiveDateTime,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE meeting_occurrences
+            SET reminder_sent_at = CURRENT_TIMESTAMP
+            WHERE meeting_id = $1 AND occurrence_start = $2
+            "#,
+        )
+        .bind(meeting_id)
+        .bind(occurrence_start)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_reminder_deliveries(
+        &self,
+        meeting_id: i32,
+        occurrence_start: NaiveDateTime,
+    ) -> Result<Vec<i32>, AppError> {
+        let user_ids = sqlx::query_scalar(
+            r#"
+            SELECT user_id FROM meeting_reminder_deliveries
+            WHERE meeting_id = $1 AND occurrence_start = $2
+            "#,
+        )
+        .bind(meeting_id)
+        .bind(occurrence_start)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    async fn record_reminder_delivery(
+        &self,
+        meeting_id: i32,
+        occurrence_start: NaiveDateTime,
+        user_id: i32,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO meeting_reminder_deliveries (meeting_id, occurrence_start, user_id)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(meeting_id)
+        .bind(occurrence_start)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_feed_user_id(&self, token: &str) -> Result<Option<i32>, AppError> {
+        let user_id =
+            sqlx::query_scalar("SELECT user_id FROM calendar_feed_tokens WHERE token = $1")
+                .bind(token)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(user_id)
+    }
+
+    async fn upsert_feed_token(&self, user_id: i32, token: &str) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO calendar_feed_tokens (user_id, token)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET token = excluded.token, created_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(user_id)
+        .bind(token)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
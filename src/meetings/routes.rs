@@ -0,0 +1,23 @@
+use actix_web::{middleware, web};
+
+use crate::{infrastructure::middlewares::auth_middleware, meetings::handlers};
+
+pub fn meeting_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/meeting")
+            // Calendar apps fetch the feed without a session; the token is the credential.
+            .service(handlers::calendar_feed)
+            .service(
+                web::scope("")
+                    .wrap(middleware::from_fn(auth_middleware::auth))
+                    .service(handlers::rotate_feed_token)
+                    .service(handlers::schedule_meeting)
+                    .service(handlers::list_meetings)
+                    .service(handlers::get_meeting)
+                    .service(handlers::cancel_meeting)
+                    .service(handlers::list_occurrences)
+                    .service(handlers::get_occurrence_room)
+                    .service(handlers::export_meeting),
+            ),
+    );
+}
@@ -0,0 +1,588 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use rand::{Rng, distributions::Alphanumeric};
+use tokio::sync::Mutex;
+
+use crate::{
+    meetings::{
+        contract::NewMeeting,
+        entities::{DueReminder, MeetingOccurrence, ScheduledMeeting, parse_timezone},
+        ical,
+        recurrence::Recurrence,
+        repository::MeetingRepository,
+    },
+    rooms::{Room, RoomService, RoomType},
+    shared::response::AppError,
+    users::UserService,
+};
+
+const FEED_TOKEN_LENGTH: usize = 32;
+const MAX_REMINDER_MINUTES: i32 = 24 * 60;
+const DEFAULT_REMINDER_MINUTES: i32 = 10;
+const DEFAULT_OCCURRENCE_WINDOW_DAYS: i64 = 30;
+
+#[async_trait]
+pub trait MeetingService: Send + Sync {
+    async fn schedule_meeting(
+        &self,
+        created_by: i32,
+        meeting: NewMeeting,
+    ) -> Result<ScheduledMeeting, AppError>;
+
+    async fn get_meeting(
+        &self,
+        meeting_id: i32,
+        user_id: i32,
+    ) -> Result<ScheduledMeeting, AppError>;
+
+    async fn list_meetings(&self, user_id: i32) -> Result<Vec<ScheduledMeeting>, AppError>;
+
+    async fn cancel_meeting(&self, meeting_id: i32, user_id: i32) -> Result<(), AppError>;
+
+    async fn list_occurrences(
+        &self,
+        meeting_id: i32,
+        user_id: i32,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<MeetingOccurrence>, AppError>;
+
+    async fn get_occurrence_room(
+        &self,
+        meeting_id: i32,
+        user_id: i32,
+        starts_at: DateTime<Utc>,
+    ) -> Result<Room, AppError>;
+
+    async fn collect_due_reminders(&self, now: DateTime<Utc>)
+    -> Result<Vec<DueReminder>, AppError>;
+
+    async fn record_reminder_delivery(
+        &self,
+        meeting_id: i32,
+        starts_at: DateTime<Utc>,
+        user_id: i32,
+    ) -> Result<(), AppError>;
+
+    async fn export_meeting(&self, meeting_id: i32, user_id: i32) -> Result<String, AppError>;
+
+    async fn calendar_feed(&self, token: &str) -> Result<String, AppError>;
+
+    async fn rotate_feed_token(&self, user_id: i32) -> Result<String, AppError>;
+}
+
+pub struct MeetingServiceImpl {
+    repo: Arc<dyn MeetingRepository + Send + Sync>,
+    room_service: Arc<dyn RoomService + Send + Sync>,
+    user_service: Arc<dyn UserService + Send + Sync>,
+    occurrence_lock: Mutex<()>,
+}
+
+impl MeetingServiceImpl {
+    pub fn new(
+        repo: Arc<dyn MeetingRepository + Send + Sync>,
+        room_service: Arc<dyn RoomService + Send + Sync>,
+        user_service: Arc<dyn UserService + Send + Sync>,
+    ) -> Self {
+        Self {
+            repo,
+            room_service,
+            user_service,
+            occurrence_lock: Mutex::new(()),
+        }
+    }
+
+    async fn get_visible_meeting(
+        &self,
+        meeting_id: i32,
+        user_id: i32,
+    ) -> Result<ScheduledMeeting, AppError> {
+        let meeting = self
+            .repo
+            .get_by_id(meeting_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Meeting {} not found", meeting_id)))?;
+
+        if meeting.created_by != user_id && !self.repo.is_attendee(meeting_id, user_id).await? {
+            return Err(AppError::Unauthorized(format!(
+                "User {} is not invited to meeting {}",
+                user_id, meeting_id
+            )));
+        }
+
+        Ok(meeting)
+    }
+
+    /// Expands a meeting into the occurrences overlapping `[from, to)`.
+    fn expand(
+        meeting: &ScheduledMeeting,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<MeetingOccurrence>, AppError> {
+        let tz = meeting.tz()?;
+        let length = meeting.ends_at - meeting.starts_at;
+
+        // Widen the local window so timezone offsets cannot drop edge occurrences.
+        let local_from = from.with_timezone(&tz).naive_local() - length - Duration::days(1);
+        let local_to = to.with_timezone(&tz).naive_local() + Duration::days(1);
+
+        let local_starts = match &meeting.rrule {
+            Some(rule) => Recurrence::parse(rule)?.occurrences_between(
+                meeting.starts_at,
+                local_from,
+                local_to,
+            ),
+            None => vec![meeting.starts_at],
+        };
+
+        Ok(local_starts
+            .into_iter()
+            .map(|local_start| {
+                let starts_at = local_to_utc(tz, local_start);
+                MeetingOccurrence {
+                    meeting_id: meeting.id,
+                    title: meeting.title.clone(),
+                    starts_at,
+                    ends_at: starts_at + length,
+                    local_starts_at: local_start,
+                    timezone: meeting.timezone.clone(),
+                    room_id: None,
+                }
+            })
+            .filter(|o| o.ends_at > from && o.starts_at < to)
+            .collect())
+    }
+
+    async fn recipients(&self, meeting: &ScheduledMeeting) -> Result<Vec<i32>, AppError> {
+        let mut recipients = self.repo.list_attendees(meeting.id).await?;
+        if !recipients.contains(&meeting.created_by) {
+            recipients.push(meeting.created_by);
+        }
+        Ok(recipients)
+    }
+
+    /// Builds the reminder for an occurrence addressed to the recipients who
+    /// have not received it yet, or returns `None` once everyone has.
+    async fn take_due_reminder(
+        &self,
+        meeting: &ScheduledMeeting,
+        occurrence: &MeetingOccurrence,
+    ) -> Result<Option<DueReminder>, AppError> {
+        let start = occurrence.starts_at.naive_utc();
+        let already_sent = self
+            .repo
+            .get_occurrence_room(meeting.id, start)
+            .await?
+            .is_some_and(|o| o.reminder_sent_at.is_some());
+        if already_sent {
+            return Ok(None);
+        }
+
+        let delivered = self
+            .repo
+            .list_reminder_deliveries(meeting.id, start)
+            .await?;
+        let recipients: Vec<i32> = self
+            .recipients(meeting)
+            .await?
+            .into_iter()
+            .filter(|user_id| !delivered.contains(user_id))
+            .collect();
+        if recipients.is_empty() {
+            self.repo.mark_reminder_sent(meeting.id, start).await?;
+            return Ok(None);
+        }
+
+        let room = self.materialize_occurrence(meeting, occurrence).await?;
+
+        Ok(Some(DueReminder {
+            meeting_id: meeting.id,
+            title: meeting.title.clone(),
+            room_id: room.id,
+            starts_at: occurrence.starts_at,
+            recipients,
+        }))
+    }
+
+    /// Returns the room for an occurrence, creating it on first use.
+    async fn materialize_occurrence(
+        &self,
+        meeting: &ScheduledMeeting,
+        occurrence: &MeetingOccurrence,
+    ) -> Result<Room, AppError> {
+        let _guard = self.occurrence_lock.lock().await;
+        let start = occurrence.starts_at.naive_utc();
+
+        if let Some(existing) = self.repo.get_occurrence_room(meeting.id, start).await? {
+            return self
+                .room_service
+                .get_room(&existing.room_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Room {} not found", existing.room_id)));
+        }
+
+        let room = self
+            .room_service
+            .create_room(
+                format!(
+                    "{} ({})",
+                    meeting.title,
+                    occurrence.local_starts_at.format("%Y-%m-%d %H:%M")
+                ),
+                RoomType::Meeting.to_string(),
+                meeting.created_by,
+                meeting.description.clone(),
+                None,
+            )
+            .await?;
+
+        if let Err(e) = self.link_occurrence_room(meeting, start, &room).await {
+            // Without the link the next pass would create yet another room
+            if let Err(purge_error) = self.room_service.purge_room(&room.id).await {
+                eprintln!(
+                    "Failed to remove room {} after it could not be linked to meeting {}: {}",
+                    room.id, meeting.id, purge_error
+                );
+            }
+            return Err(e);
+        }
+
+        Ok(room)
+    }
+
+    /// Adds the attendees to a freshly created occurrence room and records it
+    /// as the room for that occurrence.
+    async fn link_occurrence_room(
+        &self,
+        meeting: &ScheduledMeeting,
+        start: NaiveDateTime,
+        room: &Room,
+    ) -> Result<(), AppError> {
+        for attendee_id in self.repo.list_attendees(meeting.id).await? {
+            if attendee_id != meeting.created_by {
+                self.room_service
+                    .join_room(&room.id, attendee_id, None)
+                    .await?;
+            }
+        }
+
+        self.repo
+            .create_occurrence_room(meeting.id, start, &room.id)
+            .await
+    }
+}
+
+fn local_to_utc(tz: Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    tz.from_local_datetime(&local)
+        .earliest()
+        // Wall-clock times skipped by a DST jump start an hour later.
+        .or_else(|| {
+            tz.from_local_datetime(&(local + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&local))
+}
+
+#[async_trait]
+impl MeetingService for MeetingServiceImpl {
+    async fn schedule_meeting(
+        &self,
+        created_by: i32,
+        meeting: NewMeeting,
+    ) -> Result<ScheduledMeeting, AppError> {
+        if meeting.title.trim().is_empty() {
+            return Err(AppError::Validation("Meeting title cannot be empty".into()));
+        }
+
+        if meeting.ends_at <= meeting.starts_at {
+            return Err(AppError::Validation(
+                "Meeting must end after it starts".into(),
+            ));
+        }
+
+        parse_timezone(&meeting.timezone)?;
+
+        let rrule = match meeting.rrule {
+            Some(rule) if !rule.trim().is_empty() => {
+                Recurrence::parse(&rule)?;
+                Some(rule.trim().trim_start_matches("RRULE:").to_string())
+            }
+            _ => None,
+        };
+
+        let reminder_minutes = meeting.reminder_minutes.unwrap_or(DEFAULT_REMINDER_MINUTES);
+        if !(0..=MAX_REMINDER_MINUTES).contains(&reminder_minutes) {
+            return Err(AppError::Validation(format!(
+                "Reminder must be between 0 and {} minutes",
+                MAX_REMINDER_MINUTES
+            )));
+        }
+
+        for attendee_id in &meeting.attendee_ids {
+            self.user_service
+                .get_by_id(*attendee_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("User {} not found", attendee_id)))?;
+        }
+
+        let scheduled = self
+            .repo
+            .create(
+                meeting.title.trim().to_string(),
+                meeting.description,
+                created_by,
+                meeting.starts_at,
+                meeting.ends_at,
+                meeting.timezone,
+                rrule,
+                reminder_minutes,
+            )
+            .await?;
+
+        for attendee_id in meeting.attendee_ids {
+            if attendee_id != created_by {
+                self.repo.add_attendee(scheduled.id, attendee_id).await?;
+            }
+        }
+
+        Ok(scheduled)
+    }
+
+    async fn get_meeting(
+        &self,
+        meeting_id: i32,
+        user_id: i32,
+    ) -> Result<ScheduledMeeting, AppError> {
+        self.get_visible_meeting(meeting_id, user_id).await
+    }
+
+    async fn list_meetings(&self, user_id: i32) -> Result<Vec<ScheduledMeeting>, AppError> {
+        self.repo.list_for_user(user_id).await
+    }
+
+    async fn cancel_meeting(&self, meeting_id: i32, user_id: i32) -> Result<(), AppError> {
+        let meeting = self.get_visible_meeting(meeting_id, user_id).await?;
+
+        if meeting.created_by != user_id {
+            return Err(AppError::Unauthorized(
+                "Only the organizer can cancel the meeting".into(),
+            ));
+        }
+
+        self.repo.cancel(meeting_id).await
+    }
+
+    async fn list_occurrences(
+        &self,
+        meeting_id: i32,
+        user_id: i32,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<MeetingOccurrence>, AppError> {
+        let meeting = self.get_visible_meeting(meeting_id, user_id).await?;
+
+        let from = from.unwrap_or_else(Utc::now);
+        let to = to.unwrap_or_else(|| from + Duration::days(DEFAULT_OCCURRENCE_WINDOW_DAYS));
+        if to <= from {
+            return Err(AppError::Validation("'to' must be after 'from'".into()));
+        }
+
+        let mut occurrences = Self::expand(&meeting, from, to)?;
+
+        if let (Some(first), Some(last)) = (occurrences.first(), occurrences.last()) {
+            let rooms = self
+                .repo
+                .list_occurrence_rooms(
+                    meeting_id,
+                    first.starts_at.naive_utc(),
+                    last.starts_at.naive_utc() + Duration::seconds(1),
+                )
+                .await?;
+
+            for occurrence in occurrences.iter_mut() {
+                occurrence.room_id = rooms
+                    .iter()
+                    .find(|r| r.occurrence_start == occurrence.starts_at.naive_utc())
+                    .map(|r| r.room_id.clone());
+            }
+        }
+
+        Ok(occurrences)
+    }
+
+    async fn get_occurrence_room(
+        &self,
+        meeting_id: i32,
+        user_id: i32,
+        starts_at: DateTime<Utc>,
+    ) -> Result<Room, AppError> {
+        let meeting = self.get_visible_meeting(meeting_id, user_id).await?;
+
+        if meeting.is_cancelled {
+            return Err(AppError::Validation(format!(
+                "Meeting {} has been cancelled",
+                meeting_id
+            )));
+        }
+
+        let occurrence = Self::expand(&meeting, starts_at, starts_at + Duration::seconds(1))?
+            .into_iter()
+            .find(|o| o.starts_at == starts_at)
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Meeting {} has no occurrence starting at {}",
+                    meeting_id, starts_at
+                ))
+            })?;
+
+        self.materialize_occurrence(&meeting, &occurrence).await
+    }
+
+    async fn collect_due_reminders(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<DueReminder>, AppError> {
+        let mut due = Vec::new();
+
+        for meeting in self.repo.list_active().await? {
+            if meeting.reminder_minutes <= 0 {
+                continue;
+            }
+
+            let horizon = now + Duration::minutes(meeting.reminder_minutes as i64);
+            let upcoming = match Self::expand(&meeting, now, horizon) {
+                Ok(occurrences) => occurrences,
+                Err(e) => {
+                    eprintln!("Skipping reminders for meeting {}: {}", meeting.id, e);
+                    continue;
+                }
+            };
+
+            for occurrence in upcoming.into_iter().filter(|o| o.starts_at > now) {
+                // One failing meeting must not hold up everyone else's reminders
+                match self.take_due_reminder(&meeting, &occurrence).await {
+                    Ok(Some(reminder)) => due.push(reminder),
+                    Ok(None) => {}
+                    Err(e) => eprintln!(
+                        "Failed to prepare reminder for meeting {} at {}: {}",
+                        meeting.id, occurrence.starts_at, e
+                    ),
+                }
+            }
+        }
+
+        Ok(due)
+    }
+
+    async fn record_reminder_delivery(
+        &self,
+        meeting_id: i32,
+        starts_at: DateTime<Utc>,
+        user_id: i32,
+    ) -> Result<(), AppError> {
+        self.repo
+            .record_reminder_delivery(meeting_id, starts_at.naive_utc(), user_id)
+            .await
+    }
+
+    async fn export_meeting(&self, meeting_id: i32, user_id: i32) -> Result<String, AppError> {
+        let meeting = self.get_visible_meeting(meeting_id, user_id).await?;
+        Ok(ical::render_calendar(&meeting.title.clone(), &[meeting]))
+    }
+
+    async fn calendar_feed(&self, token: &str) -> Result<String, AppError> {
+        let user_id = self
+            .repo
+            .get_feed_user_id(token)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Calendar feed not found".into()))?;
+
+        let meetings = self.repo.list_for_user(user_id).await?;
+        Ok(ical::render_calendar("VibeCall meetings", &meetings))
+    }
+
+    async fn rotate_feed_token(&self, user_id: i32) -> Result<String, AppError> {
+        let token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(FEED_TOKEN_LENGTH)
+            .map(char::from)
+            .collect();
+
+        self.repo.upsert_feed_token(user_id, &token).await?;
+        Ok(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        meetings::SqliteMeetingRepository,
+        rooms::{RoomServiceImpl, SqliteRoomRepository},
+        shared::test_db,
+        users::{SqliteUserRepository, UserServiceImpl},
+    };
+
+    #[tokio::test]
+    async fn test_reminders_repeat_until_every_recipient_received_them() {
+        let pool = test_db::memory_pool().await;
+        let repo = Arc::new(SqliteMeetingRepository::new(pool.clone()));
+        let service = MeetingServiceImpl::new(
+            repo.clone(),
+            Arc::new(RoomServiceImpl::new(Arc::new(SqliteRoomRepository::new(
+                pool.clone(),
+            )))),
+            Arc::new(UserServiceImpl::new(Arc::new(SqliteUserRepository::new(
+                pool.clone(),
+            )))),
+        );
+
+        let now = Utc::now();
+        let starts_at = (now + Duration::minutes(5)).naive_utc();
+        let meeting = repo
+            .create(
+                "Standup".to_string(),
+                None,
+                1,
+                starts_at,
+                starts_at + Duration::minutes(15),
+                "UTC".to_string(),
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        repo.add_attendee(meeting.id, 2).await.unwrap();
+
+        let due = service.collect_due_reminders(now).await.unwrap();
+        assert_eq!(due.len(), 1);
+        let mut recipients = due[0].recipients.clone();
+        recipients.sort();
+        assert_eq!(recipients, vec![1, 2]);
+
+        // User 2 was offline, so only the organizer's delivery is recorded
+        service
+            .record_reminder_delivery(meeting.id, due[0].starts_at, 1)
+            .await
+            .unwrap();
+        let due = service.collect_due_reminders(now).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].recipients, vec![2]);
+
+        service
+            .record_reminder_delivery(meeting.id, due[0].starts_at, 2)
+            .await
+            .unwrap();
+        assert!(service.collect_due_reminders(now).await.unwrap().is_empty());
+        let occurrence = repo
+            .get_occurrence_room(meeting.id, due[0].starts_at.naive_utc())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(occurrence.reminder_sent_at.is_some());
+    }
+}
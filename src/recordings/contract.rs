@@ -0,0 +1,36 @@
+use actix_multipart::form::{MultipartForm, tempfile::TempFile};
+use serde::{Deserialize, Serialize};
+
+use crate::recordings::entities::Recording;
+
+#[derive(Deserialize)]
+pub struct StartRecording {
+    pub call_id: i32,
+    /// The `MediaRecorder` mime type, e.g. `video/webm;codecs=vp8,opus`.
+    pub mime_type: String,
+}
+
+#[derive(MultipartForm)]
+pub struct RecordingChunkUpload {
+    #[multipart(limit = "16MB")]
+    pub chunk: TempFile,
+}
+
+#[derive(Deserialize)]
+pub struct CompleteRecording {
+    pub total_chunks: i32,
+    pub duration_secs: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct RecordingListParams {
+    pub call_id: i32,
+}
+
+/// Where an upload stands, so a client can resume from `next_chunk`.
+#[derive(Debug, Serialize)]
+pub struct RecordingUploadStatus {
+    pub recording: Recording,
+    pub received_chunks: Vec<i32>,
+    pub next_chunk: i32,
+}
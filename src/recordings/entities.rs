@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingStatus {
+    /// Chunks are still arriving; the recording cannot be downloaded yet.
+    Uploading,
+    Ready,
+}
+
+/// The single recording of a call. `file_name` is only set once the chunks
+/// have been assembled and is never exposed to clients.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Recording {
+    pub id: i32,
+    pub call_id: i32,
+    pub recorded_by: i32,
+    pub status: RecordingStatus,
+    pub mime_type: String,
+    #[serde(skip)]
+    pub file_name: Option<String>,
+    pub size_bytes: i64,
+    pub duration_secs: Option<i32>,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RecordingChunk {
+    pub recording_id: i32,
+    pub chunk_index: i32,
+    pub file_name: String,
+    pub size_bytes: i64,
+    pub uploaded_at: String,
+}
+
+/// The outcome of removing a batch of recordings. Failed ones are logged and
+/// left in place to be retried.
+#[derive(Debug, Default)]
+pub struct PurgeReport {
+    pub removed: usize,
+    pub failures: usize,
+}
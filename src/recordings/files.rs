@@ -0,0 +1,99 @@
+use crate::shared::response::AppError;
+
+/// Mime types `MediaRecorder` produces in the browsers we support, with the
+/// extension the assembled recording is stored under.
+const SUPPORTED_TYPES: [(&str, &str); 4] = [
+    ("video/webm", "webm"),
+    ("audio/webm", "weba"),
+    ("video/mp4", "mp4"),
+    ("audio/ogg", "ogg"),
+];
+
+pub const MAX_CHUNKS: i32 = 10_000;
+
+/// Strips codec parameters and checks the type is one we can store.
+pub fn normalize_mime_type(mime_type: &str) -> Result<String, AppError> {
+    let base = mime_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+
+    if SUPPORTED_TYPES
+        .iter()
+        .any(|(supported, _)| *supported == base)
+    {
+        Ok(base)
+    } else {
+        let names: Vec<&str> = SUPPORTED_TYPES.iter().map(|(name, _)| *name).collect();
+        Err(AppError::Validation(format!(
+            "Unsupported recording type '{}'. Supported types are: {}",
+            mime_type,
+            names.join(", ")
+        )))
+    }
+}
+
+pub fn extension_for(mime_type: &str) -> &'static str {
+    SUPPORTED_TYPES
+        .iter()
+        .find(|(supported, _)| *supported == mime_type)
+        .map(|(_, extension)| *extension)
+        .unwrap_or("bin")
+}
+
+pub fn chunk_file_name(call_id: i32, recording_id: i32, chunk_index: i32) -> String {
+    format!(
+        "recordings/{}/{}-chunk-{:05}.part",
+        call_id, recording_id, chunk_index
+    )
+}
+
+pub fn recording_file_name(call_id: i32, recording_id: i32, mime_type: &str) -> String {
+    format!(
+        "recordings/{}/recording-{}.{}",
+        call_id,
+        recording_id,
+        extension_for(mime_type)
+    )
+}
+
+/// The lowest chunk index not received yet; `received` must be sorted.
+pub fn next_missing_chunk(received: &[i32]) -> i32 {
+    let mut next = 0;
+    for index in received {
+        if *index == next {
+            next += 1;
+        } else if *index > next {
+            break;
+        }
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mime_types_and_resume_point() {
+        assert_eq!(
+            normalize_mime_type("video/webm;codecs=vp8,opus").unwrap(),
+            "video/webm"
+        );
+        assert_eq!(extension_for("audio/webm"), "weba");
+        assert!(normalize_mime_type("image/png").is_err());
+
+        assert_eq!(next_missing_chunk(&[]), 0);
+        assert_eq!(next_missing_chunk(&[0, 1, 2]), 3);
+        assert_eq!(next_missing_chunk(&[0, 1, 3, 4]), 2);
+        assert_eq!(next_missing_chunk(&[1, 2]), 0);
+
+        assert_eq!(chunk_file_name(7, 3, 12), "recordings/7/3-chunk-00012.part");
+        assert_eq!(
+            recording_file_name(7, 3, "video/webm"),
+            "recordings/7/recording-3.webm"
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use actix_files::NamedFile;
+use actix_identity::Identity;
+use actix_multipart::form::MultipartForm;
+use actix_web::{
+    HttpRequest, HttpResponse, Result as ActixResult, delete, get,
+    http::header::{ContentDisposition, DispositionParam, DispositionType},
+    mime, post, put, web,
+};
+
+use crate::{
+    calls::SignalingServer,
+    recordings::{
+        RecordingService,
+        contract::{CompleteRecording, RecordingChunkUpload, RecordingListParams, StartRecording},
+        files,
+    },
+    shared::response::{AppError, respond_ok},
+};
+
+/// Uploads are only taken while the call is being recorded, so nothing
+/// captured after the recording was stopped can be added to it. Clients
+/// finish uploading before they stop the recording.
+fn ensure_still_recording(
+    signaling_server: &SignalingServer,
+    call_id: i32,
+) -> Result<(), AppError> {
+    if signaling_server.is_recording(call_id) {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!(
+            "The recording on call {} has stopped, so it no longer takes uploads",
+            call_id
+        )))
+    }
+}
+
+#[post("")]
+pub async fn start_recording(
+    payload: web::Json<StartRecording>,
+    identity: Identity,
+    recording_service: web::Data<Arc<dyn RecordingService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    // Everyone on the call has to be asked before anything is captured
+    if !signaling_server.is_recording(payload.call_id) {
+        return Err(AppError::Validation(
+            "Start the recording on the call and wait for everyone to answer before uploading"
+                .into(),
+        )
+        .into());
+    }
+
+    let status = recording_service
+        .start_recording(user_id, payload.call_id, &payload.mime_type)
+        .await?;
+    respond_ok(status)
+}
+
+#[get("")]
+pub async fn list_recordings(
+    query: web::Query<RecordingListParams>,
+    identity: Identity,
+    recording_service: web::Data<Arc<dyn RecordingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let recordings = recording_service
+        .list_recordings(query.call_id, user_id)
+        .await?;
+    respond_ok(recordings)
+}
+
+#[get("/{recording_id}/upload")]
+pub async fn upload_status(
+    recording_id: web::Path<i32>,
+    identity: Identity,
+    recording_service: web::Data<Arc<dyn RecordingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let status = recording_service
+        .upload_status(recording_id.into_inner(), user_id)
+        .await?;
+    respond_ok(status)
+}
+
+#[put("/{recording_id}/chunks/{chunk_index}")]
+pub async fn upload_chunk(
+    path: web::Path<(i32, i32)>,
+    MultipartForm(upload): MultipartForm<RecordingChunkUpload>,
+    identity: Identity,
+    recording_service: web::Data<Arc<dyn RecordingService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+    let (recording_id, chunk_index) = path.into_inner();
+
+    let recording = recording_service.get_upload(recording_id, user_id).await?;
+    ensure_still_recording(&signaling_server, recording.call_id)?;
+
+    let status = recording_service
+        .upload_chunk(recording_id, user_id, chunk_index, upload.chunk.file.path())
+        .await?;
+    respond_ok(status)
+}
+
+#[post("/{recording_id}/complete")]
+pub async fn complete_recording(
+    recording_id: web::Path<i32>,
+    payload: web::Json<CompleteRecording>,
+    identity: Identity,
+    recording_service: web::Data<Arc<dyn RecordingService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+    let recording_id = recording_id.into_inner();
+
+    let recording = recording_service.get_upload(recording_id, user_id).await?;
+    ensure_still_recording(&signaling_server, recording.call_id)?;
+
+    let recording = recording_service
+        .complete_recording(
+            recording_id,
+            user_id,
+            payload.total_chunks,
+            payload.duration_secs,
+        )
+        .await?;
+    respond_ok(recording)
+}
+
+#[get("/{recording_id}/download")]
+pub async fn download_recording(
+    req: HttpRequest,
+    recording_id: web::Path<i32>,
+    identity: Identity,
+    recording_service: web::Data<Arc<dyn RecordingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let (recording, path) = recording_service
+        .download_recording(recording_id.into_inner(), user_id)
+        .await?;
+
+    let mime_type = recording
+        .mime_type
+        .parse::<mime::Mime>()
+        .unwrap_or(mime::APPLICATION_OCTET_STREAM);
+    // Streamed from disk, with range support so players can seek
+    let file = NamedFile::open_async(&path)
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("File read error: {}", e)))?
+        .set_content_type(mime_type)
+        .set_content_disposition(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "call-{}-recording.{}",
+                recording.call_id,
+                files::extension_for(&recording.mime_type)
+            ))],
+        });
+
+    Ok(file.into_response(&req))
+}
+
+#[delete("/{recording_id}")]
+pub async fn delete_recording(
+    recording_id: web::Path<i32>,
+    identity: Identity,
+    recording_service: web::Data<Arc<dyn RecordingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    recording_service
+        .delete_recording(recording_id.into_inner(), user_id)
+        .await?;
+    respond_ok("Recording deleted successfully")
+}
@@ -0,0 +1,12 @@
+pub mod contract;
+pub mod entities;
+pub mod files;
+pub mod handlers;
+pub mod repository;
+pub mod retention;
+pub mod routes;
+pub mod service;
+
+pub use entities::{Recording, RecordingStatus};
+pub use repository::{RecordingRepository, SqliteRecordingRepository};
+pub use service::{RecordingService, RecordingServiceImpl};
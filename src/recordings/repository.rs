@@ -0,0 +1,231 @@
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+
+use crate::{
+    recordings::entities::{Recording, RecordingChunk},
+    shared::response::AppError,
+};
+
+#[async_trait]
+pub trait RecordingRepository {
+    async fn create_recording(
+        &self,
+        call_id: i32,
+        recorded_by: i32,
+        mime_type: &str,
+    ) -> Result<Recording, AppError>;
+
+    async fn get_recording(&self, recording_id: i32) -> Result<Option<Recording>, AppError>;
+
+    async fn get_recording_by_call(&self, call_id: i32) -> Result<Option<Recording>, AppError>;
+
+    /// Chunks received so far, in index order.
+    async fn list_chunks(&self, recording_id: i32) -> Result<Vec<RecordingChunk>, AppError>;
+
+    /// Records a chunk, replacing any earlier upload of the same index.
+    async fn save_chunk(
+        &self,
+        recording_id: i32,
+        chunk_index: i32,
+        file_name: &str,
+        size_bytes: i64,
+    ) -> Result<(), AppError>;
+
+    /// Marks the recording ready and forgets its chunks.
+    async fn mark_ready(
+        &self,
+        recording_id: i32,
+        file_name: &str,
+        size_bytes: i64,
+        duration_secs: Option<i32>,
+    ) -> Result<Recording, AppError>;
+
+    async fn delete_recording(&self, recording_id: i32) -> Result<(), AppError>;
+
+    /// Ready recordings completed more than `retention_days` ago, and uploads
+    /// nobody has finished within `stale_upload_hours`.
+    async fn list_expired(
+        &self,
+        retention_days: i64,
+        stale_upload_hours: i64,
+    ) -> Result<Vec<Recording>, AppError>;
+
+    async fn list_room_recordings(&self, room_id: &str) -> Result<Vec<Recording>, AppError>;
+}
+
+pub struct SqliteRecordingRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRecordingRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RecordingRepository for SqliteRecordingRepository {
+    async fn create_recording(
+        &self,
+        call_id: i32,
+        recorded_by: i32,
+        mime_type: &str,
+    ) -> Result<Recording, AppError> {
+        let recording = sqlx::query_as::<_, Recording>(
+            r#"
+            INSERT INTO call_recordings (call_id, recorded_by, mime_type)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(call_id)
+        .bind(recorded_by)
+        .bind(mime_type)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(recording)
+    }
+
+    async fn get_recording(&self, recording_id: i32) -> Result<Option<Recording>, AppError> {
+        let recording =
+            sqlx::query_as::<_, Recording>("SELECT * FROM call_recordings WHERE id = $1")
+                .bind(recording_id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(recording)
+    }
+
+    async fn get_recording_by_call(&self, call_id: i32) -> Result<Option<Recording>, AppError> {
+        let recording =
+            sqlx::query_as::<_, Recording>("SELECT * FROM call_recordings WHERE call_id = $1")
+                .bind(call_id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(recording)
+    }
+
+    async fn list_chunks(&self, recording_id: i32) -> Result<Vec<RecordingChunk>, AppError> {
+        let chunks = sqlx::query_as::<_, RecordingChunk>(
+            "SELECT * FROM call_recording_chunks WHERE recording_id = $1 ORDER BY chunk_index",
+        )
+        .bind(recording_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(chunks)
+    }
+
+    async fn save_chunk(
+        &self,
+        recording_id: i32,
+        chunk_index: i32,
+        file_name: &str,
+        size_bytes: i64,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO call_recording_chunks (recording_id, chunk_index, file_name, size_bytes)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (recording_id, chunk_index) DO UPDATE SET
+                file_name = excluded.file_name,
+                size_bytes = excluded.size_bytes,
+                uploaded_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(recording_id)
+        .bind(chunk_index)
+        .bind(file_name)
+        .bind(size_bytes)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_ready(
+        &self,
+        recording_id: i32,
+        file_name: &str,
+        size_bytes: i64,
+        duration_secs: Option<i32>,
+    ) -> Result<Recording, AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        let recording = sqlx::query_as::<_, Recording>(
+            r#"
+            UPDATE call_recordings
+            SET status = 'ready',
+                file_name = $2,
+                size_bytes = $3,
+                duration_secs = $4,
+                completed_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(recording_id)
+        .bind(file_name)
+        .bind(size_bytes)
+        .bind(duration_secs)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM call_recording_chunks WHERE recording_id = $1")
+            .bind(recording_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(recording)
+    }
+
+    async fn delete_recording(&self, recording_id: i32) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM call_recordings WHERE id = $1")
+            .bind(recording_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_expired(
+        &self,
+        retention_days: i64,
+        stale_upload_hours: i64,
+    ) -> Result<Vec<Recording>, AppError> {
+        let recordings = sqlx::query_as::<_, Recording>(
+            r#"
+            SELECT * FROM call_recordings
+            WHERE (status = 'ready' AND completed_at <= datetime('now', $1))
+                OR (status = 'uploading' AND created_at <= datetime('now', $2))
+            ORDER BY id
+            "#,
+        )
+        .bind(format!("-{} days", retention_days))
+        .bind(format!("-{} hours", stale_upload_hours))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(recordings)
+    }
+
+    async fn list_room_recordings(&self, room_id: &str) -> Result<Vec<Recording>, AppError> {
+        let recordings = sqlx::query_as::<_, Recording>(
+            r#"
+            SELECT r.* FROM call_recordings r
+            JOIN calls c ON c.id = r.call_id
+            WHERE c.room_id = $1
+            ORDER BY r.id
+            "#,
+        )
+        .bind(room_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(recordings)
+    }
+}
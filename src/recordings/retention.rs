@@ -0,0 +1,55 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::recordings::RecordingService;
+
+const RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub const DEFAULT_RETENTION_DAYS: i64 = 30;
+const MIN_RETENTION_DAYS: i64 = 1;
+const MAX_RETENTION_DAYS: i64 = 3650;
+
+/// Uploads nobody completed within this long are treated as abandoned.
+pub const STALE_UPLOAD_HOURS: i64 = 24;
+
+/// How many days finished recordings are kept, read from
+/// `RECORDING_RETENTION_DAYS`.
+pub fn retention_days_from_env() -> i64 {
+    let Ok(value) = std::env::var("RECORDING_RETENTION_DAYS") else {
+        return DEFAULT_RETENTION_DAYS;
+    };
+
+    match value.trim().parse::<i64>() {
+        Ok(days) if (MIN_RETENTION_DAYS..=MAX_RETENTION_DAYS).contains(&days) => days,
+        _ => {
+            eprintln!(
+                "Ignoring RECORDING_RETENTION_DAYS={}: expected {} to {} days",
+                value, MIN_RETENTION_DAYS, MAX_RETENTION_DAYS
+            );
+            DEFAULT_RETENTION_DAYS
+        }
+    }
+}
+
+/// Deletes recordings past the retention period, and abandoned uploads, once
+/// an hour.
+pub fn spawn_retention_task(recording_service: Arc<dyn RecordingService>, retention_days: i64) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(RETENTION_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            match recording_service
+                .purge_expired(retention_days, STALE_UPLOAD_HOURS)
+                .await
+            {
+                Ok(report) if report.removed == 0 && report.failures == 0 => {}
+                Ok(report) => println!(
+                    "Purged {} expired call recordings, {} failed",
+                    report.removed, report.failures
+                ),
+                Err(e) => eprintln!("Failed to purge expired recordings: {}", e),
+            }
+        }
+    });
+}
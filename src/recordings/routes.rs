@@ -0,0 +1,17 @@
+use actix_web::{middleware, web};
+
+use crate::{infrastructure::middlewares::auth_middleware, recordings::handlers};
+
+pub fn recording_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/recordings")
+            .wrap(middleware::from_fn(auth_middleware::auth))
+            .service(handlers::start_recording)
+            .service(handlers::list_recordings)
+            .service(handlers::upload_status)
+            .service(handlers::upload_chunk)
+            .service(handlers::complete_recording)
+            .service(handlers::download_recording)
+            .service(handlers::delete_recording),
+    );
+}
@@ -0,0 +1,464 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+
+use crate::{
+    calls::{
+        CallService,
+        entities::{Call, CallStatus},
+    },
+    recordings::{
+        contract::RecordingUploadStatus,
+        entities::{PurgeReport, Recording, RecordingStatus},
+        files,
+        repository::RecordingRepository,
+    },
+    rooms::RoomService,
+    shared::{file_service::FileService, response::AppError},
+};
+
+#[async_trait]
+pub trait RecordingService: Send + Sync {
+    /// Starts recording an active call, or resumes the caller's own
+    /// unfinished upload for it. A call only ever has one recording.
+    async fn start_recording(
+        &self,
+        user_id: i32,
+        call_id: i32,
+        mime_type: &str,
+    ) -> Result<RecordingUploadStatus, AppError>;
+
+    async fn upload_status(
+        &self,
+        recording_id: i32,
+        user_id: i32,
+    ) -> Result<RecordingUploadStatus, AppError>;
+
+    /// Loads a recording that `user_id` is still uploading.
+    async fn get_upload(&self, recording_id: i32, user_id: i32) -> Result<Recording, AppError>;
+
+    /// Stores one chunk; chunks may arrive in any order and re-uploading an
+    /// index replaces it.
+    async fn upload_chunk(
+        &self,
+        recording_id: i32,
+        user_id: i32,
+        chunk_index: i32,
+        uploaded_path: &Path,
+    ) -> Result<RecordingUploadStatus, AppError>;
+
+    /// Joins chunks `0..total_chunks` into the final recording.
+    async fn complete_recording(
+        &self,
+        recording_id: i32,
+        user_id: i32,
+        total_chunks: i32,
+        duration_secs: Option<i32>,
+    ) -> Result<Recording, AppError>;
+
+    /// Visible to the call's participants and the room owner.
+    async fn list_recordings(&self, call_id: i32, user_id: i32)
+    -> Result<Vec<Recording>, AppError>;
+
+    /// Returns where the finished recording is stored, so the handler can
+    /// stream it instead of loading it into memory.
+    async fn download_recording(
+        &self,
+        recording_id: i32,
+        user_id: i32,
+    ) -> Result<(Recording, PathBuf), AppError>;
+
+    async fn delete_recording(&self, recording_id: i32, user_id: i32) -> Result<(), AppError>;
+
+    /// Removes recordings past the retention period and abandoned uploads.
+    async fn purge_expired(
+        &self,
+        retention_days: i64,
+        stale_upload_hours: i64,
+    ) -> Result<PurgeReport, AppError>;
+
+    /// Removes every recording made in the room, files included. Purging a
+    /// room deletes its calls, which would only drop the recording rows.
+    async fn purge_room_recordings(&self, room_id: &str) -> Result<PurgeReport, AppError>;
+}
+
+pub struct RecordingServiceImpl {
+    repo: Arc<dyn RecordingRepository + Send + Sync>,
+    call_service: Arc<dyn CallService>,
+    room_service: Arc<dyn RoomService + Send + Sync>,
+    file_service: Arc<dyn FileService>,
+}
+
+impl RecordingServiceImpl {
+    pub fn new(
+        repo: Arc<dyn RecordingRepository + Send + Sync>,
+        call_service: Arc<dyn CallService>,
+        room_service: Arc<dyn RoomService + Send + Sync>,
+        file_service: Arc<dyn FileService>,
+    ) -> Self {
+        Self {
+            repo,
+            call_service,
+            room_service,
+            file_service,
+        }
+    }
+
+    async fn load_call(&self, call_id: i32) -> Result<Call, AppError> {
+        self.call_service
+            .get_call_by_id(call_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Call {} not found", call_id)))
+    }
+
+    async fn load_recording(&self, recording_id: i32) -> Result<Recording, AppError> {
+        self.repo
+            .get_recording(recording_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Recording {} not found", recording_id)))
+    }
+
+    async fn ensure_can_access(&self, call: &Call, user_id: i32) -> Result<(), AppError> {
+        if self
+            .call_service
+            .is_user_participant(call.id, user_id)
+            .await?
+            || self
+                .room_service
+                .is_user_owner(&call.room_id, user_id)
+                .await?
+        {
+            return Ok(());
+        }
+
+        Err(AppError::Unauthorized(
+            "Only call participants and the room owner can access call recordings".into(),
+        ))
+    }
+
+    /// Loads a recording that `user_id` is still uploading.
+    async fn own_upload(&self, recording_id: i32, user_id: i32) -> Result<Recording, AppError> {
+        let recording = self.load_recording(recording_id).await?;
+
+        if recording.recorded_by != user_id {
+            return Err(AppError::Unauthorized(
+                "Only the person recording can upload to this recording".into(),
+            ));
+        }
+        if recording.status != RecordingStatus::Uploading {
+            return Err(AppError::Validation(format!(
+                "Recording {} has already been completed",
+                recording_id
+            )));
+        }
+        Ok(recording)
+    }
+
+    async fn status_of(&self, recording: Recording) -> Result<RecordingUploadStatus, AppError> {
+        let received_chunks: Vec<i32> = self
+            .repo
+            .list_chunks(recording.id)
+            .await?
+            .into_iter()
+            .map(|chunk| chunk.chunk_index)
+            .collect();
+
+        Ok(RecordingUploadStatus {
+            next_chunk: files::next_missing_chunk(&received_chunks),
+            received_chunks,
+            recording,
+        })
+    }
+
+    /// Deletes the recording and whatever files it has, logging files that
+    /// could not be removed.
+    async fn remove(&self, recording: &Recording) -> Result<(), AppError> {
+        let mut file_names: Vec<String> = self
+            .repo
+            .list_chunks(recording.id)
+            .await?
+            .into_iter()
+            .map(|chunk| chunk.file_name)
+            .collect();
+        file_names.extend(recording.file_name.clone());
+
+        self.repo.delete_recording(recording.id).await?;
+
+        for file_name in file_names {
+            if let Err(e) = self.file_service.delete_file(&file_name).await {
+                eprintln!("Failed to delete recording file {}: {}", file_name, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes each recording in turn, so one failure does not keep the rest.
+    async fn remove_all(&self, recordings: &[Recording]) -> PurgeReport {
+        let mut report = PurgeReport::default();
+
+        for recording in recordings {
+            match self.remove(recording).await {
+                Ok(()) => report.removed += 1,
+                Err(e) => {
+                    eprintln!("Failed to remove recording {}: {}", recording.id, e);
+                    report.failures += 1;
+                }
+            }
+        }
+        report
+    }
+}
+
+#[async_trait]
+impl RecordingService for RecordingServiceImpl {
+    async fn start_recording(
+        &self,
+        user_id: i32,
+        call_id: i32,
+        mime_type: &str,
+    ) -> Result<RecordingUploadStatus, AppError> {
+        let mime_type = files::normalize_mime_type(mime_type)?;
+        let call = self.load_call(call_id).await?;
+
+        if call.status != CallStatus::Active {
+            return Err(AppError::Validation(format!(
+                "Call {} is not active, so it cannot be recorded",
+                call_id
+            )));
+        }
+        if !self
+            .call_service
+            .is_user_participant(call_id, user_id)
+            .await?
+        {
+            return Err(AppError::Unauthorized(
+                "Only call participants can record a call".into(),
+            ));
+        }
+
+        let recording = match self.repo.get_recording_by_call(call_id).await? {
+            Some(existing)
+                if existing.recorded_by == user_id
+                    && existing.status == RecordingStatus::Uploading =>
+            {
+                existing
+            }
+            Some(_) => {
+                return Err(AppError::Validation(format!(
+                    "Call {} already has a recording",
+                    call_id
+                )));
+            }
+            None => {
+                self.repo
+                    .create_recording(call_id, user_id, &mime_type)
+                    .await?
+            }
+        };
+
+        self.status_of(recording).await
+    }
+
+    async fn upload_status(
+        &self,
+        recording_id: i32,
+        user_id: i32,
+    ) -> Result<RecordingUploadStatus, AppError> {
+        let recording = self.own_upload(recording_id, user_id).await?;
+        self.status_of(recording).await
+    }
+
+    async fn get_upload(&self, recording_id: i32, user_id: i32) -> Result<Recording, AppError> {
+        self.own_upload(recording_id, user_id).await
+    }
+
+    async fn upload_chunk(
+        &self,
+        recording_id: i32,
+        user_id: i32,
+        chunk_index: i32,
+        uploaded_path: &Path,
+    ) -> Result<RecordingUploadStatus, AppError> {
+        let recording = self.own_upload(recording_id, user_id).await?;
+
+        if !(0..files::MAX_CHUNKS).contains(&chunk_index) {
+            return Err(AppError::Validation(format!(
+                "Chunk index must be between 0 and {}",
+                files::MAX_CHUNKS - 1
+            )));
+        }
+
+        let chunk = tokio::fs::read(uploaded_path)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("File read error: {}", e)))?;
+        if chunk.is_empty() {
+            return Err(AppError::Validation("Recording chunk is empty".into()));
+        }
+
+        let size_bytes = chunk.len() as i64;
+        let file_name = files::chunk_file_name(recording.call_id, recording.id, chunk_index);
+        self.file_service
+            .save_file(&file_name, chunk)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("File save error: {}", e)))?;
+
+        self.repo
+            .save_chunk(recording.id, chunk_index, &file_name, size_bytes)
+            .await?;
+
+        self.status_of(recording).await
+    }
+
+    async fn complete_recording(
+        &self,
+        recording_id: i32,
+        user_id: i32,
+        total_chunks: i32,
+        duration_secs: Option<i32>,
+    ) -> Result<Recording, AppError> {
+        let recording = self.own_upload(recording_id, user_id).await?;
+
+        if !(1..=files::MAX_CHUNKS).contains(&total_chunks) {
+            return Err(AppError::Validation(format!(
+                "total_chunks must be between 1 and {}",
+                files::MAX_CHUNKS
+            )));
+        }
+        if duration_secs.is_some_and(|secs| secs < 0) {
+            return Err(AppError::Validation(
+                "duration_secs cannot be negative".into(),
+            ));
+        }
+
+        let chunks = self.repo.list_chunks(recording.id).await?;
+        let indexes: Vec<i32> = chunks.iter().map(|chunk| chunk.chunk_index).collect();
+        let next_missing = files::next_missing_chunk(&indexes);
+        if next_missing < total_chunks {
+            return Err(AppError::Validation(format!(
+                "Chunk {} has not been uploaded yet",
+                next_missing
+            )));
+        }
+
+        let file_name =
+            files::recording_file_name(recording.call_id, recording.id, &recording.mime_type);
+        let file_error = |e| AppError::InternalServerError(format!("File save error: {}", e));
+
+        // Start from an empty file in case an earlier attempt left one behind
+        self.file_service
+            .save_file(&file_name, Vec::new())
+            .await
+            .map_err(file_error)?;
+
+        let mut size_bytes = 0;
+        for chunk in chunks
+            .iter()
+            .filter(|chunk| chunk.chunk_index < total_chunks)
+        {
+            let data = self
+                .file_service
+                .get_file(&chunk.file_name)
+                .await
+                .map_err(file_error)?;
+            size_bytes += data.len() as i64;
+            self.file_service
+                .append_file(&file_name, data)
+                .await
+                .map_err(file_error)?;
+        }
+
+        let recording = match self
+            .repo
+            .mark_ready(recording.id, &file_name, size_bytes, duration_secs)
+            .await
+        {
+            Ok(recording) => recording,
+            Err(e) => {
+                let _ = self.file_service.delete_file(&file_name).await;
+                return Err(e);
+            }
+        };
+
+        // Chunks past `total_chunks` were never part of the recording
+        for chunk in chunks {
+            if let Err(e) = self.file_service.delete_file(&chunk.file_name).await {
+                eprintln!(
+                    "Failed to delete recording chunk {}: {}",
+                    chunk.file_name, e
+                );
+            }
+        }
+
+        Ok(recording)
+    }
+
+    async fn list_recordings(
+        &self,
+        call_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<Recording>, AppError> {
+        let call = self.load_call(call_id).await?;
+        self.ensure_can_access(&call, user_id).await?;
+
+        Ok(self
+            .repo
+            .get_recording_by_call(call_id)
+            .await?
+            .into_iter()
+            .collect())
+    }
+
+    async fn download_recording(
+        &self,
+        recording_id: i32,
+        user_id: i32,
+    ) -> Result<(Recording, PathBuf), AppError> {
+        let recording = self.load_recording(recording_id).await?;
+        let call = self.load_call(recording.call_id).await?;
+        self.ensure_can_access(&call, user_id).await?;
+
+        let Some(file_name) = recording
+            .file_name
+            .clone()
+            .filter(|_| recording.status == RecordingStatus::Ready)
+        else {
+            return Err(AppError::Validation(format!(
+                "Recording {} is still being uploaded",
+                recording_id
+            )));
+        };
+
+        let path = self.file_service.file_path(&file_name);
+        Ok((recording, path))
+    }
+
+    async fn delete_recording(&self, recording_id: i32, user_id: i32) -> Result<(), AppError> {
+        let recording = self.load_recording(recording_id).await?;
+        let call = self.load_call(recording.call_id).await?;
+        self.ensure_can_access(&call, user_id).await?;
+
+        self.remove(&recording).await
+    }
+
+    async fn purge_expired(
+        &self,
+        retention_days: i64,
+        stale_upload_hours: i64,
+    ) -> Result<PurgeReport, AppError> {
+        let expired = self
+            .repo
+            .list_expired(retention_days, stale_upload_hours)
+            .await?;
+
+        Ok(self.remove_all(&expired).await)
+    }
+
+    async fn purge_room_recordings(&self, room_id: &str) -> Result<PurgeReport, AppError> {
+        let recordings = self.repo.list_room_recordings(room_id).await?;
+
+        Ok(self.remove_all(&recordings).await)
+    }
+}
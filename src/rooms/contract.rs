@@ -1,4 +1,7 @@
-use serde::Deserialize;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::rooms::{Room, RoomMemberView};
 
 #[derive(Deserialize)]
 pub struct NewRoom {
@@ -6,15 +9,76 @@ pub struct NewRoom {
     pub created_by: i32,
     pub description: Option<String>,
     pub room_type: String,
+    pub passcode: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RoomSearchParams {
+    pub room_type: Option<String>,
+    pub name: Option<String>,
+    pub member: Option<bool>,
+    pub has_active_call: Option<bool>,
+    pub archived: Option<bool>,
+    pub created_by: Option<i32>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct RoomPage {
+    pub rooms: Vec<Room>,
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Deserialize)]
-pub struct PaginationParams {
+pub struct MemberListParams {
     pub limit: Option<i64>,
-    pub offset: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct RoomMemberPage {
+    pub members: Vec<RoomMemberView>,
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct JoinRoomParams {
+    pub passcode: Option<String>,
 }
 
 #[derive(Deserialize)]
-pub struct UserIdParam {
-    pub user_id: i32,
+pub struct TransferOwnershipParams {
+    pub new_owner_id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct InviteMemberParams {
+    pub invitee_id: i32,
+}
+
+/// Each field names the lowest role allowed to perform the action; omitted
+/// fields keep their current value.
+#[derive(Deserialize)]
+pub struct UpdatePoliciesParams {
+    pub chat: Option<String>,
+    pub unmute: Option<String>,
+    pub screen_share: Option<String>,
+    pub start_call: Option<String>,
+    pub invite: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SetPasscodeParams {
+    pub passcode: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct RotatedPasscode {
+    pub passcode: String,
+    pub join_link: String,
 }
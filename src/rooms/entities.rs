@@ -1,9 +1,9 @@
 use std::{fmt, str::FromStr};
 
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{FromRow, Row, sqlite::SqliteRow};
 
-use crate::shared::response::AppError;
+use crate::{rooms::policy::RoomPolicies, shared::response::AppError, users::User};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Room {
@@ -15,6 +15,55 @@ pub struct Room {
     pub max_participants: i32,
     pub is_active: bool,
     pub created_at: chrono::NaiveDateTime,
+    #[serde(
+        rename = "has_passcode",
+        serialize_with = "serialize_has_passcode",
+        skip_deserializing
+    )]
+    pub passcode_hash: Option<String>,
+    pub archived_at: Option<chrono::NaiveDateTime>,
+    /// Set on one-on-one rooms created for a direct call, identifying the pair.
+    #[serde(skip)]
+    pub direct_key: Option<String>,
+    #[sqlx(flatten)]
+    pub policies: RoomPolicies,
+}
+
+impl Room {
+    pub fn has_passcode(&self) -> bool {
+        self.passcode_hash.is_some()
+    }
+
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
+    /// Order-independent key shared by the direct room of two users.
+    pub fn direct_key_for(user_a: i32, user_b: i32) -> String {
+        format!("{}:{}", user_a.min(user_b), user_a.max(user_b))
+    }
+
+    /// Direct rooms only ever admit the pair they were created for.
+    pub fn allows_direct_member(&self, user_id: i32) -> bool {
+        match &self.direct_key {
+            Some(key) => key.split(':').any(|id| id == user_id.to_string()),
+            None => true,
+        }
+    }
+}
+
+fn serialize_has_passcode<S>(hash: &Option<String>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_bool(hash.is_some())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OwnershipTransfer {
+    pub room_id: String,
+    pub previous_owner_id: i32,
+    pub new_owner_id: i32,
 }
 
 #[derive(Debug, Serialize)]
@@ -35,6 +84,39 @@ pub struct RoomMember {
     pub is_video_enabled: bool,
 }
 
+/// A current member as shown in member panels: public profile fields, the
+/// member's role and media state, and whether they are connected right now.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoomMemberView {
+    pub user_id: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub avatar_url: String,
+    pub role: RoomMemberRole,
+    pub joined_at: chrono::NaiveDateTime,
+    pub is_muted: bool,
+    pub is_video_enabled: bool,
+    pub is_connected: bool,
+}
+
+impl<'r> FromRow<'r, SqliteRow> for RoomMemberView {
+    fn from_row(row: &'r SqliteRow) -> Result<Self, sqlx::Error> {
+        let user = User::from_row(row)?;
+
+        Ok(RoomMemberView {
+            user_id: user.id,
+            first_name: user.first_name,
+            last_name: user.last_name,
+            avatar_url: user.avatar_url,
+            role: row.try_get("role")?,
+            joined_at: row.try_get("joined_at")?,
+            is_muted: row.try_get("is_muted")?,
+            is_video_enabled: row.try_get("is_video_enabled")?,
+            is_connected: false,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, sqlx::Type)]
 #[sqlx(type_name = "text", rename_all = "snake_case")]
 pub enum RoomType {
@@ -46,6 +128,12 @@ pub enum RoomType {
     Instant,
 }
 
+impl RoomType {
+    pub fn supports_passcode(&self) -> bool {
+        matches!(self, RoomType::Meeting | RoomType::Group)
+    }
+}
+
 impl FromStr for RoomType {
     type Err = AppError;
 
@@ -86,6 +174,16 @@ pub enum RoomMemberRole {
     Participant,
 }
 
+impl RoomMemberRole {
+    pub fn rank(&self) -> u8 {
+        match self {
+            Self::Owner => 2,
+            Self::Moderator => 1,
+            Self::Participant => 0,
+        }
+    }
+}
+
 impl fmt::Display for RoomMemberRole {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
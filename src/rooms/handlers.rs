@@ -1,13 +1,20 @@
 use std::sync::Arc;
 
+use actix_identity::Identity;
 use actix_web::{HttpResponse, Result as ActixResult, delete, get, post, web};
 
 use crate::{
+    calls::SignalingServer,
+    recordings::RecordingService,
     rooms::{
         RoomService,
-        contract::{NewRoom, PaginationParams, UserIdParam},
+        contract::{
+            InviteMemberParams, JoinRoomParams, MemberListParams, NewRoom, RoomSearchParams,
+            RotatedPasscode, SetPasscodeParams, TransferOwnershipParams, UpdatePoliciesParams,
+        },
     },
     shared::response::{AppError, respond_ok},
+    users::UserService,
 };
 
 #[get("/{room_id}")]
@@ -36,6 +43,7 @@ pub async fn create_room(
             room_json.room_type.clone(),
             room_json.created_by,
             room_json.description.clone(),
+            room_json.passcode.clone(),
         )
         .await?;
 
@@ -44,56 +52,274 @@ pub async fn create_room(
 
 #[get("")]
 pub async fn list_rooms(
-    query: web::Query<PaginationParams>,
+    query: web::Query<RoomSearchParams>,
+    identity: Option<Identity>,
     room_service: web::Data<Arc<dyn RoomService>>,
 ) -> ActixResult<HttpResponse> {
-    let limit = query.limit.unwrap_or(10);
-    let offset = query.offset.unwrap_or(0);
-    let rooms = room_service.list_rooms(limit, offset).await?;
-    respond_ok(rooms)
+    let viewer_id = identity
+        .and_then(|id| id.id().ok())
+        .and_then(|id_str| id_str.parse::<i32>().ok());
+
+    let page = room_service
+        .list_rooms(viewer_id, query.into_inner())
+        .await?;
+    respond_ok(page)
 }
 
 #[delete("/{room_id}")]
 pub async fn delete_room(
     room_id: web::Path<String>,
-    payload: web::Json<UserIdParam>,
+    identity: Identity,
+    room_service: web::Data<Arc<dyn RoomService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let room_id = room_id.into_inner();
+    room_service.delete_room(&room_id, user_id).await?;
+    respond_ok("Room archived successfully")
+}
+
+#[post("/{room_id}/restore")]
+pub async fn restore_room(
+    room_id: web::Path<String>,
+    identity: Identity,
     room_service: web::Data<Arc<dyn RoomService>>,
 ) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let room_id = room_id.into_inner();
+    let room = room_service.restore_room(&room_id, user_id).await?;
+    respond_ok(room)
+}
+
+#[delete("/{room_id}/purge")]
+pub async fn purge_room(
+    room_id: web::Path<String>,
+    identity: Identity,
+    user_service: web::Data<Arc<dyn UserService>>,
+    room_service: web::Data<Arc<dyn RoomService>>,
+    recording_service: web::Data<Arc<dyn RecordingService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    if !user_service.is_admin(user_id).await? {
+        return Err(AppError::Unauthorized("Only administrators can purge rooms".into()).into());
+    }
+
     let room_id = room_id.into_inner();
-    room_service.delete_room(&room_id, payload.user_id).await?;
-    respond_ok("Room deleted successfully")
+    // Recording files live outside the database, so they go first; purging
+    // the room would orphan any that are left
+    let report = recording_service.purge_room_recordings(&room_id).await?;
+    if report.failures > 0 {
+        return Err(AppError::InternalServerError(format!(
+            "{} recordings of room {} could not be removed; try again",
+            report.failures, room_id
+        ))
+        .into());
+    }
+    room_service.purge_room(&room_id).await?;
+    respond_ok("Room purged successfully")
 }
 
 #[post("/{room_id}/join")]
 pub async fn join_room(
     room_id: web::Path<String>,
-    payload: web::Json<UserIdParam>,
+    payload: web::Json<JoinRoomParams>,
+    identity: Identity,
     room_service: web::Data<Arc<dyn RoomService>>,
 ) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
     let room_id = room_id.into_inner();
-    room_service.join_room(&room_id, payload.user_id).await?;
+    room_service
+        .join_room(&room_id, user_id, payload.passcode.as_deref())
+        .await?;
     respond_ok("Joined room successfully")
 }
 
+#[post("/{room_id}/passcode")]
+pub async fn set_room_passcode(
+    room_id: web::Path<String>,
+    payload: web::Json<SetPasscodeParams>,
+    identity: Identity,
+    room_service: web::Data<Arc<dyn RoomService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let room_id = room_id.into_inner();
+    let payload = payload.into_inner();
+    let message = if payload.passcode.is_some() {
+        "Room passcode updated successfully"
+    } else {
+        "Room passcode removed successfully"
+    };
+
+    room_service
+        .set_passcode(&room_id, user_id, payload.passcode)
+        .await?;
+    respond_ok(message)
+}
+
+#[post("/{room_id}/passcode/rotate")]
+pub async fn rotate_room_passcode(
+    room_id: web::Path<String>,
+    identity: Identity,
+    room_service: web::Data<Arc<dyn RoomService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let room_id = room_id.into_inner();
+    let passcode = room_service.rotate_passcode(&room_id, user_id).await?;
+
+    let base_url =
+        std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:8085".to_string());
+    let join_link = format!(
+        "{}/turn-credentials?room_id={}&passcode={}",
+        base_url.trim_end_matches('/'),
+        room_id,
+        passcode
+    );
+
+    respond_ok(RotatedPasscode {
+        passcode,
+        join_link,
+    })
+}
+
+#[post("/{room_id}/policies")]
+pub async fn update_room_policies(
+    room_id: web::Path<String>,
+    payload: web::Json<UpdatePoliciesParams>,
+    identity: Identity,
+    room_service: web::Data<Arc<dyn RoomService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let room_id = room_id.into_inner();
+    let payload = payload.into_inner();
+    let room = room_service
+        .update_policies(&room_id, user_id, payload)
+        .await?;
+
+    signaling_server.notify_policies_updated(&room);
+    signaling_server
+        .record_moderator_action(&room_id, user_id, None, "policies_updated")
+        .await;
+    respond_ok(room)
+}
+
+#[post("/{room_id}/invite")]
+pub async fn invite_member(
+    room_id: web::Path<String>,
+    payload: web::Json<InviteMemberParams>,
+    identity: Identity,
+    room_service: web::Data<Arc<dyn RoomService>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let room_id = room_id.into_inner();
+    room_service
+        .invite_member(&room_id, user_id, payload.invitee_id)
+        .await?;
+    respond_ok("Member invited successfully")
+}
+
 #[post("/{room_id}/leave")]
 pub async fn leave_room(
     room_id: web::Path<String>,
-    payload: web::Json<UserIdParam>,
+    identity: Identity,
     room_service: web::Data<Arc<dyn RoomService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
 ) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
     let room_id = room_id.into_inner();
-    room_service.leave_room(&room_id, payload.user_id).await?;
+    if let Some(transfer) = room_service.leave_room(&room_id, user_id).await? {
+        signaling_server.notify_owner_changed(&transfer);
+    }
     respond_ok("Left room successfully")
 }
 
+#[post("/{room_id}/transfer")]
+pub async fn transfer_ownership(
+    room_id: web::Path<String>,
+    payload: web::Json<TransferOwnershipParams>,
+    identity: Identity,
+    room_service: web::Data<Arc<dyn RoomService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
+) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
+    let room_id = room_id.into_inner();
+    let transfer = room_service
+        .transfer_ownership(&room_id, user_id, payload.new_owner_id)
+        .await?;
+
+    signaling_server.notify_owner_changed(&transfer);
+    signaling_server
+        .record_moderator_action(
+            &room_id,
+            transfer.previous_owner_id,
+            Some(transfer.new_owner_id),
+            "ownership_transferred",
+        )
+        .await;
+    respond_ok(transfer)
+}
+
 #[get("/{room_id}/users")]
 pub async fn list_room_users(
     room_id: web::Path<String>,
+    query: web::Query<MemberListParams>,
+    identity: Identity,
     room_service: web::Data<Arc<dyn RoomService>>,
+    signaling_server: web::Data<Arc<SignalingServer>>,
 ) -> ActixResult<HttpResponse> {
+    let user_id: i32 = identity
+        .id()?
+        .parse::<i32>()
+        .map_err(|_| AppError::BadRequest("Invalid User Id".to_string()))?;
+
     let room_id = room_id.into_inner();
-    let users = room_service.list_room_users(&room_id).await?;
-    respond_ok(users)
+    let mut page = room_service
+        .list_members(&room_id, user_id, query.into_inner())
+        .await?;
+
+    for member in &mut page.members {
+        member.is_connected = signaling_server.is_connected_to_room(member.user_id, &room_id);
+    }
+    respond_ok(page)
 }
 
 #[get("/{room_id}/users/{user_id}/is-in-room")]
@@ -0,0 +1,76 @@
+use base64::{Engine as _, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    rooms::{entities::RoomMemberView, search::SQLITE_TIMESTAMP_FORMAT},
+    shared::response::AppError,
+};
+
+pub const DEFAULT_MEMBER_PAGE_SIZE: i64 = 50;
+pub const MAX_MEMBER_PAGE_SIZE: i64 = 200;
+
+/// SQL expression that lists owners first, then moderators, then participants.
+pub const ROLE_ORDER: &str = "CASE rm.role WHEN 'owner' THEN 0 WHEN 'moderator' THEN 1 ELSE 2 END";
+
+/// Position of the last member on a page. Members are ordered by role, then
+/// by when they joined, with the user id as a tie-breaker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemberCursor {
+    pub role_order: i64,
+    pub joined_at: String,
+    pub user_id: i32,
+}
+
+impl MemberCursor {
+    pub fn after(member: &RoomMemberView) -> Self {
+        Self {
+            role_order: 2 - member.role.rank() as i64,
+            joined_at: member.joined_at.format(SQLITE_TIMESTAMP_FORMAT).to_string(),
+            user_id: member.user_id,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        general_purpose::URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(value: &str) -> Result<Self, AppError> {
+        let bytes = general_purpose::URL_SAFE_NO_PAD
+            .decode(value)
+            .map_err(|_| AppError::Validation("Invalid pagination cursor".to_string()))?;
+
+        serde_json::from_slice(&bytes)
+            .map_err(|_| AppError::Validation("Invalid pagination cursor".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rooms::RoomMemberRole;
+
+    #[test]
+    fn test_member_cursor_orders_owners_first_and_round_trips() {
+        let member = RoomMemberView {
+            user_id: 7,
+            first_name: "Ada".to_string(),
+            last_name: "Lovelace".to_string(),
+            avatar_url: String::new(),
+            role: RoomMemberRole::Owner,
+            joined_at: chrono::NaiveDate::from_ymd_opt(2025, 10, 15)
+                .unwrap()
+                .and_hms_opt(9, 30, 0)
+                .unwrap(),
+            is_muted: false,
+            is_video_enabled: true,
+            is_connected: false,
+        };
+
+        let cursor = MemberCursor::after(&member);
+        assert_eq!(cursor.role_order, 0);
+        assert_eq!(cursor.joined_at, "2025-10-15 09:30:00");
+        assert_eq!(MemberCursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert!(MemberCursor::decode("not a cursor").is_err());
+    }
+}
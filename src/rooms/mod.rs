@@ -1,10 +1,15 @@
 pub mod contract;
 pub mod entities;
 pub mod handlers;
+pub mod members;
+pub mod passcode_throttle;
+pub mod policy;
 pub mod repository;
 pub mod routes;
+pub mod search;
 pub mod service;
 
-pub use entities::{Room, RoomMemberRole, RoomType};
+pub use entities::{OwnershipTransfer, Room, RoomMemberRole, RoomMemberView, RoomType};
+pub use policy::{RoomAction, RoomPolicies};
 pub use repository::{RoomRepository, SqliteRoomRepository};
 pub use service::{RoomService, RoomServiceImpl};
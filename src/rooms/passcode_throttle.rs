@@ -0,0 +1,152 @@
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+use crate::shared::response::AppError;
+
+const DEFAULT_MAX_FAILURES: u32 = 5;
+const DEFAULT_WINDOW: Duration = Duration::from_secs(15 * 60);
+const DEFAULT_LOCKOUT: Duration = Duration::from_secs(15 * 60);
+
+struct Attempts {
+    failures: u32,
+    window_started: Instant,
+    locked_until: Option<Instant>,
+}
+
+/// Tracks wrong passcode guesses per authenticated user and room, and locks
+/// that user out of the room's passcode check once too many failures happen
+/// inside the window. Other people, including those who know the passcode,
+/// are unaffected.
+pub struct PasscodeThrottle {
+    attempts: DashMap<(i32, String), Attempts>,
+    max_failures: u32,
+    window: Duration,
+    lockout: Duration,
+}
+
+impl Default for PasscodeThrottle {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FAILURES, DEFAULT_WINDOW, DEFAULT_LOCKOUT)
+    }
+}
+
+impl PasscodeThrottle {
+    pub fn new(max_failures: u32, window: Duration, lockout: Duration) -> Self {
+        Self {
+            attempts: DashMap::new(),
+            max_failures,
+            window,
+            lockout,
+        }
+    }
+
+    pub fn check(&self, room_id: &str, user_id: i32) -> Result<(), AppError> {
+        let key = (user_id, room_id.to_string());
+        if let Some(entry) = self.attempts.get(&key)
+            && let Some(locked_until) = entry.locked_until
+        {
+            let now = Instant::now();
+            if locked_until > now {
+                return Err(AppError::TooManyRequests(format!(
+                    "Too many wrong passcodes for room {}. Try again in {} seconds",
+                    room_id,
+                    (locked_until - now).as_secs().max(1)
+                )));
+            }
+            drop(entry);
+            self.attempts.remove(&key);
+        }
+        Ok(())
+    }
+
+    pub fn record_failure(&self, room_id: &str, user_id: i32) {
+        let now = Instant::now();
+        self.evict_stale(now);
+
+        let mut entry = self
+            .attempts
+            .entry((user_id, room_id.to_string()))
+            .or_insert(Attempts {
+                failures: 0,
+                window_started: now,
+                locked_until: None,
+            });
+
+        if now.duration_since(entry.window_started) > self.window {
+            entry.failures = 0;
+            entry.window_started = now;
+        }
+
+        entry.failures += 1;
+        if entry.failures >= self.max_failures {
+            entry.locked_until = Some(now + self.lockout);
+        }
+    }
+
+    /// Clears the user's failures once they get the passcode right.
+    pub fn record_success(&self, room_id: &str, user_id: i32) {
+        self.attempts.remove(&(user_id, room_id.to_string()));
+    }
+
+    /// Clears everyone's failures for the room, for when its passcode changes.
+    pub fn reset(&self, room_id: &str) {
+        self.attempts.retain(|(_, room), _| room != room_id);
+    }
+
+    /// Drops entries whose window has passed and that are not locked, so
+    /// guesses do not stay in memory.
+    fn evict_stale(&self, now: Instant) {
+        self.attempts.retain(|_, attempts| {
+            attempts.locked_until.is_some_and(|until| until > now)
+                || now.duration_since(attempts.window_started) <= self.window
+        });
+    }
+
+    #[cfg(test)]
+    fn tracked(&self) -> usize {
+        self.attempts.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locks_only_the_guessing_user_out_of_the_room() {
+        let throttle = PasscodeThrottle::new(3, Duration::from_secs(60), Duration::from_secs(60));
+
+        for _ in 0..2 {
+            throttle.record_failure("room", 1);
+            assert!(throttle.check("room", 1).is_ok());
+        }
+
+        throttle.record_failure("room", 1);
+        assert!(matches!(
+            throttle.check("room", 1),
+            Err(AppError::TooManyRequests(_))
+        ));
+        assert!(throttle.check("room", 2).is_ok());
+        assert!(throttle.check("other", 1).is_ok());
+
+        throttle.record_failure("room", 2);
+        throttle.record_success("room", 2);
+        throttle.reset("room");
+        assert!(throttle.check("room", 1).is_ok());
+        assert_eq!(throttle.tracked(), 0);
+    }
+
+    #[test]
+    fn test_lock_expires_and_stale_entries_are_evicted() {
+        let throttle = PasscodeThrottle::new(1, Duration::from_secs(60), Duration::ZERO);
+        throttle.record_failure("room", 1);
+        assert!(throttle.check("room", 1).is_ok());
+
+        let throttle = PasscodeThrottle::new(5, Duration::ZERO, Duration::from_secs(60));
+        throttle.record_failure("a", 1);
+        std::thread::sleep(Duration::from_millis(5));
+        throttle.record_failure("b", 1);
+        assert_eq!(throttle.tracked(), 1);
+    }
+}
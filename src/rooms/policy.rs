@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::rooms::entities::RoomMemberRole;
+
+/// Room actions whose availability is configured per room.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RoomAction {
+    Chat,
+    Unmute,
+    ScreenShare,
+    StartCall,
+    Invite,
+}
+
+impl RoomAction {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Chat => "send chat messages",
+            Self::Unmute => "unmute yourself",
+            Self::ScreenShare => "share your screen",
+            Self::StartCall => "start a call",
+            Self::Invite => "invite others",
+        }
+    }
+}
+
+/// The lowest member role allowed to perform each action. Higher roles are
+/// always allowed, so an owner can never lock themselves out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct RoomPolicies {
+    #[sqlx(rename = "chat_policy")]
+    pub chat: RoomMemberRole,
+    #[sqlx(rename = "unmute_policy")]
+    pub unmute: RoomMemberRole,
+    #[sqlx(rename = "screen_share_policy")]
+    pub screen_share: RoomMemberRole,
+    #[sqlx(rename = "start_call_policy")]
+    pub start_call: RoomMemberRole,
+    #[sqlx(rename = "invite_policy")]
+    pub invite: RoomMemberRole,
+}
+
+impl Default for RoomPolicies {
+    fn default() -> Self {
+        Self {
+            chat: RoomMemberRole::Participant,
+            unmute: RoomMemberRole::Participant,
+            screen_share: RoomMemberRole::Participant,
+            start_call: RoomMemberRole::Participant,
+            invite: RoomMemberRole::Participant,
+        }
+    }
+}
+
+impl RoomPolicies {
+    pub fn required_role(&self, action: RoomAction) -> &RoomMemberRole {
+        match action {
+            RoomAction::Chat => &self.chat,
+            RoomAction::Unmute => &self.unmute,
+            RoomAction::ScreenShare => &self.screen_share,
+            RoomAction::StartCall => &self.start_call,
+            RoomAction::Invite => &self.invite,
+        }
+    }
+
+    pub fn allows(&self, action: RoomAction, role: &RoomMemberRole) -> bool {
+        role.rank() >= self.required_role(action).rank()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_higher_roles_inherit_permissions() {
+        let policies = RoomPolicies {
+            chat: RoomMemberRole::Moderator,
+            invite: RoomMemberRole::Owner,
+            ..RoomPolicies::default()
+        };
+
+        assert!(!policies.allows(RoomAction::Chat, &RoomMemberRole::Participant));
+        assert!(policies.allows(RoomAction::Chat, &RoomMemberRole::Moderator));
+        assert!(policies.allows(RoomAction::Chat, &RoomMemberRole::Owner));
+        assert!(!policies.allows(RoomAction::Invite, &RoomMemberRole::Moderator));
+        assert!(policies.allows(RoomAction::Unmute, &RoomMemberRole::Participant));
+    }
+}
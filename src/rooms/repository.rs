@@ -1,9 +1,14 @@
 use async_trait::async_trait;
-use sqlx::SqlitePool;
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
 use uuid::Uuid;
 
 use crate::{
-    rooms::entities::{Room, RoomMemberRole, RoomType},
+    rooms::{
+        entities::{Room, RoomMember, RoomMemberRole, RoomMemberView, RoomType},
+        members::{MemberCursor, ROLE_ORDER},
+        policy::RoomPolicies,
+        search::{RoomSearch, SQLITE_TIMESTAMP_FORMAT, escape_like},
+    },
     shared::response::AppError,
     users::User,
 };
@@ -16,13 +21,46 @@ pub trait RoomRepository {
         room_type: RoomType,
         created_by: i32,
         description: Option<String>,
+        passcode_hash: Option<String>,
     ) -> Result<Room, AppError>;
 
     async fn get_by_id(&self, room_id: &str) -> Result<Option<Room>, AppError>;
 
-    async fn list_rooms(&self, limit: i64, offset: i64) -> Result<Vec<Room>, AppError>;
+    async fn get_by_direct_key(&self, direct_key: &str) -> Result<Option<Room>, AppError>;
 
-    async fn delete(&self, room_id: &str) -> Result<(), AppError>;
+    /// Creates the direct room for a pair unless one already exists, and
+    /// returns whichever room holds the key.
+    async fn create_direct(
+        &self,
+        name: String,
+        created_by: i32,
+        direct_key: &str,
+    ) -> Result<Room, AppError>;
+
+    async fn list_rooms(&self, search: &RoomSearch) -> Result<Vec<Room>, AppError>;
+
+    async fn archive(&self, room_id: &str) -> Result<(), AppError>;
+
+    async fn restore(&self, room_id: &str) -> Result<(), AppError>;
+
+    async fn purge(&self, room_id: &str) -> Result<(), AppError>;
+
+    async fn update_passcode(
+        &self,
+        room_id: &str,
+        passcode_hash: Option<String>,
+    ) -> Result<(), AppError>;
+
+    async fn update_policies(&self, room_id: &str, policies: &RoomPolicies)
+    -> Result<(), AppError>;
+
+    async fn update_media_state(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        is_muted: bool,
+        is_video_enabled: bool,
+    ) -> Result<(), AppError>;
 
     async fn join_room(&self, room_id: &str, user_id: i32) -> Result<(), AppError>;
 
@@ -30,18 +68,50 @@ pub trait RoomRepository {
 
     async fn list_room_users(&self, room_id: &str) -> Result<Vec<User>, AppError>;
 
+    /// Current members ordered by role, then join time, starting after `after`.
+    async fn list_members(
+        &self,
+        room_id: &str,
+        after: Option<&MemberCursor>,
+        limit: i64,
+    ) -> Result<Vec<RoomMemberView>, AppError>;
+
     async fn is_user_in_room(&self, room_id: &str, user_id: i32) -> Result<bool, AppError>;
 
     async fn is_user_owner(&self, room_id: &str, user_id: i32) -> Result<bool, AppError>;
 
     async fn count_active_members(&self, room_id: &str) -> Result<i64, AppError>;
 
+    async fn get_member(&self, room_id: &str, user_id: i32)
+    -> Result<Option<RoomMember>, AppError>;
+
     async fn update_member_role(
         &self,
         room_id: &str,
         user_id: i32,
         role: RoomMemberRole,
     ) -> Result<(), AppError>;
+
+    async fn find_successor(&self, room_id: &str) -> Result<Option<i32>, AppError>;
+
+    /// Current members who joined at least `min_age_secs` seconds ago of
+    /// unarchived rooms whose membership only lasts for a session: instant
+    /// rooms, and meeting occurrence rooms whose occurrence ended at least
+    /// `min_age_secs` seconds ago. Membership of other rooms is kept whether
+    /// or not the member is connected.
+    async fn list_session_room_members(
+        &self,
+        min_age_secs: i64,
+    ) -> Result<Vec<RoomMember>, AppError>;
+
+    async fn archive_empty_instant_rooms(&self) -> Result<u64, AppError>;
+
+    async fn transfer_ownership(
+        &self,
+        room_id: &str,
+        from_user_id: i32,
+        to_user_id: i32,
+    ) -> Result<(), AppError>;
 }
 
 pub struct SqliteRoomRepository {
@@ -62,12 +132,13 @@ impl RoomRepository for SqliteRoomRepository {
         room_type: RoomType,
         created_by: i32,
         description: Option<String>,
+        passcode_hash: Option<String>,
     ) -> Result<Room, AppError> {
         let room_id = Uuid::new_v4().to_string();
         let room = sqlx::query_as::<_, Room>(
             r#"
-                INSERT INTO rooms (id, name, room_type, created_by, description, max_participants, is_active)
-                VALUES ($1, $2, $3, $4, $5, 10, TRUE)
+                INSERT INTO rooms (id, name, room_type, created_by, description, max_participants, is_active, passcode_hash)
+                VALUES ($1, $2, $3, $4, $5, 10, TRUE, $6)
                 RETURNING *
             "#,
         )
@@ -76,6 +147,7 @@ impl RoomRepository for SqliteRoomRepository {
         .bind(room_type.to_string())
         .bind(created_by)
         .bind(description)
+        .bind(passcode_hash)
         .fetch_one(&self.pool)
         .await?;
 
@@ -91,26 +163,219 @@ impl RoomRepository for SqliteRoomRepository {
         Ok(room)
     }
 
-    async fn list_rooms(&self, limit: i64, offset: i64) -> Result<Vec<Room>, AppError> {
-        let rooms = sqlx::query_as::<_, Room>(
+    async fn get_by_direct_key(&self, direct_key: &str) -> Result<Option<Room>, AppError> {
+        let room = sqlx::query_as::<_, Room>("SELECT * FROM rooms WHERE direct_key = $1")
+            .bind(direct_key)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(room)
+    }
+
+    async fn create_direct(
+        &self,
+        name: String,
+        created_by: i32,
+        direct_key: &str,
+    ) -> Result<Room, AppError> {
+        sqlx::query(
             r#"
-                SELECT 
-                    *
-                FROM rooms 
-                ORDER BY created_at DESC 
-                LIMIT $1 OFFSET $2
+                INSERT INTO rooms (id, name, room_type, created_by, max_participants, is_active, direct_key)
+                VALUES ($1, $2, $3, $4, 2, TRUE, $5)
+                ON CONFLICT DO NOTHING
             "#,
         )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
+        .bind(Uuid::new_v4().to_string())
+        .bind(&name)
+        .bind(RoomType::OneOnOne.to_string())
+        .bind(created_by)
+        .bind(direct_key)
+        .execute(&self.pool)
         .await?;
 
+        self.get_by_direct_key(direct_key)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Direct room {} not found", direct_key)))
+    }
+
+    async fn list_rooms(&self, search: &RoomSearch) -> Result<Vec<Room>, AppError> {
+        let mut query: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT r.* FROM rooms r WHERE ");
+
+        // Private rooms are only visible to their current members
+        match search.viewer_id {
+            Some(viewer_id) => {
+                query
+                    .push(
+                        r#"(r.room_type != 'private' OR EXISTS (
+                            SELECT 1 FROM room_members rm
+                            WHERE rm.room_id = r.id AND rm.left_at IS NULL AND rm.user_id = "#,
+                    )
+                    .push_bind(viewer_id)
+                    .push("))");
+            }
+            None => {
+                query.push("r.room_type != 'private'");
+            }
+        }
+
+        query.push(if search.archived {
+            " AND r.archived_at IS NOT NULL"
+        } else {
+            " AND r.archived_at IS NULL"
+        });
+
+        if let Some(room_type) = &search.room_type {
+            query
+                .push(" AND r.room_type = ")
+                .push_bind(room_type.to_string());
+        }
+
+        if let Some(name) = &search.name {
+            query
+                .push(" AND r.name LIKE '%' || ")
+                .push_bind(escape_like(name))
+                .push(r#" || '%' ESCAPE '\'"#);
+        }
+
+        if let (true, Some(viewer_id)) = (search.member_only, search.viewer_id) {
+            query
+                .push(
+                    r#" AND EXISTS (
+                        SELECT 1 FROM room_members rm
+                        WHERE rm.room_id = r.id AND rm.left_at IS NULL AND rm.user_id = "#,
+                )
+                .push_bind(viewer_id)
+                .push(")");
+        }
+
+        if let Some(has_active_call) = search.has_active_call {
+            query
+                .push(if has_active_call {
+                    " AND EXISTS "
+                } else {
+                    " AND NOT EXISTS "
+                })
+                .push(
+                    r#"(
+                        SELECT 1 FROM calls c
+                        WHERE c.room_id = r.id AND c.status IN ('initiated', 'ringing', 'active')
+                    )"#,
+                );
+        }
+
+        if let Some(created_by) = search.created_by {
+            query.push(" AND r.created_by = ").push_bind(created_by);
+        }
+
+        if let Some(from) = search.created_from {
+            query
+                .push(" AND r.created_at >= ")
+                .push_bind(from.format(SQLITE_TIMESTAMP_FORMAT).to_string());
+        }
+
+        if let Some(to) = search.created_to {
+            query
+                .push(" AND r.created_at < ")
+                .push_bind(to.format(SQLITE_TIMESTAMP_FORMAT).to_string());
+        }
+
+        let column = search.sort.column();
+        let direction = search.direction;
+
+        if let Some(cursor) = &search.after {
+            query
+                .push(format!(" AND ({} {} ", column, direction.comparator()))
+                .push_bind(cursor.key.clone())
+                .push(format!(" OR ({} = ", column))
+                .push_bind(cursor.key.clone())
+                .push(format!(" AND r.id {} ", direction.comparator()))
+                .push_bind(cursor.id.clone())
+                .push("))");
+        }
+
+        query
+            .push(format!(
+                " ORDER BY {} {}, r.id {} LIMIT ",
+                column,
+                direction.keyword(),
+                direction.keyword()
+            ))
+            .push_bind(search.limit);
+
+        let rooms = query.build_query_as::<Room>().fetch_all(&self.pool).await?;
+
         Ok(rooms)
     }
 
-    async fn delete(&self, room_id: &str) -> Result<(), AppError> {
+    async fn archive(&self, room_id: &str) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE rooms
+            SET is_active = FALSE, archived_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            "#,
+        )
+        .bind(room_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn restore(&self, room_id: &str) -> Result<(), AppError> {
+        sqlx::query("UPDATE rooms SET is_active = TRUE, archived_at = NULL WHERE id = $1")
+            .bind(room_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn purge(&self, room_id: &str) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM call_participants
+            WHERE call_id IN (SELECT id FROM calls WHERE room_id = $1)
+            "#,
+        )
+        .bind(room_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM calls WHERE room_id = $1")
+            .bind(room_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM meeting_occurrences WHERE room_id = $1")
+            .bind(room_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM room_members WHERE room_id = $1")
+            .bind(room_id)
+            .execute(&mut *tx)
+            .await?;
+
         sqlx::query("DELETE FROM rooms WHERE id = $1")
+            .bind(room_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_passcode(
+        &self,
+        room_id: &str,
+        passcode_hash: Option<String>,
+    ) -> Result<(), AppError> {
+        sqlx::query("UPDATE rooms SET passcode_hash = $1 WHERE id = $2")
+            .bind(passcode_hash)
             .bind(room_id)
             .execute(&self.pool)
             .await?;
@@ -118,11 +383,66 @@ impl RoomRepository for SqliteRoomRepository {
         Ok(())
     }
 
+    async fn update_policies(
+        &self,
+        room_id: &str,
+        policies: &RoomPolicies,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE rooms
+            SET chat_policy = $1,
+                unmute_policy = $2,
+                screen_share_policy = $3,
+                start_call_policy = $4,
+                invite_policy = $5
+            WHERE id = $6
+            "#,
+        )
+        .bind(&policies.chat)
+        .bind(&policies.unmute)
+        .bind(&policies.screen_share)
+        .bind(&policies.start_call)
+        .bind(&policies.invite)
+        .bind(room_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_media_state(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        is_muted: bool,
+        is_video_enabled: bool,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE room_members
+            SET is_muted = $1, is_video_enabled = $2
+            WHERE room_id = $3 AND user_id = $4 AND left_at IS NULL
+            "#,
+        )
+        .bind(is_muted)
+        .bind(is_video_enabled)
+        .bind(room_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     async fn join_room(&self, room_id: &str, user_id: i32) -> Result<(), AppError> {
         sqlx::query(
             r#"
-            INSERT OR IGNORE INTO room_members (room_id, user_id)
+            INSERT INTO room_members (room_id, user_id)
             VALUES ($1, $2)
+            ON CONFLICT (room_id, user_id) DO UPDATE
+            SET left_at = NULL, joined_at = CURRENT_TIMESTAMP, role = 'participant'
+            WHERE room_members.left_at IS NOT NULL
             "#,
         )
         .bind(room_id)
@@ -169,6 +489,49 @@ impl RoomRepository for SqliteRoomRepository {
         Ok(users)
     }
 
+    async fn list_members(
+        &self,
+        room_id: &str,
+        after: Option<&MemberCursor>,
+        limit: i64,
+    ) -> Result<Vec<RoomMemberView>, AppError> {
+        let mut query = QueryBuilder::<Sqlite>::new(
+            r#"
+            SELECT u.*, rm.role, rm.joined_at, rm.is_muted, rm.is_video_enabled
+            FROM room_members rm
+            JOIN users u ON u.id = rm.user_id
+            WHERE rm.left_at IS NULL AND rm.room_id = "#,
+        );
+        query.push_bind(room_id);
+
+        if let Some(cursor) = after {
+            query
+                .push(format!(
+                    " AND ({}, rm.joined_at, rm.user_id) > (",
+                    ROLE_ORDER
+                ))
+                .push_bind(cursor.role_order)
+                .push(", ")
+                .push_bind(&cursor.joined_at)
+                .push(", ")
+                .push_bind(cursor.user_id)
+                .push(")");
+        }
+
+        query
+            .push(format!(
+                " ORDER BY {}, rm.joined_at, rm.user_id LIMIT ",
+                ROLE_ORDER
+            ))
+            .push_bind(limit);
+
+        let members = query
+            .build_query_as::<RoomMemberView>()
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(members)
+    }
+
     async fn is_user_in_room(&self, room_id: &str, user_id: i32) -> Result<bool, AppError> {
         let exists = sqlx::query_scalar(
             r#"
@@ -212,6 +575,21 @@ impl RoomRepository for SqliteRoomRepository {
         Ok(count)
     }
 
+    async fn get_member(
+        &self,
+        room_id: &str,
+        user_id: i32,
+    ) -> Result<Option<RoomMember>, AppError> {
+        let member = sqlx::query_as::<_, RoomMember>(
+            "SELECT * FROM room_members WHERE room_id = $1 AND user_id = $2",
+        )
+        .bind(room_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(member)
+    }
+
     async fn update_member_role(
         &self,
         room_id: &str,
@@ -238,4 +616,119 @@ impl RoomRepository for SqliteRoomRepository {
         }
         Ok(())
     }
+
+    async fn find_successor(&self, room_id: &str) -> Result<Option<i32>, AppError> {
+        let successor = sqlx::query_scalar(
+            r#"
+            SELECT user_id FROM room_members
+            WHERE room_id = $1 AND left_at IS NULL AND role != 'owner'
+            ORDER BY
+                CASE role WHEN 'moderator' THEN 0 ELSE 1 END,
+                joined_at ASC,
+                user_id ASC
+            LIMIT 1
+            "#,
+        )
+        .bind(room_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(successor)
+    }
+
+    async fn transfer_ownership(
+        &self,
+        room_id: &str,
+        from_user_id: i32,
+        to_user_id: i32,
+    ) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE room_members SET role = $1
+            WHERE room_id = $2 AND user_id = $3 AND role = 'owner'
+            "#,
+        )
+        .bind(RoomMemberRole::Moderator)
+        .bind(room_id)
+        .bind(from_user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let promoted = sqlx::query(
+            r#"
+            UPDATE room_members SET role = $1
+            WHERE room_id = $2 AND user_id = $3 AND left_at IS NULL
+            "#,
+        )
+        .bind(RoomMemberRole::Owner)
+        .bind(room_id)
+        .bind(to_user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if promoted.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "User {} not in room {}",
+                to_user_id, room_id
+            )));
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn list_session_room_members(
+        &self,
+        min_age_secs: i64,
+    ) -> Result<Vec<RoomMember>, AppError> {
+        let members = sqlx::query_as::<_, RoomMember>(
+            r#"
+            SELECT rm.*
+            FROM room_members rm
+            JOIN rooms r ON r.id = rm.room_id
+            WHERE r.archived_at IS NULL
+                AND rm.left_at IS NULL
+                AND rm.joined_at <= datetime('now', $1)
+                AND (
+                    r.room_type = 'instant'
+                    OR EXISTS (
+                        SELECT 1
+                        FROM meeting_occurrences mo
+                        JOIN scheduled_meetings m ON m.id = mo.meeting_id
+                        WHERE mo.room_id = r.id
+                            AND julianday(mo.occurrence_start)
+                                + julianday(m.ends_at) - julianday(m.starts_at)
+                                <= julianday('now', $1)
+                    )
+                )
+            "#,
+        )
+        .bind(format!("-{} seconds", min_age_secs))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(members)
+    }
+
+    async fn archive_empty_instant_rooms(&self) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE rooms
+            SET is_active = FALSE, archived_at = CURRENT_TIMESTAMP
+            WHERE room_type = 'instant'
+                AND archived_at IS NULL
+                AND NOT EXISTS (
+                    SELECT 1 FROM room_members rm
+                    WHERE rm.room_id = rooms.id AND rm.left_at IS NULL
+                )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
 }
@@ -9,8 +9,15 @@ pub fn room_routes(cfg: &mut web::ServiceConfig) {
             .service(handlers::create_room)
             .service(handlers::list_rooms)
             .service(handlers::delete_room)
+            .service(handlers::restore_room)
+            .service(handlers::purge_room)
             .service(handlers::join_room)
+            .service(handlers::set_room_passcode)
+            .service(handlers::rotate_room_passcode)
+            .service(handlers::update_room_policies)
+            .service(handlers::invite_member)
             .service(handlers::leave_room)
+            .service(handlers::transfer_ownership)
             .service(handlers::list_room_users)
             .service(handlers::is_user_in_room)
             .service(handlers::is_user_owner),
@@ -0,0 +1,196 @@
+use std::str::FromStr;
+
+use base64::{Engine as _, engine::general_purpose};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    rooms::entities::{Room, RoomType},
+    shared::response::AppError,
+};
+
+/// Format used by SQLite's CURRENT_TIMESTAMP, which is how `created_at` is stored.
+pub const SQLITE_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+pub const DEFAULT_PAGE_SIZE: i64 = 10;
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RoomSortField {
+    #[default]
+    CreatedAt,
+    Name,
+}
+
+impl RoomSortField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+            Self::Name => "name",
+        }
+    }
+
+    /// The SQL expression rooms are ordered and compared by.
+    pub fn column(&self) -> &'static str {
+        match self {
+            Self::CreatedAt => "r.created_at",
+            Self::Name => "r.name COLLATE NOCASE",
+        }
+    }
+
+    fn key_of(&self, room: &Room) -> String {
+        match self {
+            Self::CreatedAt => room.created_at.format(SQLITE_TIMESTAMP_FORMAT).to_string(),
+            Self::Name => room.name.clone(),
+        }
+    }
+}
+
+impl FromStr for RoomSortField {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "created_at" => Ok(Self::CreatedAt),
+            "name" => Ok(Self::Name),
+            _ => Err(AppError::Validation(
+                "Invalid sort field provided! Valid values are: 'created_at', 'name'".to_string(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC",
+        }
+    }
+
+    /// Comparison that selects rows after the cursor in this direction.
+    pub fn comparator(&self) -> &'static str {
+        match self {
+            Self::Asc => ">",
+            Self::Desc => "<",
+        }
+    }
+}
+
+impl FromStr for SortDirection {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "asc" => Ok(Self::Asc),
+            "desc" => Ok(Self::Desc),
+            _ => Err(AppError::Validation(
+                "Invalid sort order provided! Valid values are: 'asc', 'desc'".to_string(),
+            )),
+        }
+    }
+}
+
+/// Position of the last room on a page. Rooms are ordered by the sort key
+/// with the room id as a tie-breaker, so the pair is unique and stable.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoomCursor {
+    pub sort: String,
+    pub key: String,
+    pub id: String,
+}
+
+impl RoomCursor {
+    pub fn after(room: &Room, sort: RoomSortField) -> Self {
+        Self {
+            sort: sort.as_str().to_string(),
+            key: sort.key_of(room),
+            id: room.id.clone(),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        general_purpose::URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(value: &str, sort: RoomSortField) -> Result<Self, AppError> {
+        let invalid = || AppError::Validation("Invalid pagination cursor".to_string());
+
+        let bytes = general_purpose::URL_SAFE_NO_PAD
+            .decode(value)
+            .map_err(|_| invalid())?;
+        let cursor: Self = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+
+        if cursor.sort != sort.as_str() {
+            return Err(AppError::Validation(
+                "Pagination cursor does not match the requested sort".to_string(),
+            ));
+        }
+
+        Ok(cursor)
+    }
+}
+
+/// A validated room listing query.
+#[derive(Debug, Clone)]
+pub struct RoomSearch {
+    pub viewer_id: Option<i32>,
+    pub room_type: Option<RoomType>,
+    pub name: Option<String>,
+    pub member_only: bool,
+    pub archived: bool,
+    pub has_active_call: Option<bool>,
+    pub created_by: Option<i32>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    pub sort: RoomSortField,
+    pub direction: SortDirection,
+    pub limit: i64,
+    pub after: Option<RoomCursor>,
+}
+
+/// Escapes `LIKE` wildcards so the name filter matches a literal substring.
+pub fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip_and_sort_mismatch() {
+        let cursor = RoomCursor {
+            sort: "name".to_string(),
+            key: "Team sync".to_string(),
+            id: "4f6c".to_string(),
+        };
+
+        let encoded = cursor.encode();
+        assert_eq!(
+            RoomCursor::decode(&encoded, RoomSortField::Name).unwrap(),
+            cursor
+        );
+        assert!(RoomCursor::decode(&encoded, RoomSortField::CreatedAt).is_err());
+        assert!(RoomCursor::decode("not a cursor", RoomSortField::Name).is_err());
+    }
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("50%_off\\"), "50\\%\\_off\\\\");
+    }
+}
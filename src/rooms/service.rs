@@ -1,16 +1,28 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use rand::{Rng, distributions::Alphanumeric};
 
 use crate::{
     rooms::{
-        entities::{Room, RoomMemberRole, RoomType},
+        contract::{
+            MemberListParams, RoomMemberPage, RoomPage, RoomSearchParams, UpdatePoliciesParams,
+        },
+        entities::{OwnershipTransfer, Room, RoomMember, RoomMemberRole, RoomType},
+        members::{DEFAULT_MEMBER_PAGE_SIZE, MAX_MEMBER_PAGE_SIZE, MemberCursor},
+        passcode_throttle::PasscodeThrottle,
+        policy::RoomAction,
         repository::RoomRepository,
+        search::{
+            DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE, RoomCursor, RoomSearch, RoomSortField, SortDirection,
+        },
     },
-    shared::response::AppError,
+    shared::{response::AppError, utils},
     users::User,
 };
 
+const GENERATED_PASSCODE_LENGTH: usize = 8;
+
 #[async_trait]
 pub trait RoomService: Send + Sync {
     async fn create_room(
@@ -19,20 +31,76 @@ pub trait RoomService: Send + Sync {
         room_type: String,
         created_by: i32,
         description: Option<String>,
+        passcode: Option<String>,
     ) -> Result<Room, AppError>;
 
     async fn get_room(&self, room_id: &str) -> Result<Option<Room>, AppError>;
 
-    async fn list_rooms(&self, limit: i64, offset: i64) -> Result<Vec<Room>, AppError>;
+    async fn list_rooms(
+        &self,
+        viewer_id: Option<i32>,
+        params: RoomSearchParams,
+    ) -> Result<RoomPage, AppError>;
 
     async fn delete_room(&self, room_id: &str, user_id: i32) -> Result<(), AppError>;
 
-    async fn join_room(&self, room_id: &str, user_id: i32) -> Result<(), AppError>;
+    async fn restore_room(&self, room_id: &str, user_id: i32) -> Result<Room, AppError>;
+
+    async fn purge_room(&self, room_id: &str) -> Result<(), AppError>;
+
+    /// Finds the one-on-one room shared by the two users, creating or
+    /// restoring it as needed, and makes sure both are members.
+    async fn get_or_create_direct_room(
+        &self,
+        user_id: i32,
+        other_user_id: i32,
+        name: String,
+    ) -> Result<Room, AppError>;
+
+    async fn join_room(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        passcode: Option<&str>,
+    ) -> Result<(), AppError>;
+
+    /// Marks the user as having left. When the owner leaves, ownership passes
+    /// to the longest-standing moderator, or failing that the longest-standing
+    /// member, and the resulting transfer is returned.
+    async fn leave_room(
+        &self,
+        room_id: &str,
+        user_id: i32,
+    ) -> Result<Option<OwnershipTransfer>, AppError>;
+
+    async fn transfer_ownership(
+        &self,
+        room_id: &str,
+        owner_id: i32,
+        new_owner_id: i32,
+    ) -> Result<OwnershipTransfer, AppError>;
+
+    /// Members of instant rooms and of finished meeting occurrences, whose
+    /// membership ends when they disconnect.
+    async fn list_session_room_members(
+        &self,
+        min_age_secs: i64,
+    ) -> Result<Vec<RoomMember>, AppError>;
 
-    async fn leave_room(&self, room_id: &str, user_id: i32) -> Result<(), AppError>;
+    async fn archive_empty_instant_rooms(&self) -> Result<u64, AppError>;
 
     async fn list_room_users(&self, room_id: &str) -> Result<Vec<User>, AppError>;
 
+    /// A page of the room's current members without private contact details.
+    /// `is_connected` is left false for the caller to fill in. Only members
+    /// of the room may list them.
+    async fn list_members(
+        &self,
+        room_id: &str,
+        viewer_id: i32,
+        params: MemberListParams,
+    ) -> Result<RoomMemberPage, AppError>;
+
     async fn is_user_in_room(&self, room_id: &str, user_id: i32) -> Result<bool, AppError>;
 
     async fn is_user_owner(&self, room_id: &str, user_id: i32) -> Result<bool, AppError>;
@@ -42,16 +110,209 @@ pub trait RoomService: Send + Sync {
         room_id: &str,
         user_id: i32,
         role: RoomMemberRole,
+        passcode: Option<&str>,
+    ) -> Result<(), AppError>;
+
+    /// Checks that a user may enter the room (passcode, capacity, room type)
+    /// without adding them as a member.
+    async fn verify_room_access(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        passcode: Option<&str>,
+    ) -> Result<Room, AppError>;
+
+    /// Adds an already vetted user, e.g. one admitted from the lobby.
+    async fn admit_member(&self, room_id: &str, user_id: i32) -> Result<(), AppError>;
+
+    async fn get_member_role(
+        &self,
+        room_id: &str,
+        user_id: i32,
+    ) -> Result<Option<RoomMemberRole>, AppError>;
+
+    async fn is_user_moderator(&self, room_id: &str, user_id: i32) -> Result<bool, AppError>;
+
+    async fn set_passcode(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        passcode: Option<String>,
+    ) -> Result<(), AppError>;
+
+    async fn rotate_passcode(&self, room_id: &str, user_id: i32) -> Result<String, AppError>;
+
+    async fn update_policies(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        params: UpdatePoliciesParams,
+    ) -> Result<Room, AppError>;
+
+    /// Fails unless the user is a current member whose role the room's
+    /// policy allows to perform the action.
+    async fn ensure_permitted(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        action: RoomAction,
+    ) -> Result<(), AppError>;
+
+    /// Adds a member on another member's behalf. Invited users skip the
+    /// passcode and may join private rooms.
+    async fn invite_member(
+        &self,
+        room_id: &str,
+        inviter_id: i32,
+        invitee_id: i32,
+    ) -> Result<(), AppError>;
+
+    async fn update_media_state(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        audio_enabled: bool,
+        video_enabled: bool,
+        screen_sharing: bool,
     ) -> Result<(), AppError>;
 }
 
 pub struct RoomServiceImpl {
     repo: Arc<dyn RoomRepository + Send + Sync>,
+    passcode_throttle: PasscodeThrottle,
 }
 
 impl RoomServiceImpl {
     pub fn new(repo: Arc<dyn RoomRepository + Send + Sync>) -> Self {
-        Self { repo }
+        Self {
+            repo,
+            passcode_throttle: PasscodeThrottle::default(),
+        }
+    }
+
+    fn hash_passcode(passcode: &str) -> Result<String, AppError> {
+        let passcode = passcode.trim();
+        if passcode.len() < 4 || passcode.len() > 64 {
+            return Err(AppError::Validation(
+                "Passcode must be between 4 and 64 characters".into(),
+            ));
+        }
+
+        utils::hash_password(passcode)
+            .map_err(|_| AppError::InternalServerError("Failed to hash passcode".into()))
+    }
+
+    async fn get_owned_room(&self, room_id: &str, user_id: i32) -> Result<Room, AppError> {
+        let room = self
+            .get_room(room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
+
+        if !self.is_user_owner(room_id, user_id).await? {
+            return Err(AppError::Unauthorized(
+                "Only room owner can manage the passcode".into(),
+            ));
+        }
+
+        Ok(room)
+    }
+
+    fn verify_passcode(
+        &self,
+        room: &Room,
+        user_id: i32,
+        passcode: Option<&str>,
+    ) -> Result<(), AppError> {
+        let Some(passcode_hash) = room.passcode_hash.as_deref() else {
+            return Ok(());
+        };
+
+        self.passcode_throttle.check(&room.id, user_id)?;
+
+        let Some(passcode) = passcode else {
+            return Err(AppError::Unauthorized(format!(
+                "Room {} requires a passcode",
+                room.id
+            )));
+        };
+
+        if !utils::verify_password_hash(passcode_hash, passcode.trim()) {
+            self.passcode_throttle.record_failure(&room.id, user_id);
+            return Err(AppError::Unauthorized("Invalid room passcode".into()));
+        }
+
+        self.passcode_throttle.record_success(&room.id, user_id);
+        Ok(())
+    }
+
+    fn ensure_not_archived(room: &Room) -> Result<(), AppError> {
+        if room.is_archived() {
+            return Err(AppError::Validation(format!(
+                "Room {} is archived",
+                room.id
+            )));
+        }
+        Ok(())
+    }
+
+    fn ensure_direct_member(room: &Room, user_id: i32) -> Result<(), AppError> {
+        if !room.allows_direct_member(user_id) {
+            return Err(AppError::Unauthorized(
+                "Direct rooms are limited to the two users they were created for".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// `invited` is true for owners creating the room and members added by
+    /// an invitation, the only ways into a private room.
+    async fn ensure_room_has_space(&self, room: &Room, invited: bool) -> Result<(), AppError> {
+        let count = self
+            .repo
+            .count_active_members(&room.id)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        if count >= room.max_participants as i64 {
+            return Err(AppError::Validation(format!(
+                "Room {} is full (max {})",
+                room.id, room.max_participants
+            )));
+        }
+
+        if room.room_type == RoomType::OneOnOne && count >= 2 {
+            return Err(AppError::Validation(
+                "OneOnOne room limited to 2 participants".into(),
+            ));
+        }
+        if room.room_type == RoomType::Private && !invited {
+            return Err(AppError::Unauthorized(
+                "Private rooms require an invitation".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn add_member(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        role: RoomMemberRole,
+    ) -> Result<(), AppError> {
+        self.repo
+            .join_room(room_id, user_id)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        if role != RoomMemberRole::Participant {
+            self.repo
+                .update_member_role(room_id, user_id, role)
+                .await
+                .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        Ok(())
     }
 }
 
@@ -63,6 +324,7 @@ impl RoomService for RoomServiceImpl {
         room_type: String,
         created_by: i32,
         description: Option<String>,
+        passcode: Option<String>,
     ) -> Result<Room, AppError> {
         if name.trim().is_empty() {
             return Err(AppError::Validation("Room name cannot be empty".into()));
@@ -70,6 +332,18 @@ impl RoomService for RoomServiceImpl {
 
         let room_type = room_type.parse::<RoomType>()?;
 
+        let passcode_hash = match passcode {
+            Some(passcode) => {
+                if !room_type.supports_passcode() {
+                    return Err(AppError::Validation(
+                        "Passcodes are only supported for meeting and group rooms".into(),
+                    ));
+                }
+                Some(Self::hash_passcode(&passcode)?)
+            }
+            None => None,
+        };
+
         let max_participants = match room_type {
             RoomType::OneOnOne => 2,
             RoomType::Private | RoomType::Instant => 10,
@@ -79,13 +353,13 @@ impl RoomService for RoomServiceImpl {
 
         let mut room = self
             .repo
-            .create(name, room_type, created_by, description)
+            .create(name, room_type, created_by, description, passcode_hash)
             .await
             .map_err(|e| AppError::Database(e.to_string()))?;
 
         room.max_participants = max_participants;
 
-        self.join_room_with_role(&room.id, created_by, RoomMemberRole::Owner)
+        self.join_room_with_role(&room.id, created_by, RoomMemberRole::Owner, None)
             .await?;
 
         Ok(room)
@@ -98,18 +372,93 @@ impl RoomService for RoomServiceImpl {
             .map_err(|e| AppError::Database(e.to_string()))
     }
 
-    async fn list_rooms(&self, limit: i64, offset: i64) -> Result<Vec<Room>, AppError> {
-        if limit <= 0 || offset < 0 {
-            return Err(AppError::Validation("Invalid limit or offset".into()));
+    async fn list_rooms(
+        &self,
+        viewer_id: Option<i32>,
+        params: RoomSearchParams,
+    ) -> Result<RoomPage, AppError> {
+        let limit = params.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+            return Err(AppError::Validation(format!(
+                "Limit must be between 1 and {}",
+                MAX_PAGE_SIZE
+            )));
         }
-        self.repo
-            .list_rooms(limit, offset)
-            .await
-            .map_err(|e| AppError::Database(e.to_string()))
+
+        let member_only = params.member.unwrap_or(false);
+        if member_only && viewer_id.is_none() {
+            return Err(AppError::Unauthorized(
+                "Sign in to list the rooms you are a member of".into(),
+            ));
+        }
+
+        if let (Some(from), Some(to)) = (params.created_from, params.created_to)
+            && from >= to
+        {
+            return Err(AppError::Validation(
+                "created_from must be before created_to".into(),
+            ));
+        }
+
+        let sort = params
+            .sort
+            .as_deref()
+            .map(str::parse::<RoomSortField>)
+            .transpose()?
+            .unwrap_or_default();
+
+        let direction = match params.order.as_deref() {
+            Some(order) => order.parse::<SortDirection>()?,
+            None if sort == RoomSortField::Name => SortDirection::Asc,
+            None => SortDirection::Desc,
+        };
+
+        let after = params
+            .cursor
+            .as_deref()
+            .map(|cursor| RoomCursor::decode(cursor, sort))
+            .transpose()?;
+
+        let search = RoomSearch {
+            viewer_id,
+            room_type: params
+                .room_type
+                .as_deref()
+                .map(str::parse::<RoomType>)
+                .transpose()?,
+            name: params
+                .name
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty()),
+            member_only,
+            archived: params.archived.unwrap_or(false),
+            has_active_call: params.has_active_call,
+            created_by: params.created_by,
+            created_from: params.created_from,
+            created_to: params.created_to,
+            sort,
+            direction,
+            // Fetch one extra row to know whether another page exists
+            limit: limit + 1,
+            after,
+        };
+
+        let mut rooms = self.repo.list_rooms(&search).await?;
+
+        let next_cursor = if rooms.len() as i64 > limit {
+            rooms.truncate(limit as usize);
+            rooms
+                .last()
+                .map(|room| RoomCursor::after(room, sort).encode())
+        } else {
+            None
+        };
+
+        Ok(RoomPage { rooms, next_cursor })
     }
 
     async fn delete_room(&self, room_id: &str, user_id: i32) -> Result<(), AppError> {
-        let _room = self
+        let room = self
             .get_room(room_id)
             .await?
             .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
@@ -120,20 +469,133 @@ impl RoomService for RoomServiceImpl {
             ));
         }
 
+        if room.is_archived() {
+            return Err(AppError::Validation(format!(
+                "Room {} is already archived",
+                room_id
+            )));
+        }
+
+        self.repo
+            .archive(room_id)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        self.passcode_throttle.reset(room_id);
+
+        Ok(())
+    }
+
+    async fn restore_room(&self, room_id: &str, user_id: i32) -> Result<Room, AppError> {
+        let room = self
+            .get_room(room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
+
+        if !self.is_user_owner(room_id, user_id).await? {
+            return Err(AppError::Unauthorized(
+                "Only room owner can restore the room".into(),
+            ));
+        }
+
+        if !room.is_archived() {
+            return Err(AppError::Validation(format!(
+                "Room {} is not archived",
+                room_id
+            )));
+        }
+
+        self.repo
+            .restore(room_id)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        self.get_room(room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))
+    }
+
+    async fn purge_room(&self, room_id: &str) -> Result<(), AppError> {
+        let _room = self
+            .get_room(room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
+
         self.repo
-            .delete(room_id)
+            .purge(room_id)
             .await
             .map_err(|e| AppError::Database(e.to_string()))?;
 
+        self.passcode_throttle.reset(room_id);
+
         Ok(())
     }
 
-    async fn join_room(&self, room_id: &str, user_id: i32) -> Result<(), AppError> {
-        self.join_room_with_role(room_id, user_id, RoomMemberRole::Participant)
+    async fn get_or_create_direct_room(
+        &self,
+        user_id: i32,
+        other_user_id: i32,
+        name: String,
+    ) -> Result<Room, AppError> {
+        let direct_key = Room::direct_key_for(user_id, other_user_id);
+
+        let room = match self
+            .repo
+            .get_by_direct_key(&direct_key)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?
+        {
+            Some(room) => room,
+            None => self
+                .repo
+                .create_direct(name, user_id, &direct_key)
+                .await
+                .map_err(|e| AppError::Database(e.to_string()))?,
+        };
+
+        if room.is_archived() {
+            self.repo
+                .restore(&room.id)
+                .await
+                .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        for (member_id, partner_id) in [(user_id, other_user_id), (other_user_id, user_id)] {
+            if self.is_user_in_room(&room.id, member_id).await? {
+                continue;
+            }
+
+            // The creator gets ownership back unless it already passed to the partner
+            let role = if member_id == room.created_by
+                && !self.is_user_owner(&room.id, partner_id).await?
+            {
+                RoomMemberRole::Owner
+            } else {
+                RoomMemberRole::Participant
+            };
+            self.add_member(&room.id, member_id, role).await?;
+        }
+
+        self.get_room(&room.id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room.id)))
+    }
+
+    async fn join_room(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        passcode: Option<&str>,
+    ) -> Result<(), AppError> {
+        self.join_room_with_role(room_id, user_id, RoomMemberRole::Participant, passcode)
             .await
     }
 
-    async fn leave_room(&self, room_id: &str, user_id: i32) -> Result<(), AppError> {
+    async fn leave_room(
+        &self,
+        room_id: &str,
+        user_id: i32,
+    ) -> Result<Option<OwnershipTransfer>, AppError> {
         if !self.is_user_in_room(room_id, user_id).await? {
             return Err(AppError::NotFound(format!(
                 "User {} not in room {}",
@@ -141,6 +603,8 @@ impl RoomService for RoomServiceImpl {
             )));
         }
 
+        let was_owner = self.is_user_owner(room_id, user_id).await?;
+
         self.repo
             .leave_room(room_id, user_id)
             .await
@@ -158,13 +622,75 @@ impl RoomService for RoomServiceImpl {
                 .map_err(|e| AppError::Database(e.to_string()))?;
             if count == 0 {
                 self.repo
-                    .delete(room_id)
+                    .archive(room_id)
                     .await
                     .map_err(|e| AppError::Database(e.to_string()))?;
             }
         }
 
-        Ok(())
+        if !was_owner {
+            return Ok(None);
+        }
+
+        let Some(successor) = self
+            .repo
+            .find_successor(room_id)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+
+        self.repo
+            .update_member_role(room_id, successor, RoomMemberRole::Owner)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(Some(OwnershipTransfer {
+            room_id: room_id.to_string(),
+            previous_owner_id: user_id,
+            new_owner_id: successor,
+        }))
+    }
+
+    async fn transfer_ownership(
+        &self,
+        room_id: &str,
+        owner_id: i32,
+        new_owner_id: i32,
+    ) -> Result<OwnershipTransfer, AppError> {
+        let _room = self
+            .get_room(room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
+
+        if !self.is_user_owner(room_id, owner_id).await? {
+            return Err(AppError::Unauthorized(
+                "Only room owner can transfer ownership".into(),
+            ));
+        }
+
+        if owner_id == new_owner_id {
+            return Err(AppError::Validation("User already owns this room".into()));
+        }
+
+        if !self.is_user_in_room(room_id, new_owner_id).await? {
+            return Err(AppError::Validation(format!(
+                "User {} is not a member of room {}",
+                new_owner_id, room_id
+            )));
+        }
+
+        self.repo
+            .transfer_ownership(room_id, owner_id, new_owner_id)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        Ok(OwnershipTransfer {
+            room_id: room_id.to_string(),
+            previous_owner_id: owner_id,
+            new_owner_id,
+        })
     }
 
     async fn list_room_users(&self, room_id: &str) -> Result<Vec<User>, AppError> {
@@ -174,6 +700,59 @@ impl RoomService for RoomServiceImpl {
             .map_err(|e| AppError::Database(e.to_string()))
     }
 
+    async fn list_members(
+        &self,
+        room_id: &str,
+        viewer_id: i32,
+        params: MemberListParams,
+    ) -> Result<RoomMemberPage, AppError> {
+        let limit = params.limit.unwrap_or(DEFAULT_MEMBER_PAGE_SIZE);
+        if !(1..=MAX_MEMBER_PAGE_SIZE).contains(&limit) {
+            return Err(AppError::Validation(format!(
+                "Limit must be between 1 and {}",
+                MAX_MEMBER_PAGE_SIZE
+            )));
+        }
+
+        if self.get_room(room_id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Room {} not found", room_id)));
+        }
+
+        if !self.is_user_in_room(room_id, viewer_id).await? {
+            return Err(AppError::Unauthorized(format!(
+                "User {} is not a member of room {}",
+                viewer_id, room_id
+            )));
+        }
+
+        let after = params
+            .cursor
+            .as_deref()
+            .map(MemberCursor::decode)
+            .transpose()?;
+
+        // Fetch one extra row to know whether another page exists
+        let mut members = self
+            .repo
+            .list_members(room_id, after.as_ref(), limit + 1)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let next_cursor = if members.len() as i64 > limit {
+            members.truncate(limit as usize);
+            members
+                .last()
+                .map(|member| MemberCursor::after(member).encode())
+        } else {
+            None
+        };
+
+        Ok(RoomMemberPage {
+            members,
+            next_cursor,
+        })
+    }
+
     async fn is_user_in_room(&self, room_id: &str, user_id: i32) -> Result<bool, AppError> {
         self.repo
             .is_user_in_room(room_id, user_id)
@@ -193,52 +772,393 @@ impl RoomService for RoomServiceImpl {
         room_id: &str,
         user_id: i32,
         role: RoomMemberRole,
+        passcode: Option<&str>,
     ) -> Result<(), AppError> {
         let room = self
             .get_room(room_id)
             .await?
             .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
 
+        Self::ensure_not_archived(&room)?;
+        Self::ensure_direct_member(&room, user_id)?;
+
         if self.is_user_in_room(room_id, user_id).await? {
             return Ok(());
         }
 
-        let count = self
+        if role != RoomMemberRole::Owner {
+            self.verify_passcode(&room, user_id, passcode)?;
+        }
+
+        self.ensure_room_has_space(&room, role == RoomMemberRole::Owner)
+            .await?;
+        self.add_member(room_id, user_id, role).await
+    }
+
+    async fn verify_room_access(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        passcode: Option<&str>,
+    ) -> Result<Room, AppError> {
+        let room = self
+            .get_room(room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
+
+        Self::ensure_not_archived(&room)?;
+        Self::ensure_direct_member(&room, user_id)?;
+
+        if self.is_user_in_room(room_id, user_id).await? {
+            return Ok(room);
+        }
+
+        self.verify_passcode(&room, user_id, passcode)?;
+        self.ensure_room_has_space(&room, false).await?;
+
+        Ok(room)
+    }
+
+    async fn admit_member(&self, room_id: &str, user_id: i32) -> Result<(), AppError> {
+        let room = self
+            .get_room(room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
+
+        Self::ensure_not_archived(&room)?;
+        Self::ensure_direct_member(&room, user_id)?;
+
+        if self.is_user_in_room(room_id, user_id).await? {
+            return Ok(());
+        }
+
+        self.ensure_room_has_space(&room, false).await?;
+        self.add_member(room_id, user_id, RoomMemberRole::Participant)
+            .await
+    }
+
+    async fn get_member_role(
+        &self,
+        room_id: &str,
+        user_id: i32,
+    ) -> Result<Option<RoomMemberRole>, AppError> {
+        let member = self
             .repo
-            .count_active_members(room_id)
+            .get_member(room_id, user_id)
             .await
             .map_err(|e| AppError::Database(e.to_string()))?;
 
-        if count >= room.max_participants as i64 {
-            return Err(AppError::Validation(format!(
-                "Room {} is full (max {})",
-                room_id, room.max_participants
-            )));
-        }
+        Ok(member.filter(|m| m.left_at.is_none()).map(|m| m.role))
+    }
 
-        if room.room_type == RoomType::OneOnOne && count >= 2 {
+    async fn is_user_moderator(&self, room_id: &str, user_id: i32) -> Result<bool, AppError> {
+        Ok(matches!(
+            self.get_member_role(room_id, user_id).await?,
+            Some(RoomMemberRole::Owner | RoomMemberRole::Moderator)
+        ))
+    }
+
+    async fn set_passcode(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        passcode: Option<String>,
+    ) -> Result<(), AppError> {
+        let room = self.get_owned_room(room_id, user_id).await?;
+
+        let passcode_hash = match passcode {
+            Some(passcode) => {
+                if !room.room_type.supports_passcode() {
+                    return Err(AppError::Validation(
+                        "Passcodes are only supported for meeting and group rooms".into(),
+                    ));
+                }
+                Some(Self::hash_passcode(&passcode)?)
+            }
+            None => None,
+        };
+
+        self.repo
+            .update_passcode(room_id, passcode_hash)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        self.passcode_throttle.reset(room_id);
+
+        Ok(())
+    }
+
+    async fn rotate_passcode(&self, room_id: &str, user_id: i32) -> Result<String, AppError> {
+        let room = self.get_owned_room(room_id, user_id).await?;
+
+        if !room.room_type.supports_passcode() {
             return Err(AppError::Validation(
-                "OneOnOne room limited to 2 participants".into(),
+                "Passcodes are only supported for meeting and group rooms".into(),
             ));
         }
-        if room.room_type == RoomType::Private && role != RoomMemberRole::Owner {
+
+        let passcode: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(GENERATED_PASSCODE_LENGTH)
+            .map(char::from)
+            .collect();
+
+        self.repo
+            .update_passcode(room_id, Some(Self::hash_passcode(&passcode)?))
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        self.passcode_throttle.reset(room_id);
+
+        Ok(passcode)
+    }
+
+    async fn update_policies(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        params: UpdatePoliciesParams,
+    ) -> Result<Room, AppError> {
+        let room = self
+            .get_room(room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
+
+        Self::ensure_not_archived(&room)?;
+
+        if !self.is_user_owner(room_id, user_id).await? {
             return Err(AppError::Unauthorized(
-                "Private rooms require an invitation".into(),
+                "Only room owner can change room policies".into(),
             ));
         }
 
+        let mut policies = room.policies.clone();
+        for (value, policy) in [
+            (params.chat, &mut policies.chat),
+            (params.unmute, &mut policies.unmute),
+            (params.screen_share, &mut policies.screen_share),
+            (params.start_call, &mut policies.start_call),
+            (params.invite, &mut policies.invite),
+        ] {
+            if let Some(value) = value {
+                *policy = value.parse::<RoomMemberRole>()?;
+            }
+        }
+
         self.repo
-            .join_room(room_id, user_id)
+            .update_policies(room_id, &policies)
             .await
             .map_err(|e| AppError::Database(e.to_string()))?;
 
-        if role != RoomMemberRole::Participant {
-            self.repo
-                .update_member_role(room_id, user_id, role)
-                .await
-                .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(Room { policies, ..room })
+    }
+
+    async fn ensure_permitted(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        action: RoomAction,
+    ) -> Result<(), AppError> {
+        let room = self
+            .get_room(room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
+
+        let role = self
+            .get_member_role(room_id, user_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::Unauthorized(format!(
+                    "User {} is not a member of room {}",
+                    user_id, room_id
+                ))
+            })?;
+
+        if !room.policies.allows(action, &role) {
+            return Err(AppError::Unauthorized(format!(
+                "Your role in room {} does not allow you to {}",
+                room_id,
+                action.describe()
+            )));
         }
 
         Ok(())
     }
+
+    async fn invite_member(
+        &self,
+        room_id: &str,
+        inviter_id: i32,
+        invitee_id: i32,
+    ) -> Result<(), AppError> {
+        let room = self
+            .get_room(room_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Room {} not found", room_id)))?;
+
+        Self::ensure_not_archived(&room)?;
+        Self::ensure_direct_member(&room, invitee_id)?;
+        self.ensure_permitted(room_id, inviter_id, RoomAction::Invite)
+            .await?;
+
+        if self.is_user_in_room(room_id, invitee_id).await? {
+            return Ok(());
+        }
+
+        self.ensure_room_has_space(&room, true).await?;
+        self.add_member(room_id, invitee_id, RoomMemberRole::Participant)
+            .await
+    }
+
+    async fn update_media_state(
+        &self,
+        room_id: &str,
+        user_id: i32,
+        audio_enabled: bool,
+        video_enabled: bool,
+        screen_sharing: bool,
+    ) -> Result<(), AppError> {
+        if audio_enabled {
+            self.ensure_permitted(room_id, user_id, RoomAction::Unmute)
+                .await?;
+        }
+        if screen_sharing {
+            self.ensure_permitted(room_id, user_id, RoomAction::ScreenShare)
+                .await?;
+        }
+
+        self.repo
+            .update_media_state(room_id, user_id, !audio_enabled, video_enabled)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    async fn list_session_room_members(
+        &self,
+        min_age_secs: i64,
+    ) -> Result<Vec<RoomMember>, AppError> {
+        self.repo
+            .list_session_room_members(min_age_secs)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    async fn archive_empty_instant_rooms(&self) -> Result<u64, AppError> {
+        self.repo
+            .archive_empty_instant_rooms()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rooms::SqliteRoomRepository,
+        shared::test_db::{self, TEST_ROOM_ID},
+    };
+
+    async fn room_service() -> RoomServiceImpl {
+        let pool = test_db::memory_pool().await;
+        RoomServiceImpl::new(Arc::new(SqliteRoomRepository::new(pool)))
+    }
+
+    #[tokio::test]
+    async fn test_archived_room_can_be_restored_by_its_owner_and_purged() {
+        let service = room_service().await;
+
+        assert!(matches!(
+            service.delete_room(TEST_ROOM_ID, 2).await,
+            Err(AppError::Unauthorized(_))
+        ));
+        service.delete_room(TEST_ROOM_ID, 1).await.unwrap();
+        let room = service.get_room(TEST_ROOM_ID).await.unwrap().unwrap();
+        assert!(room.is_archived());
+        assert!(matches!(
+            service.delete_room(TEST_ROOM_ID, 1).await,
+            Err(AppError::Validation(_))
+        ));
+        assert!(service.join_room(TEST_ROOM_ID, 2, None).await.is_err());
+
+        assert!(matches!(
+            service.restore_room(TEST_ROOM_ID, 2).await,
+            Err(AppError::Unauthorized(_))
+        ));
+        let room = service.restore_room(TEST_ROOM_ID, 1).await.unwrap();
+        assert!(!room.is_archived());
+        service.join_room(TEST_ROOM_ID, 2, None).await.unwrap();
+
+        service.purge_room(TEST_ROOM_ID).await.unwrap();
+        assert!(service.get_room(TEST_ROOM_ID).await.unwrap().is_none());
+        assert!(!service.is_user_in_room(TEST_ROOM_ID, 2).await.unwrap());
+        assert!(matches!(
+            service.purge_room(TEST_ROOM_ID).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_ownership_passes_to_moderators_before_participants() {
+        let service = room_service().await;
+        service.join_room(TEST_ROOM_ID, 2, None).await.unwrap();
+        service
+            .join_room_with_role(TEST_ROOM_ID, 3, RoomMemberRole::Moderator, None)
+            .await
+            .unwrap();
+
+        let transfer = service.leave_room(TEST_ROOM_ID, 1).await.unwrap().unwrap();
+        assert_eq!((transfer.previous_owner_id, transfer.new_owner_id), (1, 3));
+        assert!(service.is_user_owner(TEST_ROOM_ID, 3).await.unwrap());
+
+        assert!(matches!(
+            service.transfer_ownership(TEST_ROOM_ID, 2, 2).await,
+            Err(AppError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            service.transfer_ownership(TEST_ROOM_ID, 3, 1).await,
+            Err(AppError::Validation(_))
+        ));
+
+        service
+            .transfer_ownership(TEST_ROOM_ID, 3, 2)
+            .await
+            .unwrap();
+        assert!(service.is_user_owner(TEST_ROOM_ID, 2).await.unwrap());
+        assert!(!service.is_user_owner(TEST_ROOM_ID, 3).await.unwrap());
+
+        let transfer = service.leave_room(TEST_ROOM_ID, 2).await.unwrap().unwrap();
+        assert_eq!(transfer.new_owner_id, 3);
+        assert!(service.leave_room(TEST_ROOM_ID, 3).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_direct_room_is_shared_and_reused_after_archiving() {
+        let service = room_service().await;
+
+        let room = service
+            .get_or_create_direct_room(1, 2, "Chat".to_string())
+            .await
+            .unwrap();
+        let again = service
+            .get_or_create_direct_room(2, 1, "Chat".to_string())
+            .await
+            .unwrap();
+        assert_eq!(room.id, again.id);
+        assert!(service.is_user_owner(&room.id, 1).await.unwrap());
+        assert!(service.is_user_in_room(&room.id, 2).await.unwrap());
+        assert!(service.join_room(&room.id, 3, None).await.is_err());
+
+        service.leave_room(&room.id, 2).await.unwrap();
+        service.delete_room(&room.id, 1).await.unwrap();
+
+        let reused = service
+            .get_or_create_direct_room(2, 1, "Chat".to_string())
+            .await
+            .unwrap();
+        assert_eq!(reused.id, room.id);
+        assert!(!reused.is_archived());
+        assert!(service.is_user_in_room(&room.id, 2).await.unwrap());
+        assert!(service.is_user_owner(&room.id, 1).await.unwrap());
+    }
 }
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
 use uuid::Uuid;
 
 #[async_trait]
@@ -24,18 +25,34 @@ pub trait FileService: Send + Sync {
         &self,
         filename: &str,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Writes `file_data` to `filename` under the upload directory, creating
+    /// missing directories and replacing any existing file.
+    async fn save_file(
+        &self,
+        filename: &str,
+        file_data: Vec<u8>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Appends `file_data` to `filename`, creating the file if needed.
+    async fn append_file(
+        &self,
+        filename: &str,
+        file_data: Vec<u8>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Where `filename` is stored, for responses that stream it from disk.
+    fn file_path(&self, filename: &str) -> PathBuf;
 }
 
 pub struct LocalFileService {
     upload_dir: PathBuf,
-    base_url: String,
 }
 
 impl LocalFileService {
-    pub fn new(upload_dir: impl Into<PathBuf>, base_url: String) -> Self {
+    pub fn new(upload_dir: impl Into<PathBuf>) -> Self {
         Self {
             upload_dir: upload_dir.into(),
-            base_url,
         }
     }
 }
@@ -100,4 +117,44 @@ impl FileService for LocalFileService {
 
         Ok(())
     }
+
+    async fn save_file(
+        &self,
+        filename: &str,
+        file_data: Vec<u8>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let filepath = self.upload_dir.join(filename);
+        if let Some(parent) = filepath.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        tokio::fs::write(&filepath, file_data).await?;
+
+        Ok(())
+    }
+
+    async fn append_file(
+        &self,
+        filename: &str,
+        file_data: Vec<u8>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let filepath = self.upload_dir.join(filename);
+        if let Some(parent) = filepath.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&filepath)
+            .await?;
+        file.write_all(&file_data).await?;
+        file.flush().await?;
+
+        Ok(())
+    }
+
+    fn file_path(&self, filename: &str) -> PathBuf {
+        self.upload_dir.join(filename)
+    }
 }
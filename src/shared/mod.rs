@@ -1,4 +1,6 @@
 pub mod base_types;
 pub mod file_service;
 pub mod response;
+#[cfg(test)]
+pub mod test_db;
 pub mod utils;
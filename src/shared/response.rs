@@ -64,6 +64,8 @@ pub enum AppError {
 
     Unauthorized(String),
 
+    TooManyRequests(String),
+
     Database(String),
 }
 
@@ -75,6 +77,7 @@ impl Display for AppError {
             AppError::NotFound(msg) => write!(f, "Not found: {}", msg),
             AppError::BadRequest(msg) => write!(f, "Bad request: {}", msg),
             AppError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
+            AppError::TooManyRequests(msg) => write!(f, "Too many requests: {}", msg),
             AppError::Database(msg) => write!(f, "Database error: {}", msg),
         }
     }
@@ -88,6 +91,7 @@ impl ResponseError for AppError {
             AppError::Validation(_) | AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
             AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
             AppError::InternalServerError(_) | AppError::Database(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
@@ -100,6 +104,7 @@ impl ResponseError for AppError {
             AppError::Validation(msg)
             | AppError::BadRequest(msg)
             | AppError::Unauthorized(msg)
+            | AppError::TooManyRequests(msg)
             | AppError::Database(msg)
             | AppError::InternalServerError(msg) => ApiResponse::<()>::error(msg.clone()),
         };
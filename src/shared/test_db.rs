@@ -0,0 +1,51 @@
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+
+/// The group room every test database starts with, owned by user 1.
+pub const TEST_ROOM_ID: &str = "test-room";
+
+/// A fresh, fully migrated in-memory database with users 1 to 3 and
+/// `TEST_ROOM_ID`. The pool keeps its single connection open, as the
+/// database disappears with it.
+pub async fn memory_pool() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to open in-memory database");
+
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("Failed to run database migrations");
+
+    for id in 1..=3 {
+        sqlx::query(
+            "INSERT INTO users (id, first_name, last_name, email, phone, avatar_url, password) \
+             VALUES ($1, 'User', $2, $3, $4, '', '')",
+        )
+        .bind(id)
+        .bind(id.to_string())
+        .bind(format!("user{}@example.com", id))
+        .bind(format!("98000000{:02}", id))
+        .execute(&pool)
+        .await
+        .expect("Failed to insert test user");
+    }
+
+    sqlx::query(
+        "INSERT INTO rooms (id, name, room_type, created_by) VALUES ($1, 'Test', 'group', 1)",
+    )
+    .bind(TEST_ROOM_ID)
+    .execute(&pool)
+    .await
+    .expect("Failed to insert test room");
+    sqlx::query("INSERT INTO room_members (room_id, user_id, role) VALUES ($1, 1, 'owner')")
+        .bind(TEST_ROOM_ID)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert test room owner");
+
+    pool
+}
@@ -14,6 +14,7 @@ pub trait UserRepository {
     async fn get_by_email(&self, email: &str) -> Result<Option<UserWithPassword>, AppError>;
     async fn get_by_phone(&self, phone: &str) -> Result<Option<UserWithPassword>, AppError>;
     async fn update_avatar(&self, user_id: i32, avatar_url: &str) -> Result<User, AppError>;
+    async fn is_admin(&self, user_id: i32) -> Result<bool, AppError>;
 }
 
 // Concrete implementation
@@ -140,4 +141,13 @@ impl UserRepository for SqliteUserRepository {
 
         Ok(user)
     }
+
+    async fn is_admin(&self, user_id: i32) -> Result<bool, AppError> {
+        let is_admin = sqlx::query_scalar("SELECT is_admin FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(is_admin.unwrap_or(false))
+    }
 }
@@ -35,6 +35,8 @@ pub trait UserService: Send + Sync {
     ) -> Result<User, AppError>;
 
     async fn authenticate(&self, email: &str, password: &str) -> Result<User, AppError>;
+
+    async fn is_admin(&self, user_id: i32) -> Result<bool, AppError>;
 }
 
 pub struct UserServiceImpl {
@@ -194,4 +196,8 @@ impl UserService for UserServiceImpl {
 
         Ok(user)
     }
+
+    async fn is_admin(&self, user_id: i32) -> Result<bool, AppError> {
+        self.repository.is_admin(user_id).await
+    }
 }